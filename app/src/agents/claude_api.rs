@@ -0,0 +1,589 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use crate::core::config::ChatConfig;
+
+/// Base URL of the hosted API; other URLs (a local mock server) may run without a key
+pub const DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
+
+/// Keychain entry the API key is stored under
+const KEYRING_SERVICE: &str = "zac-caret";
+const KEYRING_USER: &str = "anthropic_api_key";
+
+/// A conversation stops calling tools after this many rounds
+const MAX_TOOL_ROUNDS: usize = 8;
+
+/// One turn of the conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Message {
+    pub role: String,
+    pub content: Vec<ContentBlock>,
+}
+
+impl Message {
+    pub fn user(text: &str) -> Self {
+        Self {
+            role: "user".to_string(),
+            content: vec![ContentBlock::Text { text: text.to_string() }],
+        }
+    }
+
+    /// Whether this is a user message with typed text rather than tool results
+    pub fn is_user_text(&self) -> bool {
+        self.role == "user" && self.content.iter().any(|block| matches!(block, ContentBlock::Text { .. }))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentBlock {
+    Text {
+        text: String,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+        input: Value,
+    },
+    ToolResult {
+        tool_use_id: String,
+        content: String,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        is_error: bool,
+    },
+}
+
+/// A tool the model may call
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolDefinition {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub input_schema: Value,
+}
+
+/// Tokens reported for one request
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct Usage {
+    #[serde(default)]
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub cache_creation_input_tokens: u64,
+    #[serde(default)]
+    pub cache_read_input_tokens: u64,
+}
+
+/// What a conversation reports while it runs
+#[derive(Debug, Clone)]
+pub enum StreamEvent {
+    /// Next piece of the reply text
+    Text(String),
+    /// The model called a tool
+    ToolUse { name: String, input: Value },
+    /// Tokens of a finished request
+    Usage(Usage),
+}
+
+/// Streaming Messages API client
+#[derive(Debug, Clone)]
+pub struct ClaudeClient {
+    base_url: String,
+    api_key: Option<String>,
+    api_version: String,
+    pub model: String,
+    max_tokens: u32,
+}
+
+impl ClaudeClient {
+    /// Client for the configured endpoint; the hosted API needs a key from
+    /// `ANTHROPIC_API_KEY` or the OS keychain
+    pub fn from_config(config: &ChatConfig) -> Result<Self, String> {
+        let base_url = config.base_url.trim_end_matches('/').to_string();
+        let api_key = load_api_key();
+
+        if api_key.is_none() && base_url == DEFAULT_BASE_URL {
+            return Err("No API key - set ANTHROPIC_API_KEY or type /key <your key> in the chat".to_string());
+        }
+
+        Ok(Self {
+            base_url,
+            api_key,
+            api_version: config.api_version.clone(),
+            model: config.model.clone(),
+            max_tokens: config.max_tokens,
+        })
+    }
+
+    /// Run a conversation turn: stream the reply, answer tool calls with
+    /// `run_tool` and continue until the model stops calling tools.
+    /// The assistant turns and tool results are appended to `messages`.
+    pub async fn converse(
+        &self,
+        system: &str,
+        messages: &mut Vec<Message>,
+        tools: &[ToolDefinition],
+        mut on_event: impl FnMut(StreamEvent),
+        mut run_tool: impl FnMut(&str, &Value) -> Result<String, String>,
+    ) -> Result<(), String> {
+        for _ in 0..MAX_TOOL_ROUNDS {
+            let reply = self.stream_message(system, messages, tools, &mut on_event).await?;
+
+            let tool_calls: Vec<(String, String, Value)> = reply.content.iter()
+                .filter_map(|block| match block {
+                    ContentBlock::ToolUse { id, name, input } => Some((id.clone(), name.clone(), input.clone())),
+                    _ => None,
+                })
+                .collect();
+
+            messages.push(Message { role: "assistant".to_string(), content: reply.content });
+
+            if reply.stop_reason.as_deref() != Some("tool_use") || tool_calls.is_empty() {
+                return Ok(());
+            }
+
+            let results = tool_calls.into_iter()
+                .map(|(id, name, input)| {
+                    on_event(StreamEvent::ToolUse { name: name.clone(), input: input.clone() });
+                    let (content, is_error) = match run_tool(&name, &input) {
+                        Ok(output) => (output, false),
+                        Err(e) => (e, true),
+                    };
+                    ContentBlock::ToolResult { tool_use_id: id, content, is_error }
+                })
+                .collect();
+
+            messages.push(Message { role: "user".to_string(), content: results });
+        }
+
+        Err(format!("Stopped after {MAX_TOOL_ROUNDS} rounds of tool calls"))
+    }
+
+    /// Send one streaming request and assemble the reply
+    async fn stream_message(
+        &self,
+        system: &str,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        on_event: &mut impl FnMut(StreamEvent),
+    ) -> Result<AssistantReply, String> {
+        let body = json!({
+            "model": self.model,
+            "max_tokens": self.max_tokens,
+            "system": system,
+            "messages": messages,
+            "tools": tools,
+            "stream": true,
+        });
+
+        let mut request = reqwest::Client::new()
+            .post(format!("{}/v1/messages", self.base_url))
+            .header("anthropic-version", &self.api_version)
+            .json(&body);
+        if let Some(key) = &self.api_key {
+            request = request.header("x-api-key", key);
+        }
+
+        let mut response = request.send().await
+            .map_err(|e| format!("Claude API request error: {e}"))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(format!("Claude API error {status}: {}", api_error_message(&text)));
+        }
+
+        let mut parser = SseParser::default();
+        let mut reply = ReplyBuilder::default();
+
+        while let Some(chunk) = response.chunk().await.map_err(|e| format!("Claude API stream error: {e}"))? {
+            for (event, data) in parser.push(&chunk) {
+                reply.apply(&event, &data, on_event)?;
+            }
+        }
+
+        reply.finish()
+    }
+}
+
+/// API key from the environment, then the OS keychain
+fn load_api_key() -> Option<String> {
+    if let Ok(key) = std::env::var("ANTHROPIC_API_KEY") {
+        if !key.trim().is_empty() {
+            return Some(key.trim().to_string());
+        }
+    }
+
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .and_then(|entry| entry.get_password())
+        .ok()
+        .filter(|key| !key.trim().is_empty())
+}
+
+/// Keep the API key in the OS keychain for later sessions
+pub fn store_api_key(key: &str) -> Result<(), String> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .and_then(|entry| entry.set_password(key.trim()))
+        .map_err(|e| format!("Keychain error: {e}"))
+}
+
+/// `error.message` of an API error body, or the body itself
+fn api_error_message(body: &str) -> String {
+    serde_json::from_str::<Value>(body).ok()
+        .and_then(|v| v["error"]["message"].as_str().map(str::to_string))
+        .unwrap_or_else(|| body.trim().to_string())
+}
+
+/// Splits a server-sent event stream into `(event, data)` pairs
+#[derive(Default)]
+struct SseParser {
+    /// Bytes of the event still being received, which may end mid-character
+    buffer: Vec<u8>,
+}
+
+impl SseParser {
+    /// Feed received bytes; returns the events completed by them
+    fn push(&mut self, bytes: &[u8]) -> Vec<(String, String)> {
+        self.buffer.extend(bytes.iter().filter(|b| **b != b'\r'));
+
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.windows(2).position(|w| w == b"\n\n") {
+            let raw: Vec<u8> = self.buffer.drain(..end + 2).collect();
+            let raw = String::from_utf8_lossy(&raw);
+
+            let mut event = String::from("message");
+            let mut data = Vec::new();
+            for line in raw.lines() {
+                if let Some(value) = line.strip_prefix("event:") {
+                    event = value.trim().to_string();
+                } else if let Some(value) = line.strip_prefix("data:") {
+                    data.push(value.strip_prefix(' ').unwrap_or(value));
+                }
+            }
+
+            if !data.is_empty() {
+                events.push((event, data.join("\n")));
+            }
+        }
+
+        events
+    }
+}
+
+/// Assistant turn as it came back from the API
+struct AssistantReply {
+    content: Vec<ContentBlock>,
+    stop_reason: Option<String>,
+}
+
+/// Content block still being streamed
+enum PartialBlock {
+    Text(String),
+    ToolUse { id: String, name: String, json: String },
+}
+
+/// Builds the reply from stream events
+#[derive(Default)]
+struct ReplyBuilder {
+    blocks: Vec<PartialBlock>,
+    stop_reason: Option<String>,
+    usage: Usage,
+}
+
+impl ReplyBuilder {
+    fn apply(&mut self, event: &str, data: &str, on_event: &mut impl FnMut(StreamEvent)) -> Result<(), String> {
+        let data: Value = serde_json::from_str(data)
+            .map_err(|e| format!("Claude API stream error: bad {event} event: {e}"))?;
+
+        match event {
+            "message_start" => {
+                if let Ok(usage) = serde_json::from_value(data["message"]["usage"].clone()) {
+                    self.usage = usage;
+                }
+            }
+            "content_block_start" => {
+                let block = &data["content_block"];
+                let partial = match block["type"].as_str() {
+                    Some("tool_use") => PartialBlock::ToolUse {
+                        id: block["id"].as_str().unwrap_or_default().to_string(),
+                        name: block["name"].as_str().unwrap_or_default().to_string(),
+                        json: String::new(),
+                    },
+                    _ => PartialBlock::Text(block["text"].as_str().unwrap_or_default().to_string()),
+                };
+                self.blocks.push(partial);
+            }
+            "content_block_delta" => {
+                let delta = &data["delta"];
+                match (self.blocks.last_mut(), delta["type"].as_str()) {
+                    (Some(PartialBlock::Text(text)), Some("text_delta")) => {
+                        let piece = delta["text"].as_str().unwrap_or_default();
+                        text.push_str(piece);
+                        on_event(StreamEvent::Text(piece.to_string()));
+                    }
+                    (Some(PartialBlock::ToolUse { json, .. }), Some("input_json_delta")) => {
+                        json.push_str(delta["partial_json"].as_str().unwrap_or_default());
+                    }
+                    _ => {}
+                }
+            }
+            "message_delta" => {
+                if let Some(reason) = data["delta"]["stop_reason"].as_str() {
+                    self.stop_reason = Some(reason.to_string());
+                }
+                if let Some(output) = data["usage"]["output_tokens"].as_u64() {
+                    self.usage.output_tokens = output;
+                }
+            }
+            "message_stop" => on_event(StreamEvent::Usage(self.usage)),
+            "error" => {
+                return Err(format!("Claude API error: {}", data["error"]["message"].as_str().unwrap_or("unknown")));
+            }
+            // ping, content_block_stop
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<AssistantReply, String> {
+        // The API rejects empty text blocks when they are sent back
+        let content = self.blocks.into_iter()
+            .filter(|block| !matches!(block, PartialBlock::Text(text) if text.is_empty()))
+            .map(|block| match block {
+                PartialBlock::Text(text) => Ok(ContentBlock::Text { text }),
+                PartialBlock::ToolUse { id, name, json } => {
+                    let input = if json.trim().is_empty() {
+                        json!({})
+                    } else {
+                        serde_json::from_str(&json).map_err(|e| format!("Bad input for tool {name}: {e}"))?
+                    };
+                    Ok(ContentBlock::ToolUse { id, name, input })
+                }
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(AssistantReply { content, stop_reason: self.stop_reason })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(events: &[(&str, &str)]) -> Result<AssistantReply, String> {
+        let mut builder = ReplyBuilder::default();
+        for (event, data) in events {
+            builder.apply(event, data, &mut |_| {})?;
+        }
+        builder.finish()
+    }
+
+    #[test]
+    fn sse_event_split_across_chunks() {
+        let mut parser = SseParser::default();
+
+        assert!(parser.push(b"event: content_block_delta\nda").is_empty());
+        assert!(parser.push(b"ta: {\"a\":1}\n").is_empty());
+
+        let events = parser.push(b"\nevent: ping\ndata: {}\n\n");
+        assert_eq!(events, vec![
+            ("content_block_delta".to_string(), "{\"a\":1}".to_string()),
+            ("ping".to_string(), "{}".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn sse_crlf_line_endings() {
+        let mut parser = SseParser::default();
+
+        assert!(parser.push(b"event: message_stop\r\ndata: {}\r").is_empty());
+        let events = parser.push(b"\n\r\n");
+        assert_eq!(events, vec![("message_stop".to_string(), "{}".to_string())]);
+    }
+
+    #[test]
+    fn sse_character_split_across_chunks() {
+        let mut parser = SseParser::default();
+        let bytes = "data: \"caf\u{e9}\"\n\n".as_bytes();
+        let split = bytes.len() - 3;
+
+        assert!(parser.push(&bytes[..split]).is_empty());
+        let events = parser.push(&bytes[split..]);
+        assert_eq!(events, vec![("message".to_string(), "\"caf\u{e9}\"".to_string())]);
+    }
+
+    #[test]
+    fn sse_multiline_data_and_comments() {
+        let mut parser = SseParser::default();
+
+        let events = parser.push(b": keep-alive\n\nevent: x\ndata: one\ndata: two\n\n");
+        assert_eq!(events, vec![("x".to_string(), "one\ntwo".to_string())]);
+    }
+
+    #[test]
+    fn reply_joins_split_tool_input() {
+        let reply = build(&[
+            ("content_block_start", r#"{"index":0,"content_block":{"type":"tool_use","id":"toolu_1","name":"list_missions","input":{}}}"#),
+            ("content_block_delta", r#"{"index":0,"delta":{"type":"input_json_delta","partial_json":"{\"proj"}}"#),
+            ("content_block_delta", r#"{"index":0,"delta":{"type":"input_json_delta","partial_json":"ect\": \"zac\"}"}}"#),
+            ("content_block_stop", r#"{"index":0}"#),
+            ("message_delta", r#"{"delta":{"stop_reason":"tool_use"},"usage":{"output_tokens":12}}"#),
+        ]).unwrap();
+
+        assert_eq!(reply.stop_reason.as_deref(), Some("tool_use"));
+        match reply.content.as_slice() {
+            [ContentBlock::ToolUse { id, name, input }] => {
+                assert_eq!(id, "toolu_1");
+                assert_eq!(name, "list_missions");
+                assert_eq!(input, &json!({ "project": "zac" }));
+            }
+            other => panic!("unexpected content {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reply_tool_without_input_gets_empty_object() {
+        let reply = build(&[
+            ("content_block_start", r#"{"index":0,"content_block":{"type":"tool_use","id":"toolu_1","name":"list_workers","input":{}}}"#),
+        ]).unwrap();
+
+        assert!(matches!(reply.content.as_slice(), [ContentBlock::ToolUse { input, .. }] if *input == json!({})));
+    }
+
+    #[test]
+    fn reply_drops_empty_text_blocks() {
+        let reply = build(&[
+            ("content_block_start", r#"{"index":0,"content_block":{"type":"text","text":""}}"#),
+            ("content_block_start", r#"{"index":1,"content_block":{"type":"text","text":""}}"#),
+            ("content_block_delta", r#"{"index":1,"delta":{"type":"text_delta","text":"Hi"}}"#),
+        ]).unwrap();
+
+        assert!(matches!(reply.content.as_slice(), [ContentBlock::Text { text }] if text == "Hi"));
+    }
+
+    #[test]
+    fn reply_rejects_bad_tool_input() {
+        let result = build(&[
+            ("content_block_start", r#"{"index":0,"content_block":{"type":"tool_use","id":"toolu_1","name":"assign_mission","input":{}}}"#),
+            ("content_block_delta", r#"{"index":0,"delta":{"type":"input_json_delta","partial_json":"{\"worker\":"}}"#),
+        ]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn reply_surfaces_stream_errors() {
+        let mut builder = ReplyBuilder::default();
+        let result = builder.apply("error", r#"{"error":{"type":"overloaded_error","message":"Overloaded"}}"#, &mut |_| {});
+
+        assert_eq!(result, Err("Claude API error: Overloaded".to_string()));
+    }
+
+    /// Serve one canned SSE body per request on a local port, handing back each request body
+    fn mock_server(replies: Vec<String>) -> (String, std::sync::mpsc::Receiver<Value>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (requests, received) = std::sync::mpsc::channel();
+
+        std::thread::spawn(move || {
+            for reply in replies {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                requests.send(serde_json::from_slice(&body).unwrap()).unwrap();
+
+                write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n{reply}").unwrap();
+            }
+        });
+
+        (base_url, received)
+    }
+
+    fn sse(events: &[(&str, Value)]) -> String {
+        events.iter().map(|(event, data)| format!("event: {event}\ndata: {data}\n\n")).collect()
+    }
+
+    #[test]
+    fn converse_runs_tools_until_the_model_answers() {
+        let tool_turn = sse(&[
+            ("message_start", json!({"message": {"usage": {"input_tokens": 20}}})),
+            ("content_block_start", json!({"index": 0, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "list_workers", "input": {}}})),
+            ("content_block_stop", json!({"index": 0})),
+            ("message_delta", json!({"delta": {"stop_reason": "tool_use"}, "usage": {"output_tokens": 5}})),
+            ("message_stop", json!({})),
+        ]);
+        let answer_turn = sse(&[
+            ("message_start", json!({"message": {"usage": {"input_tokens": 40}}})),
+            ("content_block_start", json!({"index": 0, "content_block": {"type": "text", "text": ""}})),
+            ("content_block_delta", json!({"index": 0, "delta": {"type": "text_delta", "text": "Blake is idle."}})),
+            ("message_delta", json!({"delta": {"stop_reason": "end_turn"}, "usage": {"output_tokens": 7}})),
+            ("message_stop", json!({})),
+        ]);
+        let (base_url, requests) = mock_server(vec![tool_turn, answer_turn]);
+
+        let client = ClaudeClient {
+            base_url,
+            api_key: None,
+            api_version: "2023-06-01".to_string(),
+            model: "test-model".to_string(),
+            max_tokens: 256,
+        };
+
+        let mut messages = vec![Message::user("Who is free?")];
+        let mut tool_calls = Vec::new();
+        let mut text = String::new();
+        let mut output_tokens = 0;
+
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(client.converse(
+            "system",
+            &mut messages,
+            &[],
+            |event| match event {
+                StreamEvent::Text(piece) => text.push_str(&piece),
+                StreamEvent::Usage(usage) => output_tokens += usage.output_tokens,
+                StreamEvent::ToolUse { .. } => {}
+            },
+            |name, _| {
+                tool_calls.push(name.to_string());
+                Ok("Blake: idle".to_string())
+            },
+        )).unwrap();
+
+        assert_eq!(tool_calls, vec!["list_workers"]);
+        assert_eq!(text, "Blake is idle.");
+        assert_eq!(output_tokens, 12);
+
+        // user, assistant tool call, tool result, assistant answer
+        let roles: Vec<&str> = messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, vec!["user", "assistant", "user", "assistant"]);
+
+        let first = requests.recv().unwrap();
+        assert_eq!(first["model"], "test-model");
+        assert_eq!(first["stream"], true);
+
+        // The second request carries the tool's answer back
+        let second = requests.recv().unwrap();
+        let result = &second["messages"][2]["content"][0];
+        assert_eq!(result["type"], "tool_result");
+        assert_eq!(result["tool_use_id"], "toolu_1");
+        assert_eq!(result["content"], "Blake: idle");
+    }
+}
@@ -1 +1,2 @@
 // Agent orchestration
+pub mod claude_api;
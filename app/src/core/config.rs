@@ -0,0 +1,250 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Built-in defaults, overridden by the user's `~/zac-caret/config.toml`
+const DEFAULT_CONFIG: &str = include_str!("../../config/default.toml");
+
+/// Application settings loaded from TOML
+#[derive(Resource, Debug, Clone, Deserialize)]
+pub struct AppConfig {
+    pub general: GeneralConfig,
+    pub autonomy: AutonomyConfig,
+    pub chat: ChatConfig,
+    pub git: GitConfig,
+    pub health: HealthConfig,
+    pub knowledge: KnowledgeConfig,
+    pub production: ProductionConfig,
+    pub scheduling: SchedulingConfig,
+    pub tokens: TokensConfig,
+    pub pricing: PricingConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GeneralConfig {
+    /// Have workers reflect on each finished mission before going idle
+    pub reflection_enabled: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AutonomyConfig {
+    /// Whether autonomy starts enabled; the Z toggle is remembered after that
+    pub enabled: bool,
+    /// Concurrency outside every window (0 to only run inside windows)
+    pub max_concurrent_workers: usize,
+    pub assignment_interval_secs: f32,
+    /// Time-of-day concurrency rules, first match wins
+    #[serde(default)]
+    pub windows: Vec<AutonomyWindowConfig>,
+    /// Times autonomy leaves a project alone
+    #[serde(default)]
+    pub blackouts: Vec<ProjectBlackoutConfig>,
+}
+
+/// Concurrency for part of the day, in local time
+#[derive(Debug, Clone, Deserialize)]
+pub struct AutonomyWindowConfig {
+    pub name: String,
+    /// `mon` ... `sun`; empty for every day
+    #[serde(default)]
+    pub days: Vec<String>,
+    /// `HH:MM`; an end before the start runs past midnight
+    pub start: String,
+    pub end: String,
+    /// 0 makes the window a quiet period
+    pub max_concurrent_workers: usize,
+}
+
+/// A time autonomy doesn't assign missions of one project, in local time
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProjectBlackoutConfig {
+    /// Project name
+    pub project: String,
+    #[serde(default)]
+    pub days: Vec<String>,
+    pub start: String,
+    pub end: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatConfig {
+    /// Messages API endpoint; point it at a local mock server to chat without a key
+    pub base_url: String,
+    pub api_version: String,
+    pub model: String,
+    /// Longest reply per request
+    pub max_tokens: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitConfig {
+    /// Commit changes an agent left uncommitted when its mission succeeds
+    pub auto_commit: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HealthConfig {
+    pub enabled: bool,
+    /// Seconds between health checks of each project
+    pub interval_secs: u64,
+    /// A probe running longer than this is killed and counted as skipped
+    pub timeout_secs: u64,
+    pub build: bool,
+    pub tests: bool,
+    pub lint: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KnowledgeConfig {
+    /// Add relevant past learnings to mission files (needs a Library)
+    pub injection_enabled: bool,
+    /// Rough token budget for the injected section
+    pub max_injection_tokens: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProductionConfig {
+    /// Seconds the Town Hall takes to train a worker
+    pub worker_build_secs: f32,
+    /// Tokens charged to the budget per queued worker (0 makes workers free)
+    pub worker_token_cost: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokensConfig {
+    /// Tokens the agents may use per hourly period
+    pub hourly_budget: u64,
+    /// Tokens per calendar day (0 for no daily cap)
+    pub daily_budget: u64,
+    /// Tokens per calendar month (0 for no monthly cap)
+    pub monthly_budget: u64,
+    /// Dollars per hour, used instead of `hourly_budget` when above 0
+    pub hourly_budget_usd: f64,
+    /// Dollars per calendar day, used instead of `daily_budget` when above 0
+    pub daily_budget_usd: f64,
+    /// Dollars per calendar month, used instead of `monthly_budget` when above 0
+    pub monthly_budget_usd: f64,
+    /// Warn when this share of the tightest budget is left (0.0 - 1.0)
+    pub warning_threshold: f32,
+    /// Tokens kept back for each running mission before a new one may start
+    pub headroom_per_mission: u64,
+    /// Daily cap for every project without its own (0 for none)
+    pub project_daily_cap: u64,
+    /// Daily cap for every worker without its own (0 for none)
+    pub worker_daily_cap: u64,
+    /// Daily caps by project name or id
+    #[serde(default)]
+    pub project_caps: HashMap<String, u64>,
+    /// Daily caps by worker name or id
+    #[serde(default)]
+    pub worker_caps: HashMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PricingConfig {
+    /// Model whose rates apply to workers without a model set
+    pub default_model: String,
+    /// Rates by model name
+    pub models: HashMap<String, ModelRates>,
+}
+
+/// Dollars per million tokens
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct ModelRates {
+    pub input: f64,
+    pub output: f64,
+    pub cache_read: f64,
+    pub cache_write: f64,
+}
+
+impl PricingConfig {
+    /// Rates for a worker's model, falling back to the default model
+    pub fn rates(&self, model: Option<&str>) -> ModelRates {
+        model.and_then(|m| self.models.get(m))
+            .or_else(|| self.models.get(&self.default_model))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Dollars per token for estimates, at the output rate so they err high
+    pub fn estimate_rate(&self, model: Option<&str>) -> f64 {
+        self.rates(model).output / 1_000_000.0
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SchedulingConfig {
+    /// Strategy autonomous assignment uses to rank missions (see `scheduling::STRATEGY_NAMES`)
+    pub strategy: String,
+}
+
+impl AppConfig {
+    /// Built-in defaults from `config/default.toml`
+    pub fn defaults() -> Self {
+        toml::from_str(DEFAULT_CONFIG).expect("Built-in default config is invalid")
+    }
+
+    /// Load defaults, then layer the user's config file on top if it exists
+    pub fn load(user_config_path: &Path) -> Result<Self, String> {
+        let mut merged: toml::Value = toml::from_str(DEFAULT_CONFIG)
+            .map_err(|e| format!("Invalid default config: {e}"))?;
+
+        if user_config_path.exists() {
+            let content = std::fs::read_to_string(user_config_path)
+                .map_err(|e| format!("Failed to read {}: {e}", user_config_path.display()))?;
+            let mut user: toml::Value = toml::from_str(&content)
+                .map_err(|e| format!("Invalid {}: {e}", user_config_path.display()))?;
+            move_legacy_keys(&mut user);
+            merge_toml(&mut merged, user);
+        }
+
+        merged.try_into()
+            .map_err(|e| format!("Invalid config: {e}"))
+    }
+}
+
+/// Keys that moved out of `[general]`, as (old key, new table, new key)
+const LEGACY_KEYS: &[(&str, &str, &str)] = &[
+    ("autonomy_enabled", "autonomy", "enabled"),
+    ("max_concurrent_workers", "autonomy", "max_concurrent_workers"),
+];
+
+/// Carry keys from older configs over to where they live now, unless the new key is set too
+fn move_legacy_keys(user: &mut toml::Value) {
+    let Some(root) = user.as_table_mut() else { return };
+
+    for (old_key, table, new_key) in LEGACY_KEYS {
+        let Some(value) = root.get_mut("general")
+            .and_then(toml::Value::as_table_mut)
+            .and_then(|general| general.remove(*old_key)) else { continue };
+
+        let Some(target) = root.entry(*table)
+            .or_insert_with(|| toml::Value::Table(toml::map::Map::new()))
+            .as_table_mut() else { continue };
+
+        if target.contains_key(*new_key) {
+            eprintln!("⚠️  Config: ignoring [general] {old_key}, [{table}] {new_key} is set");
+        } else {
+            eprintln!("⚠️  Config: [general] {old_key} is now [{table}] {new_key} - please move it");
+            target.insert(new_key.to_string(), value);
+        }
+    }
+}
+
+/// Recursively overlay `overlay` onto `base`, table by table
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base_table), toml::Value::Table(overlay_table)) => {
+            for (key, value) in overlay_table {
+                match base_table.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base_table.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
@@ -1,4 +1,5 @@
 use rusqlite::{Connection, Result};
+use std::collections::HashMap;
 use std::path::Path;
 use tauri::{AppHandle, Manager};
 
@@ -69,6 +70,89 @@ pub fn init_database(path: &Path) -> Result<Connection> {
             UNIQUE(project_id, mission_number)
         );
 
+        -- One row per mission run, with what it did to the repository
+        CREATE TABLE IF NOT EXISTS mission_attempts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            mission_id TEXT NOT NULL,
+            worker_id TEXT,
+            started_at DATETIME,
+            completed_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            success BOOLEAN,
+            duration_seconds INTEGER,
+            tokens_used INTEGER DEFAULT 0,
+            head_before TEXT,
+            head_after TEXT,
+            commits_created INTEGER DEFAULT 0,
+            files_changed INTEGER DEFAULT 0,
+            lines_added INTEGER DEFAULT 0,
+            lines_removed INTEGER DEFAULT 0,
+            tree_dirty BOOLEAN DEFAULT 0,
+            diff_stat TEXT,
+            auto_commit TEXT,
+            FOREIGN KEY (mission_id) REFERENCES missions(id)
+        );
+
+        -- Health check time series per project
+        CREATE TABLE IF NOT EXISTS project_health (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            project_id TEXT NOT NULL,
+            checked_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            build_status TEXT NOT NULL,
+            test_status TEXT NOT NULL,
+            lint_status TEXT NOT NULL,
+            last_commit_age_seconds INTEGER,
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_project_health
+         ON project_health(project_id, checked_at);
+
+        -- Utility buildings (Library, Workshop, Barracks), at most one of each
+        CREATE TABLE IF NOT EXISTS buildings (
+            id TEXT PRIMARY KEY,
+            building_type TEXT NOT NULL UNIQUE,
+            stage INTEGER DEFAULT 1,
+            position_x REAL DEFAULT 0,
+            position_y REAL DEFAULT 0,
+            position_z REAL DEFAULT 0,
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        );
+
+        -- Town Hall worker production queue, in order
+        CREATE TABLE IF NOT EXISTS production_orders (
+            id TEXT PRIMARY KEY,
+            position INTEGER NOT NULL,
+            worker_name TEXT NOT NULL,
+            queued_at TEXT NOT NULL,
+            started_at TEXT,
+            duration_secs REAL NOT NULL,
+            token_cost INTEGER DEFAULT 0
+        );
+
+        -- Hall of fame: retired workers and their career stats
+        CREATE TABLE IF NOT EXISTS retired_workers (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            color_r REAL,
+            color_g REAL,
+            color_b REAL,
+            total_tasks_completed INTEGER DEFAULT 0,
+            total_tokens_used INTEGER DEFAULT 0,
+            specialty_scores_json TEXT,
+            hired_at DATETIME,
+            retired_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        );
+
+        -- Reflections a worker wrote after its missions
+        CREATE TABLE IF NOT EXISTS worker_notes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            worker_id TEXT NOT NULL,
+            mission_id TEXT,
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            note TEXT NOT NULL,
+            FOREIGN KEY (worker_id) REFERENCES workers(id)
+        );
+
         -- Knowledge base for accumulated learnings
         CREATE TABLE IF NOT EXISTS knowledge_entries (
             id TEXT PRIMARY KEY,
@@ -110,6 +194,19 @@ pub fn init_database(path: &Path) -> Result<Connection> {
             related_task_id TEXT
         );
 
+        CREATE INDEX IF NOT EXISTS idx_zac_journal_timestamp
+         ON zac_journal(timestamp);
+
+        -- Token usage, one row per charge or refund
+        CREATE TABLE IF NOT EXISTS token_ledger (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            recorded_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            period_start TEXT NOT NULL,
+            tokens INTEGER NOT NULL,
+            source TEXT NOT NULL,
+            mission_id TEXT
+        );
+
         -- Sessions
         CREATE TABLE IF NOT EXISTS sessions (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -121,9 +218,52 @@ pub fn init_database(path: &Path) -> Result<Connection> {
         );
     "#)?;
 
+    apply_column_migrations(&conn)?;
+
     Ok(conn)
 }
 
+/// Columns added after the initial schema, applied idempotently on startup
+const COLUMN_MIGRATIONS: &[(&str, &str, &str)] = &[
+    ("projects", "archived_at", "DATETIME"),
+    ("missions", "domains", "TEXT DEFAULT '[]'"),
+    ("workers", "specialty_scores_json", "TEXT DEFAULT '{}'"),
+    ("workers", "state_json", "TEXT"),
+    ("workers", "profile_json", "TEXT DEFAULT '{}'"),
+    ("token_ledger", "project_id", "TEXT"),
+    ("token_ledger", "worker_id", "TEXT"),
+    ("token_ledger", "cost_usd", "REAL DEFAULT 0"),
+    ("missions", "cost_usd", "REAL DEFAULT 0"),
+    ("workers", "total_cost_usd", "REAL DEFAULT 0"),
+    ("retired_workers", "total_cost_usd", "REAL DEFAULT 0"),
+    ("mission_attempts", "input_tokens", "INTEGER DEFAULT 0"),
+    ("mission_attempts", "output_tokens", "INTEGER DEFAULT 0"),
+    ("mission_attempts", "cache_read_tokens", "INTEGER DEFAULT 0"),
+    ("mission_attempts", "cache_write_tokens", "INTEGER DEFAULT 0"),
+    ("mission_attempts", "cost_usd", "REAL DEFAULT 0"),
+    ("mission_attempts", "shared_repo", "BOOLEAN DEFAULT 0"),
+];
+
+fn apply_column_migrations(conn: &Connection) -> Result<()> {
+    for (table, column, declaration) in COLUMN_MIGRATIONS {
+        if !column_exists(conn, table, column)? {
+            conn.execute_batch(&format!("ALTER TABLE {table} ADD COLUMN {column} {declaration};"))?;
+        }
+    }
+    Ok(())
+}
+
+fn column_exists(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
+    let columns = stmt.query_map([], |row| row.get::<_, String>(1))?;
+    for name in columns {
+        if name? == column {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
 pub fn save_state(conn: &Connection, key: &str, value: &str) -> Result<()> {
     conn.execute(
         "INSERT OR REPLACE INTO app_state (key, value_json) VALUES (?1, ?2)",
@@ -141,3 +281,82 @@ pub fn load_state(conn: &Connection, key: &str) -> Result<Option<String>> {
         Err(e) => Err(e),
     }
 }
+
+/// Add a token charge (negative for refunds) to the ledger
+pub fn record_token_usage(conn: &Connection, period_start: &str, entry: &crate::game::resources::UsageEntry) -> Result<()> {
+    conn.execute(
+        "INSERT INTO token_ledger (period_start, tokens, cost_usd, source, mission_id, project_id, worker_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        rusqlite::params![period_start, entry.tokens, entry.cost_usd, entry.source, entry.mission_id, entry.project_id, entry.worker_id],
+    )?;
+    Ok(())
+}
+
+/// Net (tokens, dollars) charged in an hourly budget period
+pub fn period_ledger_totals(conn: &Connection, period_start: &str) -> Result<(i64, f64)> {
+    conn.query_row(
+        "SELECT COALESCE(SUM(tokens), 0), COALESCE(SUM(cost_usd), 0) FROM token_ledger WHERE period_start = ?1",
+        [period_start],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+}
+
+/// Net (tokens, dollars) recorded since `since` (`YYYY-MM-DD HH:MM:SS`, UTC)
+pub fn ledger_totals_since(conn: &Connection, since: &str) -> Result<(i64, f64)> {
+    conn.query_row(
+        "SELECT COALESCE(SUM(tokens), 0), COALESCE(SUM(cost_usd), 0) FROM token_ledger WHERE recorded_at >= ?1",
+        [since],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+}
+
+/// Net tokens recorded since `since`, per value of `column` (`project_id` or `worker_id`)
+pub fn token_usage_by(conn: &Connection, column: &str, since: &str) -> Result<HashMap<String, u64>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {column}, SUM(tokens) FROM token_ledger
+         WHERE recorded_at >= ?1 AND {column} IS NOT NULL GROUP BY {column}"
+    ))?;
+    let rows = stmt.query_map([since], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+
+    let mut usage = HashMap::new();
+    for row in rows {
+        let (id, tokens) = row?;
+        usage.insert(id, tokens.max(0) as u64);
+    }
+    Ok(usage)
+}
+
+/// Dollars spent per project since `since`, most expensive first
+pub fn cost_by_project(conn: &Connection, since: &str, limit: usize) -> Result<Vec<(String, f64)>> {
+    cost_report(conn, &format!(
+        "SELECT COALESCE(p.name, l.project_id), SUM(l.cost_usd) FROM token_ledger l
+         LEFT JOIN projects p ON p.id = l.project_id
+         WHERE l.project_id IS NOT NULL AND l.recorded_at >= ?1
+         GROUP BY l.project_id ORDER BY 2 DESC LIMIT {limit}"
+    ), since)
+}
+
+/// Dollars spent per worker (retired ones included) since `since`, most expensive first
+pub fn cost_by_worker(conn: &Connection, since: &str, limit: usize) -> Result<Vec<(String, f64)>> {
+    cost_report(conn, &format!(
+        "SELECT COALESCE(w.name, r.name, l.worker_id), SUM(l.cost_usd) FROM token_ledger l
+         LEFT JOIN workers w ON w.id = l.worker_id
+         LEFT JOIN retired_workers r ON r.id = l.worker_id
+         WHERE l.worker_id IS NOT NULL AND l.recorded_at >= ?1
+         GROUP BY l.worker_id ORDER BY 2 DESC LIMIT {limit}"
+    ), since)
+}
+
+/// Dollars spent per local calendar day since `since`, newest first
+pub fn cost_by_day(conn: &Connection, since: &str) -> Result<Vec<(String, f64)>> {
+    cost_report(conn,
+        "SELECT date(recorded_at, 'localtime'), SUM(cost_usd) FROM token_ledger
+         WHERE recorded_at >= ?1 GROUP BY 1 ORDER BY 1 DESC",
+        since)
+}
+
+fn cost_report(conn: &Connection, sql: &str, since: &str) -> Result<Vec<(String, f64)>> {
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([since], |row| Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?)))?;
+    rows.collect()
+}
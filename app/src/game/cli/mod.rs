@@ -1,7 +1,13 @@
+pub mod usage;
+
 use std::process::{Command, Child, Stdio};
 use std::path::PathBuf;
-use std::io::Write;
+use std::io::{BufRead, BufReader, Write};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
 use uuid::Uuid;
+use crate::game::worker::profile::WorkerProfile;
 
 pub struct ClaudeCliManager {
     #[allow(dead_code)]
@@ -16,6 +22,22 @@ pub struct ClaudeProcess {
     pub mission_id: String,
     pub child: Child,
     pub started_at: std::time::Instant,
+    pub project_path: String,
+    /// HEAD of the project repo when the mission started
+    pub head_before: Option<String>,
+    /// Paths already uncommitted when the mission started
+    pub dirty_before: Vec<String>,
+    /// Another mission ran in the same repo while this one did
+    pub shared_repo: bool,
+    /// Stdout collected so far by the reader thread
+    pub output: Arc<Mutex<String>>,
+    /// Highest token count reported in the output so far
+    pub streamed_tokens: Arc<AtomicU32>,
+    /// Streamed tokens already charged to the budget
+    pub charged_tokens: u32,
+    /// From the worker's profile; the mission is stopped past it
+    pub token_limit: Option<u32>,
+    reader: Option<JoinHandle<()>>,
 }
 
 impl ClaudeCliManager {
@@ -33,15 +55,27 @@ impl ClaudeCliManager {
         mission_id: String,
         project_path: &str,
         mission_file: &str,
+        profile: &WorkerProfile,
     ) -> Result<String, String> {
         let process_id = Uuid::new_v4().to_string();
 
         println!("🚀 Spawning Claude CLI for mission: {mission_file}");
         println!("   Working dir: {project_path}");
 
-        // Build command
-        let child = Command::new("claude-code")
-            .arg("--dangerously-skip-permissions")
+        // Snapshot HEAD and uncommitted paths before the agent touches the repo
+        let head_before = crate::game::git::head_commit(std::path::Path::new(project_path));
+        let dirty_before = crate::game::git::dirty_paths(std::path::Path::new(project_path));
+
+        // Missions sharing a repo can't tell their changes apart
+        let mut shared_repo = false;
+        for other in self.active_processes.iter_mut().filter(|p| p.project_path == project_path) {
+            other.shared_repo = true;
+            shared_repo = true;
+        }
+
+        // Build command, applying the worker's profile
+        let mut child = Command::new("claude-code")
+            .args(profile.cli_args())
             .arg(mission_file)
             .current_dir(project_path)
             .stdin(Stdio::piped())
@@ -50,12 +84,41 @@ impl ClaudeCliManager {
             .spawn()
             .map_err(|e| format!("Failed to spawn claude-code: {e}"))?;
 
+        // Read stdout as it arrives so token usage can be charged while the mission runs
+        let output = Arc::new(Mutex::new(String::new()));
+        let streamed_tokens = Arc::new(AtomicU32::new(0));
+        let reader = child.stdout.take().map(|stdout| {
+            let output = output.clone();
+            let streamed_tokens = streamed_tokens.clone();
+
+            std::thread::spawn(move || {
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    if let Some(tokens) = parse_token_count(&line) {
+                        streamed_tokens.fetch_max(tokens, Ordering::Relaxed);
+                    }
+                    if let Ok(mut output) = output.lock() {
+                        output.push_str(&line);
+                        output.push('\n');
+                    }
+                }
+            })
+        });
+
         let process = ClaudeProcess {
             id: process_id.clone(),
             worker_id,
             mission_id,
             child,
             started_at: std::time::Instant::now(),
+            project_path: project_path.to_string(),
+            head_before,
+            dirty_before,
+            shared_repo,
+            output,
+            streamed_tokens,
+            charged_tokens: 0,
+            token_limit: profile.max_tokens_per_mission,
+            reader,
         };
 
         self.active_processes.push(process);
@@ -69,20 +132,39 @@ impl ClaudeCliManager {
         let mut still_running = Vec::new();
 
         for mut process in self.active_processes.drain(..) {
+            // A mission over its token limit is stopped and counts as failed
+            let over_limit = process.token_limit
+                .filter(|limit| process.streamed_tokens.load(Ordering::Relaxed) > *limit);
+            if let Some(limit) = over_limit {
+                println!("🛑 Mission {} went over its {limit} token limit - stopping it", process.mission_id);
+                let _ = process.child.kill();
+            }
+
             match process.child.try_wait() {
                 Ok(Some(status)) => {
                     // Process completed
                     let duration = process.started_at.elapsed();
 
-                    // Try to read stdout
-                    let output = process.child.wait_with_output().ok();
+                    // The reader thread finishes once stdout closes
+                    if let Some(reader) = process.reader.take() {
+                        let _ = reader.join();
+                    }
+                    let mut output = process.output.lock().ok().map(|o| o.clone());
+                    if let (Some(limit), Some(output)) = (over_limit, output.as_mut()) {
+                        output.push_str(&format!("Stopped: went over the {limit} token limit\n"));
+                    }
 
                     let result = CompletionResult {
                         worker_id: process.worker_id,
                         mission_id: process.mission_id,
-                        success: status.success(),
+                        success: status.success() && over_limit.is_none(),
                         duration_secs: duration.as_secs(),
-                        output: output.map(|o| String::from_utf8_lossy(&o.stdout).to_string()),
+                        output,
+                        project_path: process.project_path,
+                        head_before: process.head_before,
+                        dirty_before: process.dirty_before,
+                        shared_repo: process.shared_repo,
+                        charged_tokens: process.charged_tokens,
                     };
 
                     completed.push(result);
@@ -102,6 +184,32 @@ impl ClaudeCliManager {
         completed
     }
 
+    /// Tokens reported since the last call as (mission, worker, tokens), marking them as charged
+    pub fn take_streamed_usage(&mut self) -> Vec<(String, String, u32)> {
+        let mut usage = Vec::new();
+
+        for process in self.active_processes.iter_mut() {
+            let seen = process.streamed_tokens.load(Ordering::Relaxed);
+            if seen > process.charged_tokens {
+                usage.push((process.mission_id.clone(), process.worker_id.clone(), seen - process.charged_tokens));
+                process.charged_tokens = seen;
+            }
+        }
+
+        usage
+    }
+
+    /// Kill the process running a worker's mission, returning the mission id
+    pub fn cancel_worker(&mut self, worker_id: &str) -> Option<String> {
+        let index = self.active_processes.iter().position(|p| p.worker_id == worker_id)?;
+        let mut process = self.active_processes.remove(index);
+
+        let _ = process.child.kill();
+        let _ = process.child.wait();
+
+        Some(process.mission_id)
+    }
+
     /// Send additional instructions to a running worker
     #[allow(dead_code)]
     pub fn send_message(&mut self, process_id: &str, message: &str) -> Result<(), String> {
@@ -131,24 +239,38 @@ pub struct CompletionResult {
     pub success: bool,
     pub duration_secs: u64,
     pub output: Option<String>,
+    pub project_path: String,
+    pub head_before: Option<String>,
+    pub dirty_before: Vec<String>,
+    pub shared_repo: bool,
+    /// Tokens already charged to the budget while the mission ran
+    pub charged_tokens: u32,
+}
+
+/// Token count from the CLI's usage line, "Tokens used: 4521" (or "2,345").
+/// Any other line, including agent prose mentioning tokens, is ignored.
+fn parse_token_count(line: &str) -> Option<u32> {
+    let line = line.trim();
+    let prefix = "tokens used:";
+    if !line.get(..prefix.len())?.eq_ignore_ascii_case(prefix) {
+        return None;
+    }
+
+    let count = line[prefix.len()..].trim();
+    if count.is_empty() || !count.chars().all(|c| c.is_ascii_digit() || c == ',') {
+        return None;
+    }
+    count.replace(',', "").parse().ok()
 }
 
 impl CompletionResult {
     /// Parse tokens used from output
     pub fn extract_tokens(&self) -> u32 {
-        if let Some(output) = &self.output {
-            // Look for pattern like "Tokens used: 4521" or similar
-            // This is a simplified parser - adjust based on actual Claude CLI output
-            if let Some(start) = output.find("tokens") {
-                let snippet = &output[start..];
-                if let Some(num_start) = snippet.find(char::is_numeric) {
-                    let num_str: String = snippet[num_start..]
-                        .chars()
-                        .take_while(|c| c.is_numeric())
-                        .collect();
-                    return num_str.parse().unwrap_or(0);
-                }
-            }
+        // The usage line is cumulative, so the highest one counts
+        let reported = self.output.as_deref()
+            .and_then(|output| output.lines().filter_map(parse_token_count).max());
+        if let Some(tokens) = reported {
+            return tokens;
         }
 
         // Default estimate if not found
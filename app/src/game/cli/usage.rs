@@ -0,0 +1,66 @@
+use serde::Serialize;
+use serde_json::Value;
+use crate::core::config::ModelRates;
+
+/// Token usage of one mission run, split the way it is priced
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct UsageBreakdown {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_write_tokens: u64,
+    /// Cost the CLI reported itself, preferred over our own pricing
+    pub reported_cost_usd: Option<f64>,
+}
+
+impl UsageBreakdown {
+    /// Read the usage from the CLI's JSON result line. Without one, all
+    /// `total_tokens` count as output tokens so the cost errs high.
+    /// Anything else in the output, including what the agent printed, is ignored.
+    pub fn parse(output: &str, total_tokens: u32) -> Self {
+        let mut usage = output.lines().rev()
+            .find_map(Self::from_result_line)
+            .unwrap_or_default();
+
+        if usage.total() == 0 {
+            usage.output_tokens = total_tokens as u64;
+        }
+
+        usage
+    }
+
+    /// Usage of a `{"type": "result", "usage": {...}, "total_cost_usd": 0.12}` line
+    pub fn from_result_line(line: &str) -> Option<Self> {
+        let result: Value = serde_json::from_str(line.trim()).ok()?;
+        if result["type"] != "result" {
+            return None;
+        }
+
+        let usage = &result["usage"];
+        let count = |key: &str| usage[key].as_u64().unwrap_or(0);
+        Some(Self {
+            input_tokens: count("input_tokens"),
+            output_tokens: count("output_tokens"),
+            cache_read_tokens: count("cache_read_input_tokens"),
+            cache_write_tokens: count("cache_creation_input_tokens"),
+            reported_cost_usd: result["total_cost_usd"].as_f64(),
+        })
+    }
+
+    pub fn total(&self) -> u64 {
+        self.input_tokens + self.output_tokens + self.cache_read_tokens + self.cache_write_tokens
+    }
+
+    /// Dollar cost at the given rates, unless the CLI reported one
+    pub fn cost_usd(&self, rates: &ModelRates) -> f64 {
+        if let Some(cost) = self.reported_cost_usd {
+            return cost;
+        }
+
+        (self.input_tokens as f64 * rates.input
+            + self.output_tokens as f64 * rates.output
+            + self.cache_read_tokens as f64 * rates.cache_read
+            + self.cache_write_tokens as f64 * rates.cache_write)
+            / 1_000_000.0
+    }
+}
@@ -1,3 +1,5 @@
 pub mod staged_building;
+pub mod utility_building;
 
 pub use staged_building::{StagedBuilding, BuildingType};
+pub use utility_building::UtilityBuilding;
@@ -1,28 +1,98 @@
 use bevy::prelude::*;
+use crate::game::entities::building_condition::BuildingCondition;
 use crate::game::entities::building_stage::BuildingStage;
+use crate::game::entities::building_theme::{BuildingModel, BuildingTheme};
+use crate::game::entities::utility_building::utility_model;
 
 /// Component for buildings that evolve through stages
 #[derive(Component, Debug)]
 pub struct StagedBuilding {
     pub current_stage: BuildingStage,
-    #[allow(dead_code)]
+    pub condition: BuildingCondition,
+    pub theme: BuildingTheme,
     pub building_type: BuildingType,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BuildingType {
     TownHall,
-    // Future: Workshop, Library, etc.
+    /// Holds the knowledge base and unlocks knowledge injection
+    Library,
+    /// Hosts project health checks
+    Workshop,
+    /// Raises the worker cap
+    Barracks,
+}
+
+impl BuildingType {
+    /// Buildings the player can construct, in build-key order (1, 2, 3)
+    pub const UTILITY: [BuildingType; 3] = [
+        BuildingType::Library,
+        BuildingType::Workshop,
+        BuildingType::Barracks,
+    ];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            BuildingType::TownHall => "town_hall",
+            BuildingType::Library => "library",
+            BuildingType::Workshop => "workshop",
+            BuildingType::Barracks => "barracks",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "town_hall" => Some(BuildingType::TownHall),
+            "library" => Some(BuildingType::Library),
+            "workshop" => Some(BuildingType::Workshop),
+            "barracks" => Some(BuildingType::Barracks),
+            _ => None,
+        }
+    }
+
+    /// Town Hall level needed before this building can be built
+    pub fn required_town_hall_level(&self) -> u8 {
+        match self {
+            BuildingType::TownHall | BuildingType::Workshop => 1,
+            BuildingType::Library => 2,
+            BuildingType::Barracks => 3,
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            BuildingType::TownHall => "Town Hall",
+            BuildingType::Library => "Library",
+            BuildingType::Workshop => "Workshop",
+            BuildingType::Barracks => "Barracks",
+        }
+    }
 }
 
 impl StagedBuilding {
     pub fn new(building_type: BuildingType, stage: u8) -> Self {
         Self {
             current_stage: BuildingStage::from_u8(stage),
+            condition: BuildingCondition::Normal,
+            theme: BuildingTheme::Generic,
             building_type,
         }
     }
 
+    pub fn with_theme(mut self, theme: BuildingTheme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Geometry for the current stage: utility buildings have their own, the rest follow their theme
+    pub fn model(&self) -> BuildingModel {
+        match self.building_type {
+            BuildingType::TownHall => self.theme.model(self.current_stage),
+            utility => utility_model(utility, self.current_stage),
+        }
+    }
+
     pub fn upgrade(&mut self) {
         let next_stage = (self.current_stage.as_u8() + 1).min(10);
         self.current_stage = BuildingStage::from_u8(next_stage);
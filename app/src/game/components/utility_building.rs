@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+use crate::game::components::BuildingType;
+
+/// Marks a Library, Workshop or Barracks entity (by `buildings.id`)
+#[derive(Component, Debug, Clone)]
+pub struct UtilityBuilding {
+    pub id: String,
+    pub building_type: BuildingType,
+}
@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+
+/// How a project's building looks on top of its stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BuildingCondition {
+    /// Nothing to report (or never checked)
+    #[default]
+    Normal,
+    /// A worker is on a mission for this project
+    Scaffolding,
+    /// Build is red: smoke and cracks
+    Damaged,
+    /// Build and tests are green: flag on the roof
+    Flagged,
+}
+
+impl BuildingCondition {
+    /// Extra meshes drawn over a building of `size`, relative to its center
+    pub fn overlay_parts(&self, size: Vec3) -> Vec<(Mesh, Color, Transform)> {
+        let roof = size.y * 0.5;
+
+        match self {
+            BuildingCondition::Normal => Vec::new(),
+            BuildingCondition::Scaffolding => {
+                let wood = Color::srgb(0.85, 0.7, 0.3);
+                let height = size.y + 0.6;
+                let half_x = size.x * 0.5 + 0.2;
+                let half_z = size.z * 0.5 + 0.2;
+
+                // Corner poles
+                let mut parts: Vec<(Mesh, Color, Transform)> = [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)]
+                    .iter()
+                    .map(|(sx, sz)| (
+                        Mesh::from(Cuboid::new(0.1, height, 0.1)),
+                        wood,
+                        Transform::from_xyz(sx * half_x, height * 0.5 - roof, sz * half_z),
+                    ))
+                    .collect();
+
+                // Planks across the front and back
+                for sz in [-1.0, 1.0] {
+                    parts.push((
+                        Mesh::from(Cuboid::new(half_x * 2.0, 0.08, 0.3)),
+                        wood,
+                        Transform::from_xyz(0.0, 0.0, sz * half_z),
+                    ));
+                }
+
+                parts
+            }
+            BuildingCondition::Damaged => {
+                let smoke = Color::srgba(0.2, 0.2, 0.2, 0.8);
+                let crack = Color::srgb(0.1, 0.1, 0.1);
+
+                vec![
+                    // Smoke rising from the roof
+                    (Mesh::from(Sphere::new(0.45)), smoke, Transform::from_xyz(0.0, roof + 0.5, 0.0)),
+                    (Mesh::from(Sphere::new(0.35)), smoke, Transform::from_xyz(0.2, roof + 1.1, 0.1)),
+                    (Mesh::from(Sphere::new(0.25)), smoke, Transform::from_xyz(0.4, roof + 1.6, 0.2)),
+                    // Cracks down the front wall
+                    (
+                        Mesh::from(Cuboid::new(0.06, size.y * 0.6, 0.02)),
+                        crack,
+                        Transform::from_xyz(-size.x * 0.2, 0.0, size.z * 0.5 + 0.01)
+                            .with_rotation(Quat::from_rotation_z(0.3)),
+                    ),
+                    (
+                        Mesh::from(Cuboid::new(0.06, size.y * 0.4, 0.02)),
+                        crack,
+                        Transform::from_xyz(size.x * 0.2, size.y * 0.1, size.z * 0.5 + 0.01)
+                            .with_rotation(Quat::from_rotation_z(-0.4)),
+                    ),
+                ]
+            }
+            BuildingCondition::Flagged => vec![
+                // Pole
+                (
+                    Mesh::from(Cuboid::new(0.06, 1.2, 0.06)),
+                    Color::srgb(0.8, 0.8, 0.8),
+                    Transform::from_xyz(0.0, roof + 0.6, 0.0),
+                ),
+                // Flag
+                (
+                    Mesh::from(Cuboid::new(0.6, 0.35, 0.03)),
+                    Color::srgb(0.2, 0.8, 0.3),
+                    Transform::from_xyz(0.3, roof + 1.0, 0.0),
+                ),
+            ],
+        }
+    }
+}
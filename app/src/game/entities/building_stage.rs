@@ -37,52 +37,52 @@ impl BuildingStage {
         *self as u8
     }
 
-    /// Generate mesh for this stage (primitive shapes for now)
-    pub fn generate_mesh(&self) -> Mesh {
+    /// Footprint and height of this stage's cuboid (see `BuildingTheme::Generic`)
+    pub fn dimensions(&self) -> Vec3 {
         match self {
             BuildingStage::Empty => {
                 // Small ground marker
-                Mesh::from(Cuboid::new(0.5, 0.1, 0.5))
+                Vec3::new(0.5, 0.1, 0.5)
             }
             BuildingStage::Foundation => {
                 // Flat foundation stones
-                Mesh::from(Cuboid::new(2.0, 0.3, 2.0))
+                Vec3::new(2.0, 0.3, 2.0)
             }
             BuildingStage::Frame => {
                 // Taller frame structure
-                Mesh::from(Cuboid::new(1.8, 1.5, 1.8))
+                Vec3::new(1.8, 1.5, 1.8)
             }
             BuildingStage::WallsAndRoof => {
                 // Basic building shape
-                Mesh::from(Cuboid::new(2.0, 2.0, 2.0))
+                Vec3::new(2.0, 2.0, 2.0)
             }
             BuildingStage::Complete => {
                 // Slightly taller
-                Mesh::from(Cuboid::new(2.0, 2.5, 2.0))
+                Vec3::new(2.0, 2.5, 2.0)
             }
             BuildingStage::Enhanced => {
                 // Wider base
-                Mesh::from(Cuboid::new(2.5, 2.5, 2.5))
+                Vec3::new(2.5, 2.5, 2.5)
             }
             BuildingStage::SecondFloor => {
                 // Taller multi-story
-                Mesh::from(Cuboid::new(2.5, 3.5, 2.5))
+                Vec3::new(2.5, 3.5, 2.5)
             }
             BuildingStage::Tower => {
                 // Add height for tower
-                Mesh::from(Cuboid::new(2.5, 4.5, 2.5))
+                Vec3::new(2.5, 4.5, 2.5)
             }
             BuildingStage::Decorated => {
                 // Slightly larger
-                Mesh::from(Cuboid::new(2.8, 4.5, 2.8))
+                Vec3::new(2.8, 4.5, 2.8)
             }
             BuildingStage::Grand => {
                 // Impressive size
-                Mesh::from(Cuboid::new(3.0, 5.0, 3.0))
+                Vec3::new(3.0, 5.0, 3.0)
             }
             BuildingStage::Monument => {
                 // Maximum grandeur
-                Mesh::from(Cuboid::new(3.5, 6.0, 3.5))
+                Vec3::new(3.5, 6.0, 3.5)
             }
         }
     }
@@ -0,0 +1,281 @@
+use bevy::prelude::*;
+use crate::game::entities::building_stage::BuildingStage;
+
+/// Visual style of a project building, stored in `projects.building_theme`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BuildingTheme {
+    #[default]
+    Generic,
+    MedievalTower,
+    Rocket,
+    Factory,
+    Lighthouse,
+}
+
+/// Meshes making up a building, relative to the building center
+pub struct BuildingModel {
+    pub parts: Vec<(Mesh, Color, Transform)>,
+    /// Bounding box of all parts
+    pub size: Vec3,
+}
+
+/// Collects parts placed from the ground up, then centers them like the stage cuboids
+pub(crate) struct ModelBuilder {
+    parts: Vec<(Mesh, Color, Transform)>,
+    size: Vec3,
+}
+
+impl ModelBuilder {
+    pub(crate) fn new() -> Self {
+        Self { parts: Vec::new(), size: Vec3::ZERO }
+    }
+
+    /// Add a part whose center sits at `center` (y measured from the ground)
+    pub(crate) fn add(&mut self, mesh: impl Into<Mesh>, color: Color, center: Vec3, half_extents: Vec3) {
+        self.add_rotated(mesh, color, center, half_extents, Quat::IDENTITY);
+    }
+
+    pub(crate) fn add_rotated(&mut self, mesh: impl Into<Mesh>, color: Color, center: Vec3, half_extents: Vec3, rotation: Quat) {
+        self.size.x = self.size.x.max((center.x.abs() + half_extents.x) * 2.0);
+        self.size.z = self.size.z.max((center.z.abs() + half_extents.z) * 2.0);
+        self.size.y = self.size.y.max(center.y + half_extents.y);
+        self.parts.push((mesh.into(), color, Transform::from_translation(center).with_rotation(rotation)));
+    }
+
+    pub(crate) fn finish(mut self) -> BuildingModel {
+        let offset = Vec3::Y * self.size.y * 0.5;
+        for (_, _, transform) in &mut self.parts {
+            transform.translation -= offset;
+        }
+
+        BuildingModel { parts: self.parts, size: self.size }
+    }
+}
+
+impl BuildingTheme {
+    /// Every theme, in the order the preview cycles through them
+    pub const ALL: [BuildingTheme; 5] = [
+        BuildingTheme::Generic,
+        BuildingTheme::MedievalTower,
+        BuildingTheme::Rocket,
+        BuildingTheme::Factory,
+        BuildingTheme::Lighthouse,
+    ];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            BuildingTheme::Generic => "generic",
+            BuildingTheme::MedievalTower => "medieval_tower",
+            BuildingTheme::Rocket => "rocket",
+            BuildingTheme::Factory => "factory",
+            BuildingTheme::Lighthouse => "lighthouse",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "medieval_tower" => BuildingTheme::MedievalTower,
+            "rocket" => BuildingTheme::Rocket,
+            "factory" => BuildingTheme::Factory,
+            "lighthouse" => BuildingTheme::Lighthouse,
+            _ => BuildingTheme::Generic,
+        }
+    }
+
+    /// Next theme in [`BuildingTheme::ALL`], wrapping around
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|t| t == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Main, accent and detail colors
+    pub fn palette(&self) -> [Color; 3] {
+        match self {
+            BuildingTheme::Generic => [
+                Color::srgb(0.6, 0.5, 0.4),
+                Color::srgb(0.5, 0.4, 0.3),
+                Color::srgb(0.95, 0.85, 0.6),
+            ],
+            BuildingTheme::MedievalTower => [
+                Color::srgb(0.55, 0.55, 0.5), // Stone
+                Color::srgb(0.55, 0.15, 0.15), // Roof tiles
+                Color::srgb(0.85, 0.7, 0.2), // Banner gold
+            ],
+            BuildingTheme::Rocket => [
+                Color::srgb(0.92, 0.92, 0.95), // Hull
+                Color::srgb(0.8, 0.15, 0.1), // Nose and fins
+                Color::srgb(0.35, 0.35, 0.4), // Launch pad
+            ],
+            BuildingTheme::Factory => [
+                Color::srgb(0.6, 0.3, 0.2), // Brick
+                Color::srgb(0.3, 0.3, 0.32), // Chimneys and roof
+                Color::srgb(0.9, 0.75, 0.3), // Windows
+            ],
+            BuildingTheme::Lighthouse => [
+                Color::srgb(0.95, 0.95, 0.95), // White bands
+                Color::srgb(0.8, 0.1, 0.1), // Red bands
+                Color::srgb(1.0, 0.9, 0.4), // Lantern
+            ],
+        }
+    }
+
+    /// Geometry for `stage`
+    pub fn model(&self, stage: BuildingStage) -> BuildingModel {
+        let level = stage.as_u8();
+        let [main, accent, detail] = self.palette();
+        let mut builder = ModelBuilder::new();
+
+        // Stages 0 and 1 look alike across themes: a marker, then a foundation
+        if *self == BuildingTheme::Generic || level <= 1 {
+            let size = stage.dimensions();
+            let color = if *self == BuildingTheme::Generic { stage.get_color() } else { main };
+            builder.add(Cuboid::from_size(size), color, Vec3::Y * size.y * 0.5, size * 0.5);
+            return builder.finish();
+        }
+
+        let growth = level as f32;
+
+        match self {
+            // Drawn as a single stage cuboid above
+            BuildingTheme::Generic => {}
+            BuildingTheme::MedievalTower => {
+                let radius = 0.9 + growth * 0.03;
+                let height = 0.6 + growth * 0.45;
+                builder.add(Cylinder::new(radius, height), main, Vec3::Y * height * 0.5, Vec3::new(radius, height * 0.5, radius));
+
+                if level >= 3 {
+                    let roof = Cone { radius: radius * 1.15, height: radius * 1.4 };
+                    builder.add(roof, accent, Vec3::Y * (height + roof.height * 0.5), Vec3::new(roof.radius, roof.height * 0.5, roof.radius));
+                }
+
+                if level >= 5 {
+                    // Battlements around the top of the wall
+                    for i in 0..8 {
+                        let angle = i as f32 * std::f32::consts::TAU / 8.0;
+                        let center = Vec3::new(angle.cos() * radius, height, angle.sin() * radius);
+                        builder.add(Cuboid::new(0.25, 0.3, 0.25), main, center, Vec3::splat(0.15));
+                    }
+                }
+
+                if level >= 7 {
+                    // Side turret
+                    let turret_height = height * 0.6;
+                    let turret_center = Vec3::new(radius + 0.3, turret_height * 0.5, 0.0);
+                    builder.add(Cylinder::new(0.4, turret_height), main, turret_center, Vec3::new(0.4, turret_height * 0.5, 0.4));
+                    let turret_roof = Cone { radius: 0.5, height: 0.7 };
+                    builder.add(turret_roof, accent, Vec3::new(radius + 0.3, turret_height + 0.35, 0.0), Vec3::new(0.5, 0.35, 0.5));
+                }
+
+                if level >= 9 {
+                    // Banner hung from the wall
+                    builder.add(Cuboid::new(0.5, 0.9, 0.05), detail, Vec3::new(0.0, height * 0.7, radius + 0.05), Vec3::new(0.25, 0.45, 0.05));
+                }
+            }
+            BuildingTheme::Rocket => {
+                // Launch pad
+                builder.add(Cuboid::new(2.6, 0.2, 2.6), detail, Vec3::Y * 0.1, Vec3::new(1.3, 0.1, 1.3));
+
+                let radius = 0.55 + growth * 0.02;
+                let body = 0.5 + growth * 0.4;
+                builder.add(Cylinder::new(radius, body), main, Vec3::Y * (0.2 + body * 0.5), Vec3::new(radius, body * 0.5, radius));
+
+                if level >= 3 {
+                    // Fins
+                    for i in 0..4 {
+                        let angle = i as f32 * std::f32::consts::FRAC_PI_2;
+                        let center = Vec3::new(angle.cos() * (radius + 0.2), 0.6, angle.sin() * (radius + 0.2));
+                        builder.add_rotated(
+                            Cuboid::new(0.4, 0.8, 0.06),
+                            accent,
+                            center,
+                            Vec3::new(0.2, 0.4, 0.2),
+                            Quat::from_rotation_y(-angle),
+                        );
+                    }
+                }
+
+                if level >= 4 {
+                    let nose = Cone { radius, height: radius * 2.0 };
+                    builder.add(nose, accent, Vec3::Y * (0.2 + body + nose.height * 0.5), Vec3::new(radius, nose.height * 0.5, radius));
+                }
+
+                if level >= 8 {
+                    // Side boosters
+                    let booster = body * 0.55;
+                    for side in [-1.0, 1.0] {
+                        let center = Vec3::new(side * (radius + 0.3), 0.2 + booster * 0.5, 0.0);
+                        builder.add(Cylinder::new(0.25, booster), main, center, Vec3::new(0.25, booster * 0.5, 0.25));
+                        builder.add(
+                            Cone { radius: 0.25, height: 0.4 },
+                            accent,
+                            center + Vec3::Y * (booster * 0.5 + 0.2),
+                            Vec3::new(0.25, 0.2, 0.25),
+                        );
+                    }
+                }
+            }
+            BuildingTheme::Factory => {
+                let hall_height = 0.8 + growth * 0.15;
+                builder.add(Cuboid::new(2.4, hall_height, 1.8), main, Vec3::Y * hall_height * 0.5, Vec3::new(1.2, hall_height * 0.5, 0.9));
+                builder.add(Cuboid::new(2.5, 0.15, 1.9), accent, Vec3::Y * (hall_height + 0.075), Vec3::new(1.25, 0.075, 0.95));
+
+                if level >= 4 {
+                    // Lit windows along the front
+                    for x in [-0.7, 0.0, 0.7] {
+                        builder.add(Cuboid::new(0.35, 0.3, 0.05), detail, Vec3::new(x, hall_height * 0.55, 0.92), Vec3::new(0.175, 0.15, 0.05));
+                    }
+                }
+
+                if level >= 5 {
+                    // Annex
+                    let annex = hall_height * 0.7;
+                    builder.add(Cuboid::new(1.2, annex, 1.4), main, Vec3::new(1.8, annex * 0.5, 0.0), Vec3::new(0.6, annex * 0.5, 0.7));
+                }
+
+                // One more chimney every three stages
+                let chimneys = (level / 3) as usize;
+                let chimney_height = hall_height + 0.8 + growth * 0.15;
+                for i in 0..chimneys {
+                    let center = Vec3::new(-0.8 + i as f32 * 0.8, chimney_height * 0.5, -0.5);
+                    builder.add(Cylinder::new(0.18, chimney_height), accent, center, Vec3::new(0.18, chimney_height * 0.5, 0.18));
+                }
+            }
+            BuildingTheme::Lighthouse => {
+                // Tapering tower in alternating bands
+                let bands = level as usize;
+                let band_height = 0.55;
+                let base_radius = 1.0;
+                let top_radius = 0.55;
+
+                for i in 0..bands {
+                    let t0 = i as f32 / 10.0;
+                    let t1 = (i + 1) as f32 / 10.0;
+                    let band = ConicalFrustum {
+                        radius_bottom: base_radius + (top_radius - base_radius) * t0,
+                        radius_top: base_radius + (top_radius - base_radius) * t1,
+                        height: band_height,
+                    };
+                    let color = if i % 2 == 0 { main } else { accent };
+                    let center = Vec3::Y * (i as f32 * band_height + band_height * 0.5);
+                    builder.add(band, color, center, Vec3::new(band.radius_bottom, band_height * 0.5, band.radius_bottom));
+                }
+
+                let tower = bands as f32 * band_height;
+                let top = base_radius + (top_radius - base_radius) * bands as f32 / 10.0;
+
+                if level >= 5 {
+                    // Lantern room and cap
+                    builder.add(Cylinder::new(top * 0.8, 0.5), detail, Vec3::Y * (tower + 0.25), Vec3::new(top, 0.25, top));
+                    builder.add(Cone { radius: top, height: 0.5 }, accent, Vec3::Y * (tower + 0.75), Vec3::new(top, 0.25, top));
+                }
+
+                if level >= 7 {
+                    // Gallery ring below the lantern
+                    builder.add(Cylinder::new(top + 0.25, 0.08), main, Vec3::Y * (tower + 0.04), Vec3::new(top + 0.25, 0.04, top + 0.25));
+                }
+            }
+        }
+
+        builder.finish()
+    }
+}
@@ -1,5 +1,8 @@
+pub mod building_condition;
 pub mod building_stage;
+pub mod building_theme;
 pub mod town_hall;
+pub mod utility_building;
 
 // Note: These exports are used in later phases
 #[allow(unused_imports)]
@@ -1,18 +1,25 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use crate::game::components::{StagedBuilding, BuildingType};
 
+/// Highest Town Hall level (its building reaches stage 10)
+pub const MAX_TOWN_HALL_LEVEL: u8 = 7;
+
+/// Town Hall progression, persisted in `app_state` under `town_hall`
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
 pub struct TownHall {
-    pub _level: u8,
-    pub _worker_capacity: u8,
+    pub level: u8,
+}
+
+impl Default for TownHall {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TownHall {
-    #[allow(dead_code)]
     pub fn new() -> Self {
-        Self {
-            _level: 1,
-            _worker_capacity: 5,
-        }
+        Self { level: 1 }
     }
 
     /// Spawn the town hall entity with staging component
@@ -25,24 +32,42 @@ impl TownHall {
             .id()
     }
 
-    pub fn _max_workers(&self) -> u8 {
-        5 + (self._level - 1) * 2
+    pub fn max_workers(&self) -> usize {
+        5 + (self.level as usize - 1) * 2
     }
 
-    pub fn _upgrade_requirements(&self) -> _TownHallUpgradeReq {
-        match self._level {
-            1 => _TownHallUpgradeReq { _projects: 1, _workers: 2 },
-            2 => _TownHallUpgradeReq { _projects: 2, _workers: 4 },
-            3 => _TownHallUpgradeReq { _projects: 3, _workers: 6 },
-            _ => _TownHallUpgradeReq { 
-                _projects: self._level as usize, 
-                _workers: self._level as usize * 2 
-            },
+    /// Building stage shown for this level (level 1 is a complete basic structure)
+    pub fn stage(&self) -> u8 {
+        (3 + self.level).min(10)
+    }
+
+    /// What the next level needs, or `None` at the top level
+    pub fn upgrade_requirements(&self) -> Option<TownHallUpgradeReq> {
+        if self.level >= MAX_TOWN_HALL_LEVEL {
+            return None;
         }
+
+        Some(match self.level {
+            1 => TownHallUpgradeReq { projects: 1, workers: 2 },
+            2 => TownHallUpgradeReq { projects: 2, workers: 4 },
+            3 => TownHallUpgradeReq { projects: 3, workers: 6 },
+            _ => TownHallUpgradeReq {
+                projects: self.level as usize,
+                workers: self.level as usize * 2,
+            },
+        })
     }
 }
 
-pub struct _TownHallUpgradeReq {
-    pub _projects: usize,
-    pub _workers: usize,
+/// Completed projects and workers needed for the next Town Hall level
+#[derive(Debug, Clone, Copy)]
+pub struct TownHallUpgradeReq {
+    pub projects: usize,
+    pub workers: usize,
+}
+
+impl TownHallUpgradeReq {
+    pub fn is_met(&self, completed_projects: usize, workers: usize) -> bool {
+        completed_projects >= self.projects && workers >= self.workers
+    }
 }
@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+use crate::game::components::BuildingType;
+use crate::game::entities::building_stage::BuildingStage;
+use crate::game::entities::building_theme::{BuildingModel, ModelBuilder};
+
+/// Geometry for a Library, Workshop or Barracks at `stage`
+pub fn utility_model(building_type: BuildingType, stage: BuildingStage) -> BuildingModel {
+    let level = stage.as_u8().max(1);
+    let growth = level as f32;
+    let mut builder = ModelBuilder::new();
+
+    match building_type {
+        BuildingType::Library => {
+            let marble = Color::srgb(0.88, 0.86, 0.8);
+            let roof = Color::srgb(0.3, 0.35, 0.55);
+            let books = [
+                Color::srgb(0.6, 0.15, 0.15),
+                Color::srgb(0.15, 0.35, 0.6),
+                Color::srgb(0.2, 0.5, 0.25),
+            ];
+
+            let height = 1.2 + growth * 0.15;
+            builder.add(Cuboid::new(2.6, 0.2, 2.0), marble, Vec3::Y * 0.1, Vec3::new(1.3, 0.1, 1.0));
+            builder.add(Cuboid::new(2.2, height, 1.6), marble, Vec3::Y * (0.2 + height * 0.5), Vec3::new(1.1, height * 0.5, 0.8));
+            builder.add(Cuboid::new(2.5, 0.25, 1.9), roof, Vec3::Y * (0.2 + height + 0.125), Vec3::new(1.25, 0.125, 0.95));
+
+            // Columns along the front
+            let columns = 2 + (level / 3) as usize;
+            for i in 0..columns {
+                let x = -1.0 + 2.0 * i as f32 / (columns - 1) as f32;
+                builder.add(Cylinder::new(0.1, height), marble, Vec3::new(x, 0.2 + height * 0.5, 0.95), Vec3::new(0.1, height * 0.5, 0.1));
+            }
+
+            // A shelf of books per stage, visible through the side
+            for i in 0..level as usize {
+                let shelf = (i / 5) as f32;
+                let slot = (i % 5) as f32;
+                builder.add(
+                    Cuboid::new(0.12, 0.3, 0.25),
+                    books[i % books.len()],
+                    Vec3::new(1.12, 0.45 + shelf * 0.4, -0.5 + slot * 0.25),
+                    Vec3::new(0.06, 0.15, 0.125),
+                );
+            }
+        }
+        BuildingType::Workshop => {
+            let timber = Color::srgb(0.55, 0.4, 0.25);
+            let metal = Color::srgb(0.45, 0.45, 0.5);
+            let forge = Color::srgb(1.0, 0.5, 0.1);
+
+            let height = 1.0 + growth * 0.1;
+            builder.add(Cuboid::new(2.2, height, 2.0), timber, Vec3::Y * height * 0.5, Vec3::new(1.1, height * 0.5, 1.0));
+            builder.add_rotated(
+                Cuboid::new(2.4, 0.12, 1.3),
+                metal,
+                Vec3::new(0.0, height + 0.3, -0.45),
+                Vec3::new(1.2, 0.35, 0.65),
+                Quat::from_rotation_x(0.45),
+            );
+            builder.add_rotated(
+                Cuboid::new(2.4, 0.12, 1.3),
+                metal,
+                Vec3::new(0.0, height + 0.3, 0.45),
+                Vec3::new(1.2, 0.35, 0.65),
+                Quat::from_rotation_x(-0.45),
+            );
+
+            // Glowing forge door
+            builder.add(Cuboid::new(0.6, 0.7, 0.05), forge, Vec3::new(0.0, 0.35, 1.02), Vec3::new(0.3, 0.35, 0.05));
+
+            // One more workbench outside every few stages
+            for i in 0..(level / 3) as usize {
+                builder.add(Cuboid::new(0.5, 0.4, 0.35), metal, Vec3::new(1.45, 0.2, -0.6 + i as f32 * 0.6), Vec3::new(0.25, 0.2, 0.175));
+            }
+
+            let chimney = height + 0.6 + growth * 0.05;
+            builder.add(Cylinder::new(0.15, chimney), metal, Vec3::new(-0.8, chimney * 0.5, -0.6), Vec3::new(0.15, chimney * 0.5, 0.15));
+        }
+        BuildingType::Barracks => {
+            let wall = Color::srgb(0.5, 0.45, 0.38);
+            let roof = Color::srgb(0.35, 0.25, 0.18);
+            let pennant = Color::srgb(0.2, 0.4, 0.8);
+
+            // Longhouse that stretches with each stage
+            let length = 1.8 + growth * 0.15;
+            builder.add(Cuboid::new(length, 1.1, 1.6), wall, Vec3::Y * 0.55, Vec3::new(length * 0.5, 0.55, 0.8));
+            builder.add(Cuboid::new(length + 0.2, 0.3, 1.8), roof, Vec3::Y * 1.25, Vec3::new(length * 0.5 + 0.1, 0.15, 0.9));
+
+            // Bunk windows, one per worker slot the Barracks adds
+            for i in 0..level as usize {
+                let x = -length * 0.5 + 0.2 + i as f32 * (length - 0.4) / 9.0;
+                builder.add(Cuboid::new(0.1, 0.15, 0.05), roof, Vec3::new(x, 0.75, 0.82), Vec3::new(0.05, 0.075, 0.05));
+            }
+
+            if level >= 5 {
+                // Watchtower
+                builder.add(Cuboid::new(0.6, 2.4, 0.6), wall, Vec3::new(length * 0.5 + 0.3, 1.2, 0.0), Vec3::new(0.3, 1.2, 0.3));
+                builder.add(Cuboid::new(0.4, 0.25, 0.03), pennant, Vec3::new(length * 0.5 + 0.5, 2.7, 0.0), Vec3::new(0.2, 0.125, 0.03));
+            }
+        }
+        // Project buildings and the Town Hall use building themes instead
+        BuildingType::TownHall => {
+            let size = stage.dimensions();
+            builder.add(Cuboid::from_size(size), stage.get_color(), Vec3::Y * size.y * 0.5, size * 0.5);
+        }
+    }
+
+    builder.finish()
+}
@@ -0,0 +1,206 @@
+use serde::Serialize;
+use std::path::Path;
+use std::process::Command;
+
+/// Hash of git's empty tree, used as the diff base for repositories with no commits
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// Paths Zac^ writes into projects itself, left out of mission diffs and auto-commits
+const ZAC_PATHSPECS: &[&str] = &[".", ":(exclude).zac", ":(exclude)missions"];
+
+/// What a mission did to a project's repository
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GitMissionResult {
+    pub head_before: Option<String>,
+    pub head_after: Option<String>,
+    pub commits_created: u32,
+    pub files_changed: u32,
+    pub lines_added: u32,
+    pub lines_removed: u32,
+    /// Uncommitted changes remained after the mission (and any auto-commit)
+    pub tree_dirty: bool,
+    /// `git diff --stat` output from `head_before` to the working tree
+    pub diff_stat: String,
+    /// Commit created by Zac^ on the agent's behalf, if any
+    pub auto_commit: Option<String>,
+    /// Other missions ran in the repo at the same time, so their changes may be counted too
+    #[serde(skip)]
+    pub shared_repo: bool,
+}
+
+impl GitMissionResult {
+    /// One-line summary for logs and mission files
+    pub fn summary_line(&self) -> String {
+        format!(
+            "{} commit(s), {} file(s) changed, +{} -{}{}{}",
+            self.commits_created,
+            self.files_changed,
+            self.lines_added,
+            self.lines_removed,
+            if self.tree_dirty { " (uncommitted changes left)" } else { "" },
+            if self.shared_repo { " (shared repo: includes other missions' work)" } else { "" },
+        )
+    }
+}
+
+fn git(repo: &Path, args: &[&str]) -> Result<String, String> {
+    git_raw(repo, args).map(|out| out.trim().to_string())
+}
+
+/// Like `git`, without trimming the output
+fn git_raw(repo: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo)
+        .output()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Zac^'s own exclusions plus `skip`, e.g. paths that were dirty before a mission
+fn pathspecs(skip: &[String]) -> Vec<String> {
+    ZAC_PATHSPECS.iter()
+        .map(|spec| spec.to_string())
+        .chain(skip.iter().map(|path| format!(":(exclude,literal){path}")))
+        .collect()
+}
+
+/// Paths with uncommitted changes outside Zac^'s own files
+pub fn dirty_paths(repo: &Path) -> Vec<String> {
+    let mut args = vec!["status", "--porcelain", "-z", "--"];
+    args.extend_from_slice(ZAC_PATHSPECS);
+    let Ok(out) = git_raw(repo, &args) else { return Vec::new() };
+
+    // "XY path\0", with the original path following renames and copies
+    let mut paths = Vec::new();
+    let mut entries = out.split('\0').filter(|entry| !entry.is_empty());
+    while let Some(entry) = entries.next() {
+        let Some(path) = entry.get(3..) else { continue };
+        if entry.starts_with('R') || entry.starts_with('C') {
+            entries.next();
+        }
+        paths.push(path.to_string());
+    }
+    paths
+}
+
+/// Current HEAD commit, or `None` outside a repo or before the first commit
+pub fn head_commit(repo: &Path) -> Option<String> {
+    git(repo, &["rev-parse", "HEAD"]).ok()
+}
+
+/// Unix timestamp of the last commit on HEAD
+pub fn last_commit_time(repo: &Path) -> Option<i64> {
+    git(repo, &["log", "-1", "--format=%ct"]).ok()?.parse().ok()
+}
+
+/// Commit everything the agent left behind except the `skip` paths, returning the new HEAD
+pub fn auto_commit(repo: &Path, message: &str, skip: &[String]) -> Result<String, String> {
+    let specs = pathspecs(skip);
+
+    let mut add_args = vec!["add", "-A", "--"];
+    add_args.extend(specs.iter().map(String::as_str));
+    git(repo, &add_args)?;
+
+    // Only these paths, even if something else was staged beforehand
+    let mut commit_args = vec!["commit", "-m", message, "--"];
+    commit_args.extend(specs.iter().map(String::as_str));
+    git(repo, &commit_args)?;
+
+    head_commit(repo).ok_or_else(|| "No HEAD after auto-commit".to_string())
+}
+
+/// Compare the repository now against `head_before`, leaving out the `skip` paths
+pub fn mission_result(repo: &Path, head_before: Option<String>, skip: &[String]) -> GitMissionResult {
+    let specs = pathspecs(skip);
+
+    let head_after = head_commit(repo);
+    let base = head_before.clone().unwrap_or_else(|| EMPTY_TREE.to_string());
+
+    let commits_created = match (&head_before, &head_after) {
+        (Some(before), Some(after)) if before != after => {
+            git(repo, &["rev-list", "--count", &format!("{before}..{after}")]).ok()
+        }
+        (None, Some(after)) => git(repo, &["rev-list", "--count", after]).ok(),
+        _ => None,
+    }
+    .and_then(|count| count.parse().ok())
+    .unwrap_or(0);
+
+    // Diff against the working tree so uncommitted work is counted too
+    let mut shortstat_args = vec!["diff", "--shortstat", &base, "--"];
+    shortstat_args.extend(specs.iter().map(String::as_str));
+    let (files_changed, lines_added, lines_removed) = git(repo, &shortstat_args)
+        .map(|out| parse_shortstat(&out))
+        .unwrap_or_default();
+
+    let mut stat_args = vec!["diff", "--stat", &base, "--"];
+    stat_args.extend(specs.iter().map(String::as_str));
+    let diff_stat = git(repo, &stat_args).unwrap_or_default();
+
+    GitMissionResult {
+        head_before,
+        head_after,
+        commits_created,
+        files_changed,
+        lines_added,
+        lines_removed,
+        tree_dirty: dirty_paths(repo).iter().any(|path| !skip.contains(path)),
+        diff_stat,
+        auto_commit: None,
+        shared_repo: false,
+    }
+}
+
+/// Parse " 3 files changed, 10 insertions(+), 2 deletions(-)"
+fn parse_shortstat(output: &str) -> (u32, u32, u32) {
+    let mut files = 0;
+    let mut added = 0;
+    let mut removed = 0;
+
+    for part in output.split(',') {
+        let part = part.trim();
+        let Some(number) = part.split_whitespace().next().and_then(|n| n.parse().ok()) else {
+            continue;
+        };
+
+        if part.contains("file") {
+            files = number;
+        } else if part.contains("insertion") {
+            added = number;
+        } else if part.contains("deletion") {
+            removed = number;
+        }
+    }
+
+    (files, added, removed)
+}
+
+/// Conventional commit message for a mission's leftover changes
+pub fn conventional_commit_message(mission_number: u32, title: &str, worker_name: &str) -> String {
+    let lowered = title.to_lowercase();
+    let commit_type = if lowered.starts_with("fix") || lowered.contains("bug") {
+        "fix"
+    } else if lowered.starts_with("doc") || lowered.contains("readme") {
+        "docs"
+    } else if lowered.starts_with("test") {
+        "test"
+    } else if lowered.starts_with("refactor") {
+        "refactor"
+    } else {
+        "feat"
+    };
+
+    format!(
+        "{commit_type}(mission-{mission_number}): {title}\n\nCompleted by Zac^ worker {worker_name}."
+    )
+}
@@ -0,0 +1,168 @@
+use serde::Serialize;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use crate::core::config::HealthConfig;
+
+/// Result of one health probe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CheckStatus {
+    Passing,
+    Failing,
+    /// No command for this build system, disabled, or timed out
+    Skipped,
+}
+
+impl CheckStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            CheckStatus::Passing => "passing",
+            CheckStatus::Failing => "failing",
+            CheckStatus::Skipped => "skipped",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "passing" => CheckStatus::Passing,
+            "failing" => CheckStatus::Failing,
+            _ => CheckStatus::Skipped,
+        }
+    }
+}
+
+/// One health check of a project
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub project_id: String,
+    pub checked_at: String,
+    pub build: CheckStatus,
+    pub tests: CheckStatus,
+    pub lint: CheckStatus,
+    /// Seconds since the last commit, `None` outside a repo
+    pub last_commit_age_secs: Option<i64>,
+}
+
+impl HealthReport {
+    /// One-line summary for logs
+    pub fn summary_line(&self) -> String {
+        let age = match self.last_commit_age_secs {
+            Some(secs) => format!("{}d", secs / 86_400),
+            None => "n/a".to_string(),
+        };
+
+        format!(
+            "build {}, tests {}, lint {}, last commit {age} ago",
+            self.build.as_str(),
+            self.tests.as_str(),
+            self.lint.as_str(),
+        )
+    }
+}
+
+/// Build, test and lint commands for a detected build system
+fn probe_commands(build_system: &str) -> [Option<&'static [&'static str]>; 3] {
+    match build_system {
+        "cargo" => [
+            Some(&["cargo", "build", "--quiet"]),
+            Some(&["cargo", "test", "--quiet"]),
+            Some(&["cargo", "clippy", "--quiet", "--", "-D", "warnings"]),
+        ],
+        "go" => [
+            Some(&["go", "build", "./..."]),
+            Some(&["go", "test", "./..."]),
+            Some(&["go", "vet", "./..."]),
+        ],
+        "npm" => [
+            Some(&["npm", "run", "build", "--if-present"]),
+            Some(&["npm", "test", "--if-present"]),
+            Some(&["npm", "run", "lint", "--if-present"]),
+        ],
+        "pyproject" | "setuptools" | "pip" => [
+            Some(&["python3", "-m", "compileall", "-q", "."]),
+            Some(&["python3", "-m", "pytest", "-q"]),
+            None,
+        ],
+        "maven" => [
+            Some(&["mvn", "-q", "compile"]),
+            Some(&["mvn", "-q", "test"]),
+            None,
+        ],
+        "gradle" => [
+            Some(&["gradle", "-q", "assemble"]),
+            Some(&["gradle", "-q", "test"]),
+            None,
+        ],
+        "make" => [
+            Some(&["make"]),
+            Some(&["make", "test"]),
+            None,
+        ],
+        _ => [None, None, None],
+    }
+}
+
+/// Run build, tests, lint and the last-commit probe for one project.
+///
+/// Blocking - call from a background thread.
+pub fn check_project(project_id: &str, project_path: &Path, build_system: &str, config: &HealthConfig) -> HealthReport {
+    let [build_cmd, test_cmd, lint_cmd] = probe_commands(build_system);
+    let timeout = Duration::from_secs(config.timeout_secs);
+
+    let build = run_probe(project_path, build_cmd.filter(|_| config.build), timeout);
+
+    // Tests and lint are meaningless on a red build
+    let (tests, lint) = if build == CheckStatus::Failing {
+        (CheckStatus::Skipped, CheckStatus::Skipped)
+    } else {
+        (
+            run_probe(project_path, test_cmd.filter(|_| config.tests), timeout),
+            run_probe(project_path, lint_cmd.filter(|_| config.lint), timeout),
+        )
+    };
+
+    let last_commit_age_secs = crate::game::git::last_commit_time(project_path)
+        .map(|committed| chrono::Utc::now().timestamp() - committed);
+
+    HealthReport {
+        project_id: project_id.to_string(),
+        checked_at: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        build,
+        tests,
+        lint,
+        last_commit_age_secs,
+    }
+}
+
+fn run_probe(project_path: &Path, command: Option<&[&str]>, timeout: Duration) -> CheckStatus {
+    let Some((program, args)) = command.and_then(|c| c.split_first()) else {
+        return CheckStatus::Skipped;
+    };
+
+    let Ok(mut child) = Command::new(program)
+        .args(args)
+        .current_dir(project_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        // Toolchain not installed
+        return CheckStatus::Skipped;
+    };
+
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return CheckStatus::Passing,
+            Ok(Some(_)) => return CheckStatus::Failing,
+            Ok(None) if started.elapsed() > timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return CheckStatus::Skipped;
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(250)),
+            Err(_) => return CheckStatus::Skipped,
+        }
+    }
+}
@@ -4,6 +4,8 @@ pub mod camera;
 pub mod cli;
 pub mod components;
 pub mod entities;
+pub mod git;
+pub mod health;
 pub mod project;
 pub mod resources;
 pub mod systems;
@@ -0,0 +1,390 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::game::project::{Mission, Project};
+use crate::game::project::state_sync::{export_project_state, import_project_state};
+use crate::game::project::zac_files::ZacProjectConfig;
+use crate::game::resources::ProjectManager;
+use crate::game::systems::MissionManager;
+
+/// Roadmap file picked up as the mission source when present
+const ROADMAP_FILE: &str = "PROJECT_ROADMAP.md";
+
+/// Upper bound on missions harvested from TODO/FIXME comments
+const MAX_TODO_MISSIONS: usize = 25;
+
+/// Upper bound on files scanned for TODO/FIXME comments
+const MAX_SCANNED_FILES: usize = 2000;
+
+/// Directories never scanned for TODO/FIXME comments
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules", "dist", "build", "venv", "__pycache__", "vendor"];
+
+/// Source file extensions scanned for TODO/FIXME comments
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "js", "jsx", "ts", "tsx", "py", "go", "java", "kt", "c", "h", "cpp", "hpp",
+    "cs", "rb", "swift", "php", "lua", "sh",
+];
+
+/// Marker files used to detect language and build system, in priority order
+const BUILD_MARKERS: &[(&str, &str, &str)] = &[
+    ("Cargo.toml", "rust", "cargo"),
+    ("go.mod", "go", "go"),
+    ("pyproject.toml", "python", "pyproject"),
+    ("setup.py", "python", "setuptools"),
+    ("requirements.txt", "python", "pip"),
+    ("package.json", "javascript", "npm"),
+    ("pom.xml", "java", "maven"),
+    ("build.gradle", "java", "gradle"),
+    ("build.gradle.kts", "kotlin", "gradle"),
+    ("Gemfile", "ruby", "bundler"),
+    ("CMakeLists.txt", "cpp", "cmake"),
+    ("Makefile", "unknown", "make"),
+];
+
+/// Where a candidate mission was found
+#[derive(Debug, Clone, PartialEq)]
+pub enum MissionSource {
+    Roadmap,
+    TodoComment { file: String, line: usize },
+}
+
+/// A mission proposed by scouting a repository
+#[derive(Debug, Clone)]
+pub struct CandidateMission {
+    pub title: String,
+    pub description: String,
+    pub source: MissionSource,
+}
+
+/// Result of scouting a directory before conquering it
+#[derive(Debug, Clone)]
+pub struct ConquestReport {
+    pub name: String,
+    pub path: PathBuf,
+    pub language: String,
+    pub build_system: String,
+    pub suggested_theme: String,
+    pub candidate_missions: Vec<CandidateMission>,
+}
+
+pub struct ProjectConquest;
+
+impl ProjectConquest {
+    /// Inspect a local repository without touching the database
+    pub fn scout(path: &Path) -> Result<ConquestReport, String> {
+        let path = path.canonicalize()
+            .map_err(|e| format!("Cannot access {}: {e}", path.display()))?;
+
+        if !path.is_dir() {
+            return Err(format!("{} is not a directory", path.display()));
+        }
+
+        if !path.join(".git").exists() {
+            return Err(format!("{} is not a git repository", path.display()));
+        }
+
+        let name = path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Unnamed Project".to_string());
+
+        let (language, build_system) = Self::detect_build_system(&path);
+        let suggested_theme = Self::suggest_theme(&language, &build_system).to_string();
+
+        let roadmap_path = path.join(ROADMAP_FILE);
+        let candidate_missions = if roadmap_path.exists() {
+            let content = fs::read_to_string(&roadmap_path)
+                .map_err(|e| format!("Failed to read {ROADMAP_FILE}: {e}"))?;
+            Self::parse_roadmap(&content)
+        } else {
+            Self::scan_todo_comments(&path)
+        };
+
+        Ok(ConquestReport {
+            name,
+            path,
+            language,
+            build_system,
+            suggested_theme,
+            candidate_missions,
+        })
+    }
+
+    /// Detect (language, build_system) from marker files in the project root
+    pub fn detect_build_system(path: &Path) -> (String, String) {
+        for (marker, language, build_system) in BUILD_MARKERS {
+            if path.join(marker).exists() {
+                let language = if *marker == "package.json" && path.join("tsconfig.json").exists() {
+                    "typescript"
+                } else {
+                    language
+                };
+                return (language.to_string(), build_system.to_string());
+            }
+        }
+
+        ("unknown".to_string(), "none".to_string())
+    }
+
+    /// Pick a building theme that fits the project's stack
+    fn suggest_theme(language: &str, build_system: &str) -> &'static str {
+        match (language, build_system) {
+            ("rust", _) | ("cpp", _) => "factory",
+            ("go", _) => "rocket",
+            ("javascript", _) | ("typescript", _) => "lighthouse",
+            ("python", _) | ("ruby", _) => "medieval_tower",
+            (_, "maven") | (_, "gradle") => "factory",
+            _ => "generic",
+        }
+    }
+
+    /// Turn roadmap checkboxes (or headings, if there are none) into missions
+    fn parse_roadmap(content: &str) -> Vec<CandidateMission> {
+        let mut missions = Vec::new();
+        let mut section = String::new();
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+
+            if let Some(heading) = trimmed.strip_prefix("## ") {
+                section = heading.trim().to_string();
+                continue;
+            }
+
+            if let Some(item) = trimmed.strip_prefix("- [ ]").or_else(|| trimmed.strip_prefix("* [ ]")) {
+                let title = item.trim();
+                if title.is_empty() {
+                    continue;
+                }
+                missions.push(CandidateMission {
+                    title: title.to_string(),
+                    description: if section.is_empty() {
+                        title.to_string()
+                    } else {
+                        format!("{section}: {title}")
+                    },
+                    source: MissionSource::Roadmap,
+                });
+            }
+        }
+
+        if missions.is_empty() {
+            missions = content.lines()
+                .filter_map(|line| line.trim().strip_prefix("## "))
+                .map(|heading| CandidateMission {
+                    title: heading.trim().to_string(),
+                    description: format!("Roadmap section: {}", heading.trim()),
+                    source: MissionSource::Roadmap,
+                })
+                .collect();
+        }
+
+        missions
+    }
+
+    /// Collect TODO/FIXME comments from source files as missions
+    fn scan_todo_comments(root: &Path) -> Vec<CandidateMission> {
+        let mut missions = Vec::new();
+        let mut scanned = 0;
+        let mut stack = vec![root.to_path_buf()];
+
+        while let Some(dir) = stack.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else { continue };
+
+            let mut entries: Vec<_> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+            entries.sort();
+
+            for entry in entries {
+                let file_name = entry.file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+
+                if entry.is_dir() {
+                    if !file_name.starts_with('.') && !SKIPPED_DIRS.contains(&file_name.as_str()) {
+                        stack.push(entry);
+                    }
+                    continue;
+                }
+
+                let is_source = entry.extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext));
+                if !is_source {
+                    continue;
+                }
+
+                scanned += 1;
+                if scanned > MAX_SCANNED_FILES {
+                    return missions;
+                }
+
+                let Ok(content) = fs::read_to_string(&entry) else { continue };
+                let relative = entry.strip_prefix(root).unwrap_or(&entry).to_string_lossy().to_string();
+
+                for (index, line) in content.lines().enumerate() {
+                    let Some(text) = Self::extract_todo(line) else { continue };
+
+                    missions.push(CandidateMission {
+                        title: text.clone(),
+                        description: format!("{text}\n\nFound at {relative}:{}", index + 1),
+                        source: MissionSource::TodoComment {
+                            file: relative.clone(),
+                            line: index + 1,
+                        },
+                    });
+
+                    if missions.len() >= MAX_TODO_MISSIONS {
+                        return missions;
+                    }
+                }
+            }
+        }
+
+        missions
+    }
+
+    /// Extract the text after a TODO/FIXME marker, if the line has one
+    fn extract_todo(line: &str) -> Option<String> {
+        for marker in ["TODO", "FIXME"] {
+            if let Some(pos) = line.find(marker) {
+                let rest = line[pos + marker.len()..]
+                    .trim_start_matches(|c: char| c == ':' || c == '(' || c.is_whitespace());
+                // Skip "(name):" style attributions
+                let rest = match rest.find("):") {
+                    Some(end) if end < 24 => rest[end + 2..].trim(),
+                    _ => rest.trim(),
+                };
+                let rest = rest.trim_end_matches("*/").trim();
+                if rest.is_empty() {
+                    return None;
+                }
+                return Some(format!("{marker}: {rest}"));
+            }
+        }
+        None
+    }
+}
+
+/// Conquer a directory: register it, write `.zac/config.toml` and seed missions.
+///
+/// A directory that already carries `.zac/` state (moved or cloned from another
+/// machine) keeps its project id and missions instead of being re-scouted.
+pub fn conquer_project(
+    path: &Path,
+    project_manager: &ProjectManager,
+    mission_manager: &MissionManager,
+) -> Result<Project, String> {
+    let report = ProjectConquest::scout(path)?;
+    let path_str = report.path.to_string_lossy().to_string();
+
+    if let Some(project_id) = project_manager.find_project_by_path(&path_str)? {
+        if !project_manager.is_archived(&project_id)? {
+            return Err(format!("{} is already a managed project", report.name));
+        }
+
+        // Re-conquering an archived project brings it back
+        project_manager.restore_project(&project_id)?;
+        println!("♻️  Restored archived project '{}'", report.name);
+        return project_manager.load_projects()?
+            .into_iter()
+            .find(|p| p.id == project_id)
+            .ok_or_else(|| format!("Project {project_id} vanished after restore"));
+    }
+
+    if let Some(existing) = ZacProjectConfig::read(&report.path)? {
+        return adopt_project(existing, &report, project_manager, mission_manager);
+    }
+
+    let project_id = project_manager.create_project(
+        report.name.clone(),
+        path_str.clone(),
+        report.suggested_theme.clone(),
+    )?;
+
+    let project = discard_on_error(
+        seed_missions(project_id.clone(), path_str, &report, project_manager, mission_manager),
+        &project_id,
+        project_manager,
+    )?;
+
+    let from_roadmap = report.candidate_missions.iter()
+        .filter(|m| m.source == MissionSource::Roadmap)
+        .count();
+
+    println!("⚔️  Conquered '{}' ({} / {}) - theme '{}'",
+             report.name, report.language, report.build_system, report.suggested_theme);
+    println!("   {} missions ({} from roadmap, {} from TODO/FIXME comments)",
+             project.total_missions, from_roadmap, project.total_missions as usize - from_roadmap);
+
+    Ok(project)
+}
+
+/// Seed a newly created project with the scouted missions and export its `.zac/` state
+fn seed_missions(
+    project_id: String,
+    path_str: String,
+    report: &ConquestReport,
+    project_manager: &ProjectManager,
+    mission_manager: &MissionManager,
+) -> Result<Project, String> {
+    for (index, candidate) in report.candidate_missions.iter().enumerate() {
+        let mut mission = Mission::new(project_id.clone(), index as u32 + 1, candidate.title.clone());
+        mission.description = candidate.description.clone();
+        mission_manager.create_mission(&mission)?;
+    }
+
+    let total_missions = report.candidate_missions.len() as u32;
+    project_manager.update_total_missions(&project_id, total_missions)?;
+
+    let project = Project {
+        id: project_id,
+        name: report.name.clone(),
+        path: path_str,
+        building_theme: report.suggested_theme.clone(),
+        total_missions,
+        completed_missions: 0,
+    };
+    export_project_state(&project, project_manager, mission_manager)?;
+
+    Ok(project)
+}
+
+/// Delete a half-imported project on error, so conquering the directory can be retried
+fn discard_on_error<T>(result: Result<T, String>, project_id: &str, project_manager: &ProjectManager) -> Result<T, String> {
+    if result.is_err() {
+        if let Err(e) = project_manager.delete_project(project_id) {
+            eprintln!("Failed to discard half-imported project {project_id}: {e}");
+        }
+    }
+    result
+}
+
+fn adopt_project(
+    existing: ZacProjectConfig,
+    report: &ConquestReport,
+    project_manager: &ProjectManager,
+    mission_manager: &MissionManager,
+) -> Result<Project, String> {
+    let path_str = report.path.to_string_lossy().to_string();
+
+    project_manager.create_project_with_id(
+        existing.project.id.clone(),
+        existing.project.name.clone(),
+        path_str,
+        existing.project.building_theme.clone(),
+    )?;
+
+    discard_on_error(
+        import_project_state(&existing.project.id, &report.path, project_manager, mission_manager),
+        &existing.project.id,
+        project_manager,
+    )?;
+
+    let project = project_manager.load_projects()?
+        .into_iter()
+        .find(|p| p.id == existing.project.id)
+        .ok_or_else(|| format!("Project {} vanished after import", existing.project.id))?;
+
+    println!("⚔️  Conquered '{}' from existing .zac/ state - {}/{} missions complete",
+             project.name, project.completed_missions, project.total_missions);
+
+    Ok(project)
+}
@@ -0,0 +1,44 @@
+/// Domains a mission can be tagged with, used for worker specialties
+pub const DOMAINS: [&str; 4] = ["rust", "frontend", "docs", "tests"];
+
+/// Path fragments and extensions that point at a domain
+const PATH_HINTS: &[(&str, &[&str])] = &[
+    ("rust", &[".rs", "cargo.toml", "src-tauri/"]),
+    ("frontend", &[".tsx", ".jsx", ".ts", ".js", ".css", ".scss", ".html", ".vue", ".svelte", "ui/", "components/"]),
+    ("docs", &[".md", ".rst", "docs/", "readme"]),
+    ("tests", &["tests/", "test_", "_test.", ".test.", ".spec.", "__tests__"]),
+];
+
+/// Whole words that point at a domain
+const KEYWORD_HINTS: &[(&str, &[&str])] = &[
+    ("rust", &["rust", "cargo", "crate", "clippy", "borrow", "trait", "bevy", "tokio", "serde"]),
+    ("frontend", &["frontend", "ui", "ux", "css", "react", "svelte", "vue", "html", "layout", "button", "page", "component", "styling"]),
+    ("docs", &["docs", "documentation", "readme", "guide", "changelog", "tutorial", "comments"]),
+    ("tests", &["test", "tests", "testing", "coverage", "fixture", "fixtures", "benchmark", "regression"]),
+];
+
+/// Tag a mission from the paths and keywords in its title and description
+pub fn infer_domains(title: &str, description: &str) -> Vec<String> {
+    let text = format!("{title}\n{description}").to_lowercase();
+
+    let tokens: Vec<&str> = text.split_whitespace()
+        .map(|t| t.trim_matches(|c: char| matches!(c, '`' | '"' | '\'' | ',' | ';' | ':' | '(' | ')' | '[' | ']')))
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    let words: Vec<&str> = text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    DOMAINS.iter()
+        .filter(|domain| {
+            let paths = PATH_HINTS.iter().find(|(d, _)| d == *domain).map(|(_, h)| *h).unwrap_or(&[]);
+            let keywords = KEYWORD_HINTS.iter().find(|(d, _)| d == *domain).map(|(_, h)| *h).unwrap_or(&[]);
+
+            // Only tokens that look like paths count for path hints
+            tokens.iter().any(|t| (t.contains('/') || t.contains('.')) && paths.iter().any(|h| t.contains(h)))
+                || words.iter().any(|w| keywords.contains(w))
+        })
+        .map(|d| d.to_string())
+        .collect()
+}
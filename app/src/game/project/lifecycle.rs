@@ -0,0 +1,73 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::game::project::{MissionAttempt, Project};
+use crate::game::project::zac_files::ZacMissionEntry;
+use crate::game::resources::ProjectManager;
+use crate::game::systems::MissionManager;
+
+/// Snapshot of a project written out before it is deleted
+#[derive(Debug, Serialize)]
+pub struct ProjectHistoryExport {
+    pub exported_at: String,
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub building_theme: String,
+    pub total_missions: u32,
+    pub completed_missions: u32,
+    pub missions: Vec<ZacMissionEntry>,
+    pub attempts: Vec<MissionAttempt>,
+}
+
+/// Write a project's history to `<export_dir>/<name>-<timestamp>.json`
+pub fn export_project_history(
+    project: &Project,
+    mission_manager: &MissionManager,
+    export_dir: &Path,
+) -> Result<PathBuf, String> {
+    fs::create_dir_all(export_dir)
+        .map_err(|e| format!("Failed to create export directory: {e}"))?;
+
+    let history = ProjectHistoryExport {
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        id: project.id.clone(),
+        name: project.name.clone(),
+        path: project.path.clone(),
+        building_theme: project.building_theme.clone(),
+        total_missions: project.total_missions,
+        completed_missions: project.completed_missions,
+        missions: mission_manager.load_missions(&project.id)?
+            .iter()
+            .map(ZacMissionEntry::from_mission)
+            .collect(),
+        attempts: mission_manager.load_project_attempts(&project.id)?,
+    };
+
+    let content = serde_json::to_string_pretty(&history)
+        .map_err(|e| format!("JSON error: {e}"))?;
+
+    let safe_name: String = project.name.chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    let filename = format!("{safe_name}-{}.json", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+    let export_path = export_dir.join(filename);
+
+    fs::write(&export_path, content)
+        .map_err(|e| format!("Failed to write history export: {e}"))?;
+
+    Ok(export_path)
+}
+
+/// Export a project's history, then delete it from the database
+pub fn delete_project_with_export(
+    project: &Project,
+    project_manager: &ProjectManager,
+    mission_manager: &MissionManager,
+    export_dir: &Path,
+) -> Result<PathBuf, String> {
+    // Never delete without a successful export
+    let export_path = export_project_history(project, mission_manager, export_dir)?;
+    project_manager.delete_project(&project.id)?;
+    Ok(export_path)
+}
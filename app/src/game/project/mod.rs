@@ -2,6 +2,12 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+pub mod conquest;
+pub mod domains;
+pub mod lifecycle;
+pub mod state_sync;
+pub mod zac_files;
+
 /// Component representing a software project as a building
 #[derive(Component, Debug, Clone)]
 pub struct Project {
@@ -89,14 +95,13 @@ pub struct Mission {
     pub file_path: Option<String>,
     #[allow(dead_code)]
     pub assigned_worker_id: Option<String>,
-    #[allow(dead_code)]
     pub tokens_used: u32,
-    #[allow(dead_code)]
     pub completion_summary: Option<String>,
+    /// Domain tags (see [`domains::DOMAINS`]) used to match workers
+    pub domains: Vec<String>,
 }
 
 impl Mission {
-    #[allow(dead_code)]
     pub fn new(project_id: String, mission_number: u32, title: String) -> Self {
         Self {
             id: Uuid::new_v4().to_string(),
@@ -110,6 +115,16 @@ impl Mission {
             assigned_worker_id: None,
             tokens_used: 0,
             completion_summary: None,
+            domains: Vec::new(),
+        }
+    }
+
+    /// Stored tags, or tags inferred from the title and description
+    pub fn domains_or_inferred(&self) -> Vec<String> {
+        if self.domains.is_empty() {
+            domains::infer_domains(&self.title, &self.description)
+        } else {
+            self.domains.clone()
         }
     }
 
@@ -121,3 +136,17 @@ impl Mission {
         self.dependencies.iter().all(|dep| completed_missions.contains(dep))
     }
 }
+
+/// One run of a mission by a worker
+#[derive(Debug, Clone, Serialize)]
+pub struct MissionAttempt {
+    pub mission_id: String,
+    pub worker_id: String,
+    pub started_at: String,
+    pub success: bool,
+    pub duration_secs: u64,
+    pub tokens_used: u32,
+    pub usage: crate::game::cli::usage::UsageBreakdown,
+    pub cost_usd: f64,
+    pub git: crate::game::git::GitMissionResult,
+}
@@ -0,0 +1,149 @@
+use std::path::Path;
+use crate::game::project::Project;
+use crate::game::project::conquest::ProjectConquest;
+use crate::game::project::zac_files::{
+    ZacBuildingSection, ZacMissionEntry, ZacProjectConfig, ZacProjectSection, ZacTasksFile,
+};
+use crate::game::resources::{ProjectManager, ProjectSyncState};
+use crate::game::systems::MissionManager;
+
+/// Outcome of reconciling a project's `.zac/` files with SQLite
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncOutcome {
+    /// Database copy won and was written to `.zac/`
+    Exported,
+    /// `.zac/` copy was newer and was merged into the database
+    Imported,
+    /// Both copies already agree
+    InSync,
+}
+
+/// Reconcile one project on startup.
+///
+/// Rules:
+/// - No `.zac/config.toml`, or one naming a different project id: the database wins.
+/// - `.zac/` `updated_at` newer than `projects.last_updated`: files are imported.
+///   Missions merge by number (see [`MissionManager::import_mission`]), then the
+///   merged result is written back.
+/// - Otherwise the database wins.
+pub fn reconcile_project(
+    project: &Project,
+    project_manager: &ProjectManager,
+    mission_manager: &MissionManager,
+) -> Result<SyncOutcome, String> {
+    let project_path = Path::new(&project.path);
+    let db_state = project_manager.load_sync_state(&project.id)?;
+
+    let files_are_newer = match ZacProjectConfig::read(project_path)? {
+        Some(config) if config.project.id == project.id => {
+            config.project.updated_at > db_state.last_updated
+        }
+        Some(config) => {
+            println!("⚠️  .zac/ in {} belongs to project {} - overwriting with database copy",
+                     project.path, config.project.id);
+            false
+        }
+        None => false,
+    };
+
+    if files_are_newer {
+        import_project_state(&project.id, project_path, project_manager, mission_manager)?;
+        export_project_state(project, project_manager, mission_manager)?;
+        return Ok(SyncOutcome::Imported);
+    }
+
+    if export_project_state(project, project_manager, mission_manager)? {
+        Ok(SyncOutcome::Exported)
+    } else {
+        Ok(SyncOutcome::InSync)
+    }
+}
+
+/// Write the database copy of a project to `.zac/`, returning whether anything changed
+pub fn export_project_state(
+    project: &Project,
+    project_manager: &ProjectManager,
+    mission_manager: &MissionManager,
+) -> Result<bool, String> {
+    let project_path = Path::new(&project.path);
+    if !project_path.is_dir() {
+        return Err(format!("Project directory {} not found", project.path));
+    }
+
+    let db_state = project_manager.load_sync_state(&project.id)?;
+
+    // Keep detected stack info from an existing config rather than re-detecting
+    let (language, build_system) = match ZacProjectConfig::read(project_path).ok().flatten() {
+        Some(existing) => (existing.project.language, existing.project.build_system),
+        None => ProjectConquest::detect_build_system(project_path),
+    };
+
+    let config = ZacProjectConfig {
+        project: ZacProjectSection {
+            id: project.id.clone(),
+            name: project.name.clone(),
+            language,
+            build_system,
+            building_theme: db_state.building_theme.clone(),
+            updated_at: db_state.last_updated.clone(),
+        },
+        building: ZacBuildingSection {
+            stage: db_state.visual_stage,
+            position: db_state.position,
+        },
+    };
+
+    let tasks = ZacTasksFile {
+        missions: mission_manager.load_missions(&project.id)?
+            .iter()
+            .map(ZacMissionEntry::from_mission)
+            .collect(),
+    };
+
+    let config_changed = config.write(project_path)?;
+    let tasks_changed = tasks.write(project_path)?;
+
+    Ok(config_changed || tasks_changed)
+}
+
+/// Merge `.zac/` state into the database for `project_id`
+pub fn import_project_state(
+    project_id: &str,
+    project_path: &Path,
+    project_manager: &ProjectManager,
+    mission_manager: &MissionManager,
+) -> Result<(), String> {
+    let config = ZacProjectConfig::read(project_path)?
+        .ok_or_else(|| format!("No .zac/config.toml in {}", project_path.display()))?;
+
+    if let Some(tasks) = ZacTasksFile::read(project_path)? {
+        for entry in &tasks.missions {
+            mission_manager.import_mission(&entry.to_mission(project_id))?;
+        }
+
+        // Missions removed from the file go from the database too
+        let listed: Vec<u32> = tasks.missions.iter().map(|m| m.mission_number).collect();
+        let removed = mission_manager.delete_missions_except(project_id, &listed)?;
+        if removed > 0 {
+            println!("   🗑️  Removed {removed} missions no longer in .zac/tasks.json");
+        }
+    }
+
+    let missions = mission_manager.load_missions(project_id)?;
+    let completed = missions.iter()
+        .filter(|m| m.status == crate::game::project::MissionStatus::Completed)
+        .count() as u32;
+
+    project_manager.update_total_missions(project_id, missions.len() as u32)?;
+    project_manager.update_mission_count(project_id, completed)?;
+
+    // Applied last so `last_updated` matches the file timestamp
+    project_manager.apply_sync_state(project_id, &ProjectSyncState {
+        building_theme: config.project.building_theme,
+        visual_stage: config.building.stage,
+        position: config.building.position,
+        last_updated: config.project.updated_at,
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::game::project::{Mission, MissionStatus};
+
+/// Name of the per-project state directory
+pub const ZAC_DIR: &str = ".zac";
+
+/// Contents of `.zac/config.toml`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZacProjectConfig {
+    pub project: ZacProjectSection,
+    #[serde(default)]
+    pub building: ZacBuildingSection,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZacProjectSection {
+    pub id: String,
+    pub name: String,
+    #[serde(default = "unknown")]
+    pub language: String,
+    #[serde(default = "unknown")]
+    pub build_system: String,
+    pub building_theme: String,
+    /// SQLite `CURRENT_TIMESTAMP` format (UTC) of the last change
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ZacBuildingSection {
+    pub stage: u8,
+    pub position: [f32; 3],
+}
+
+fn unknown() -> String {
+    "unknown".to_string()
+}
+
+impl ZacProjectConfig {
+    pub fn path_for(project_path: &Path) -> PathBuf {
+        project_path.join(ZAC_DIR).join("config.toml")
+    }
+
+    /// Write config to `<project>/.zac/config.toml`, returning whether it changed
+    pub fn write(&self, project_path: &Path) -> Result<bool, String> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| format!("TOML error: {e}"))?;
+
+        write_if_changed(&Self::path_for(project_path), &content)
+    }
+
+    /// Read config from `<project>/.zac/config.toml`, if present
+    pub fn read(project_path: &Path) -> Result<Option<Self>, String> {
+        let path = Self::path_for(project_path);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read .zac/config.toml: {e}"))?;
+
+        toml::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("Invalid .zac/config.toml: {e}"))
+    }
+}
+
+/// Contents of `.zac/tasks.json`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ZacTasksFile {
+    pub missions: Vec<ZacMissionEntry>,
+}
+
+/// A mission as stored in `.zac/tasks.json` (keyed by mission number, not id)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZacMissionEntry {
+    pub mission_number: u32,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub status: String,
+    #[serde(default)]
+    pub dependencies: Vec<u32>,
+    #[serde(default)]
+    pub tokens_used: u32,
+    #[serde(default)]
+    pub completion_summary: Option<String>,
+}
+
+impl ZacMissionEntry {
+    pub fn from_mission(mission: &Mission) -> Self {
+        Self {
+            mission_number: mission.mission_number,
+            title: mission.title.clone(),
+            description: mission.description.clone(),
+            status: mission.status.as_str().to_string(),
+            dependencies: mission.dependencies.clone(),
+            tokens_used: mission.tokens_used,
+            completion_summary: mission.completion_summary.clone()
+                .filter(|s| !s.is_empty()),
+        }
+    }
+
+    /// Convert into a mission for `project_id`.
+    ///
+    /// In-progress missions come back as not started, since the process that
+    /// was running them does not exist on this machine.
+    pub fn to_mission(&self, project_id: &str) -> Mission {
+        let status = match MissionStatus::from_str(&self.status) {
+            MissionStatus::InProgress => MissionStatus::NotStarted,
+            status => status,
+        };
+
+        let mut mission = Mission::new(project_id.to_string(), self.mission_number, self.title.clone());
+        mission.description = self.description.clone();
+        mission.status = status;
+        mission.dependencies = self.dependencies.clone();
+        mission.tokens_used = self.tokens_used;
+        mission.completion_summary = self.completion_summary.clone();
+        mission
+    }
+}
+
+impl ZacTasksFile {
+    pub fn path_for(project_path: &Path) -> PathBuf {
+        project_path.join(ZAC_DIR).join("tasks.json")
+    }
+
+    /// Write tasks to `<project>/.zac/tasks.json`, returning whether it changed
+    pub fn write(&self, project_path: &Path) -> Result<bool, String> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("JSON error: {e}"))?;
+
+        write_if_changed(&Self::path_for(project_path), &content)
+    }
+
+    /// Read tasks from `<project>/.zac/tasks.json`, if present
+    pub fn read(project_path: &Path) -> Result<Option<Self>, String> {
+        let path = Self::path_for(project_path);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read .zac/tasks.json: {e}"))?;
+
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("Invalid .zac/tasks.json: {e}"))
+    }
+}
+
+/// Write a state file only when its content differs, to keep git diffs quiet
+fn write_if_changed(path: &Path, content: &str) -> Result<bool, String> {
+    if fs::read_to_string(path).is_ok_and(|existing| existing == content) {
+        return Ok(false);
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create .zac directory: {e}"))?;
+    }
+
+    fs::write(path, content)
+        .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+
+    Ok(true)
+}
@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use rusqlite::Connection;
 use std::path::PathBuf;
+use crate::game::health::{CheckStatus, HealthReport};
+use crate::game::systems::autonomy_schedule::{AutonomyWindow, ProjectBlackout};
 use tauri::AppHandle;
 
 #[derive(Resource)]
@@ -26,29 +28,59 @@ impl ProjectManager {
         Self { db_path }
     }
 
-    #[allow(dead_code)]
-    pub fn create_project(&self, name: String, path: String) -> Result<String, String> {
+    pub fn create_project(&self, name: String, path: String, building_theme: String) -> Result<String, String> {
+        let id = uuid::Uuid::new_v4().to_string();
+        self.create_project_with_id(id, name, path, building_theme)
+    }
+
+    /// Insert a project under a known id (e.g. one carried in `.zac/config.toml`)
+    pub fn create_project_with_id(&self, id: String, name: String, path: String, building_theme: String) -> Result<String, String> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| format!("Database error: {e}"))?;
 
-        let id = uuid::Uuid::new_v4().to_string();
-
         conn.execute(
-            "INSERT INTO projects (id, name, path) VALUES (?1, ?2, ?3)",
-            [&id, &name, &path],
+            "INSERT INTO projects (id, name, path, building_theme) VALUES (?1, ?2, ?3, ?4)",
+            [&id, &name, &path, &building_theme],
         ).map_err(|e| format!("Failed to insert project: {e}"))?;
 
         Ok(id)
     }
 
+    pub fn find_project_by_path(&self, path: &str) -> Result<Option<String>, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let result = conn.query_row(
+            "SELECT id FROM projects WHERE path = ?1",
+            [path],
+            |row| row.get(0),
+        );
+
+        match result {
+            Ok(id) => Ok(Some(id)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(format!("Query error: {e}")),
+        }
+    }
+
+    /// Load active (non-archived) projects
     pub fn load_projects(&self) -> Result<Vec<crate::game::project::Project>, String> {
+        self.query_projects("WHERE archived_at IS NULL ORDER BY created_at ASC")
+    }
+
+    /// Load archived projects, most recently archived first
+    pub fn load_archived_projects(&self) -> Result<Vec<crate::game::project::Project>, String> {
+        self.query_projects("WHERE archived_at IS NOT NULL ORDER BY archived_at DESC")
+    }
+
+    fn query_projects(&self, filter: &str) -> Result<Vec<crate::game::project::Project>, String> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| format!("Database error: {e}"))?;
 
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare(&format!(
             "SELECT id, name, path, building_theme, total_missions, completed_missions
-             FROM projects ORDER BY created_at DESC"
-        ).map_err(|e| format!("Query error: {e}"))?;
+             FROM projects {filter}"
+        )).map_err(|e| format!("Query error: {e}"))?;
 
         let projects = stmt.query_map([], |row| {
             Ok(crate::game::project::Project {
@@ -69,6 +101,87 @@ impl ProjectManager {
         Ok(result)
     }
 
+    pub fn is_archived(&self, project_id: &str) -> Result<bool, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        conn.query_row(
+            "SELECT archived_at IS NOT NULL FROM projects WHERE id = ?1",
+            [project_id],
+            |row| row.get(0),
+        ).map_err(|e| format!("Query error: {e}"))
+    }
+
+    /// Hide a project from the world and the scheduler, keeping its history
+    pub fn archive_project(&self, project_id: &str) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        conn.execute(
+            "UPDATE projects SET archived_at = CURRENT_TIMESTAMP, last_updated = CURRENT_TIMESTAMP
+             WHERE id = ?1",
+            [project_id],
+        ).map_err(|e| format!("Archive error: {e}"))?;
+
+        Ok(())
+    }
+
+    pub fn restore_project(&self, project_id: &str) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        conn.execute(
+            "UPDATE projects SET archived_at = NULL, last_updated = CURRENT_TIMESTAMP
+             WHERE id = ?1",
+            [project_id],
+        ).map_err(|e| format!("Restore error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Permanently delete a project and everything hanging off it.
+    ///
+    /// Missions, their attempts and health history are deleted; knowledge entries and workers
+    /// only lose their link to those missions.
+    pub fn delete_project(&self, project_id: &str) -> Result<(), String> {
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let tx = conn.transaction()
+            .map_err(|e| format!("Transaction error: {e}"))?;
+
+        tx.execute(
+            "UPDATE knowledge_entries SET mission_id = NULL
+             WHERE mission_id IN (SELECT id FROM missions WHERE project_id = ?1)",
+            [project_id],
+        ).map_err(|e| format!("Delete error: {e}"))?;
+
+        tx.execute(
+            "UPDATE workers SET current_task_id = NULL
+             WHERE current_task_id IN (SELECT id FROM missions WHERE project_id = ?1)",
+            [project_id],
+        ).map_err(|e| format!("Delete error: {e}"))?;
+
+        tx.execute(
+            "DELETE FROM mission_attempts
+             WHERE mission_id IN (SELECT id FROM missions WHERE project_id = ?1)",
+            [project_id],
+        ).map_err(|e| format!("Delete error: {e}"))?;
+
+        tx.execute("DELETE FROM project_health WHERE project_id = ?1", [project_id])
+            .map_err(|e| format!("Delete error: {e}"))?;
+
+        tx.execute("DELETE FROM missions WHERE project_id = ?1", [project_id])
+            .map_err(|e| format!("Delete error: {e}"))?;
+
+        tx.execute("DELETE FROM projects WHERE id = ?1", [project_id])
+            .map_err(|e| format!("Delete error: {e}"))?;
+
+        tx.commit().map_err(|e| format!("Commit error: {e}"))?;
+
+        Ok(())
+    }
+
     pub fn update_mission_count(&self, project_id: &str, completed: u32) -> Result<(), String> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| format!("Database error: {e}"))?;
@@ -81,21 +194,358 @@ impl ProjectManager {
 
         Ok(())
     }
+
+    pub fn update_total_missions(&self, project_id: &str, total: u32) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        conn.execute(
+            "UPDATE projects SET total_missions = ?1, last_updated = CURRENT_TIMESTAMP
+             WHERE id = ?2",
+            [&total.to_string(), project_id],
+        ).map_err(|e| format!("Update error: {e}"))?;
+
+        Ok(())
+    }
+
+    pub fn update_visual_stage(&self, project_id: &str, stage: u8) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        conn.execute(
+            "UPDATE projects SET visual_stage = ?1, last_updated = CURRENT_TIMESTAMP
+             WHERE id = ?2 AND visual_stage != ?1",
+            [&stage.to_string(), project_id],
+        ).map_err(|e| format!("Update error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Number of health checks recorded across all projects
+    pub fn count_health_checks(&self) -> Result<usize, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM project_health", [], |row| row.get(0))
+            .map_err(|e| format!("Query error: {e}"))?;
+
+        Ok(count as usize)
+    }
+
+    /// Projects (archived or not) with every mission completed
+    pub fn count_completed_projects(&self) -> Result<usize, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM projects WHERE total_missions > 0 AND completed_missions >= total_missions",
+            [],
+            |row| row.get(0),
+        ).map_err(|e| format!("Query error: {e}"))?;
+
+        Ok(count as usize)
+    }
+
+    /// Save the theme a project's building is drawn in
+    pub fn update_building_theme(&self, project_id: &str, theme: &str) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        conn.execute(
+            "UPDATE projects SET building_theme = ?1, last_updated = CURRENT_TIMESTAMP
+             WHERE id = ?2",
+            [theme, project_id],
+        ).map_err(|e| format!("Update error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Load a project's saved building position (`None` until one is assigned)
+    pub fn load_position(&self, project_id: &str) -> Result<Option<Vec3>, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let position: Vec3 = conn.query_row(
+            "SELECT position_x, position_y, position_z FROM projects WHERE id = ?1",
+            [project_id],
+            |row| Ok(Vec3::new(row.get(0)?, row.get(1)?, row.get(2)?)),
+        ).map_err(|e| format!("Query error: {e}"))?;
+
+        // The origin belongs to the Town Hall, so it doubles as "unplaced"
+        if position == Vec3::ZERO {
+            Ok(None)
+        } else {
+            Ok(Some(position))
+        }
+    }
+
+    pub fn save_position(&self, project_id: &str, position: Vec3) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        conn.execute(
+            "UPDATE projects
+             SET position_x = ?1, position_y = ?2, position_z = ?3, last_updated = CURRENT_TIMESTAMP
+             WHERE id = ?4",
+            rusqlite::params![position.x, position.y, position.z, project_id],
+        ).map_err(|e| format!("Update error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Append one health check to the project's time series
+    pub fn record_health(&self, report: &HealthReport) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        conn.execute(
+            "INSERT INTO project_health (project_id, checked_at, build_status, test_status,
+                                         lint_status, last_commit_age_seconds)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                report.project_id,
+                report.checked_at,
+                report.build.as_str(),
+                report.tests.as_str(),
+                report.lint.as_str(),
+                report.last_commit_age_secs,
+            ],
+        ).map_err(|e| format!("Insert error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Most recent health check for a project, if it has ever been checked
+    pub fn load_latest_health(&self, project_id: &str) -> Result<Option<HealthReport>, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let result = conn.query_row(
+            "SELECT checked_at, build_status, test_status, lint_status, last_commit_age_seconds
+             FROM project_health WHERE project_id = ?1
+             ORDER BY checked_at DESC, id DESC LIMIT 1",
+            [project_id],
+            |row| Ok(HealthReport {
+                project_id: project_id.to_string(),
+                checked_at: row.get(0)?,
+                build: CheckStatus::from_str(&row.get::<_, String>(1)?),
+                tests: CheckStatus::from_str(&row.get::<_, String>(2)?),
+                lint: CheckStatus::from_str(&row.get::<_, String>(3)?),
+                last_commit_age_secs: row.get(4)?,
+            }),
+        );
+
+        match result {
+            Ok(report) => Ok(Some(report)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(format!("Query error: {e}")),
+        }
+    }
+
+    /// Load the project columns mirrored into `.zac/` state files
+    pub fn load_sync_state(&self, project_id: &str) -> Result<ProjectSyncState, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        conn.query_row(
+            "SELECT building_theme, visual_stage, position_x, position_y, position_z, last_updated
+             FROM projects WHERE id = ?1",
+            [project_id],
+            |row| {
+                Ok(ProjectSyncState {
+                    building_theme: row.get(0)?,
+                    visual_stage: row.get(1)?,
+                    position: [row.get(2)?, row.get(3)?, row.get(4)?],
+                    last_updated: row.get(5)?,
+                })
+            },
+        ).map_err(|e| format!("Query error: {e}"))
+    }
+
+    /// Overwrite mirrored columns with state imported from `.zac/`
+    pub fn apply_sync_state(&self, project_id: &str, state: &ProjectSyncState) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        conn.execute(
+            "UPDATE projects
+             SET building_theme = ?1, visual_stage = ?2,
+                 position_x = ?3, position_y = ?4, position_z = ?5,
+                 last_updated = COALESCE(NULLIF(?6, ''), CURRENT_TIMESTAMP)
+             WHERE id = ?7",
+            rusqlite::params![
+                state.building_theme,
+                state.visual_stage,
+                state.position[0],
+                state.position[1],
+                state.position[2],
+                state.last_updated,
+                project_id,
+            ],
+        ).map_err(|e| format!("Update error: {e}"))?;
+
+        Ok(())
+    }
+}
+
+/// Project columns mirrored into `.zac/` state files
+#[derive(Debug, Clone)]
+pub struct ProjectSyncState {
+    pub building_theme: String,
+    pub visual_stage: u8,
+    pub position: [f32; 3],
+    /// SQLite `CURRENT_TIMESTAMP` format (UTC), compared lexically
+    pub last_updated: String,
+}
+
+/// Resource holding directories waiting to be conquered as projects
+#[derive(Resource, Default)]
+pub struct ConquestQueue {
+    pub pending: Vec<PathBuf>,
+}
+
+impl ConquestQueue {
+    /// Collect `--conquer <path>` arguments from the command line
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Self {
+        let mut pending = Vec::new();
+
+        while let Some(arg) = args.next() {
+            if arg == "--conquer" {
+                if let Some(path) = args.next() {
+                    pending.push(PathBuf::from(path));
+                }
+            } else if let Some(path) = arg.strip_prefix("--conquer=") {
+                pending.push(PathBuf::from(path));
+            }
+        }
+
+        Self { pending }
+    }
+}
+
+/// Resource for managing utility buildings
+#[derive(Resource)]
+pub struct BuildingManager {
+    pub db_path: PathBuf,
+}
+
+/// A utility building as stored in SQLite
+#[derive(Debug, Clone)]
+pub struct UtilityBuildingRecord {
+    pub id: String,
+    pub building_type: crate::game::components::BuildingType,
+    pub stage: u8,
+    pub position: Vec3,
+}
+
+impl BuildingManager {
+    pub fn new(db_path: PathBuf) -> Self {
+        Self { db_path }
+    }
+
+    pub fn create_building(&self, building_type: crate::game::components::BuildingType, position: Vec3) -> Result<String, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let id = uuid::Uuid::new_v4().to_string();
+
+        conn.execute(
+            "INSERT INTO buildings (id, building_type, stage, position_x, position_y, position_z)
+             VALUES (?1, ?2, 1, ?3, ?4, ?5)",
+            rusqlite::params![id, building_type.as_str(), position.x, position.y, position.z],
+        ).map_err(|e| format!("Failed to insert building: {e}"))?;
+
+        Ok(id)
+    }
+
+    pub fn load_buildings(&self) -> Result<Vec<UtilityBuildingRecord>, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, building_type, stage, position_x, position_y, position_z
+             FROM buildings ORDER BY created_at ASC"
+        ).map_err(|e| format!("Query error: {e}"))?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, u8>(2)?,
+                Vec3::new(row.get(3)?, row.get(4)?, row.get(5)?),
+            ))
+        }).map_err(|e| format!("Map error: {e}"))?;
+
+        let mut result = Vec::new();
+        for row in rows {
+            let (id, type_str, stage, position) = row.map_err(|e| format!("Row error: {e}"))?;
+
+            // Skip building types this version doesn't know about
+            if let Some(building_type) = crate::game::components::BuildingType::from_str(&type_str) {
+                result.push(UtilityBuildingRecord { id, building_type, stage, position });
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub fn update_stage(&self, building_id: &str, stage: u8) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        conn.execute(
+            "UPDATE buildings SET stage = ?1 WHERE id = ?2",
+            rusqlite::params![stage, building_id],
+        ).map_err(|e| format!("Update error: {e}"))?;
+
+        Ok(())
+    }
+
+    pub fn save_position(&self, building_id: &str, position: Vec3) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        conn.execute(
+            "UPDATE buildings SET position_x = ?1, position_y = ?2, position_z = ?3 WHERE id = ?4",
+            rusqlite::params![position.x, position.y, position.z, building_id],
+        ).map_err(|e| format!("Update error: {e}"))?;
+
+        Ok(())
+    }
+}
+
+/// Stages of the utility buildings standing in town
+#[derive(Resource, Default, Debug)]
+pub struct TownFacilities {
+    pub library: Option<u8>,
+    pub workshop: Option<u8>,
+    pub barracks: Option<u8>,
+}
+
+/// Extra worker slots per Barracks stage
+const BARRACKS_WORKERS_PER_STAGE: usize = 2;
+
+impl TownFacilities {
+    /// Worker cap: the Town Hall's limit plus whatever the Barracks adds
+    pub fn worker_cap(&self, base: usize) -> usize {
+        base + self.barracks.unwrap_or(0) as usize * BARRACKS_WORKERS_PER_STAGE
+    }
+
+    /// How many health checks the Workshop can run at once (none without one)
+    pub fn health_check_slots(&self) -> usize {
+        self.workshop.map_or(0, |stage| 1 + stage as usize / 4)
+    }
 }
 
 /// Resource for managing workers
 #[derive(Resource)]
 pub struct WorkerManager {
     pub db_path: PathBuf,
-    pub max_workers: usize,
 }
 
 impl WorkerManager {
     pub fn new(db_path: PathBuf) -> Self {
-        Self {
-            db_path,
-            max_workers: 20, // Configurable limit
-        }
+        Self { db_path }
     }
 
     pub fn create_worker(&self, name: String, color: (f32, f32, f32)) -> Result<String, String> {
@@ -126,19 +576,35 @@ impl WorkerManager {
 
         let mut stmt = conn.prepare(
             "SELECT id, name, color_r, color_g, color_b, state, current_task_id,
-                    total_tasks_completed, total_tokens_used
+                    total_tasks_completed, total_tokens_used, specialty_scores_json, state_json,
+                    profile_json, total_cost_usd
              FROM workers"
         ).map_err(|e| format!("Query error: {e}"))?;
 
         let workers = stmt.query_map([], |row| {
+            // Older rows stored an empty string for "no task"
+            let current_task_id = row.get::<_, Option<String>>(6)?.filter(|id| !id.is_empty());
+            let state = crate::game::worker::WorkerState::from_saved(
+                row.get::<_, Option<String>>(10)?.as_deref(),
+                &row.get::<_, Option<String>>(5)?.unwrap_or_default(),
+                current_task_id.as_deref(),
+            );
+
             Ok(crate::game::worker::Worker {
                 id: row.get(0)?,
                 name: row.get(1)?,
                 color: Color::srgb(row.get(2)?, row.get(3)?, row.get(4)?),
-                state: crate::game::worker::WorkerState::from_str(&row.get::<_, String>(5)?),
-                current_task_id: row.get(6)?,
+                state,
+                current_task_id,
                 total_tasks_completed: row.get::<_, i32>(7)? as u32,
                 total_tokens_used: row.get::<_, i64>(8)? as u64,
+                total_cost_usd: row.get::<_, Option<f64>>(12)?.unwrap_or(0.0),
+                specialty_scores: row.get::<_, Option<String>>(9)?
+                    .and_then(|s| serde_json::from_str(&s).ok())
+                    .unwrap_or_default(),
+                profile: row.get::<_, Option<String>>(11)?
+                    .and_then(|s| serde_json::from_str(&s).ok())
+                    .unwrap_or_default(),
             })
         }).map_err(|e| format!("Map error: {e}"))?;
 
@@ -154,29 +620,251 @@ impl WorkerManager {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| format!("Database error: {e}"))?;
 
+        let state_json = serde_json::to_string(state)
+            .map_err(|e| format!("JSON error: {e}"))?;
+
+        // `state` keeps the short name for readability; `state_json` is the full state
         conn.execute(
-            "UPDATE workers SET state = ?1, current_task_id = ?2 WHERE id = ?3",
-            [state.as_str(), task_id.unwrap_or(""), worker_id],
+            "UPDATE workers SET state = ?1, state_json = ?2, current_task_id = ?3 WHERE id = ?4",
+            rusqlite::params![state.as_str(), state_json, task_id, worker_id],
         ).map_err(|e| format!("Update error: {e}"))?;
 
         Ok(())
     }
 
-    pub fn increment_worker_stats(&self, worker_id: &str, tokens: u64) -> Result<(), String> {
+    pub fn increment_worker_stats(&self, worker_id: &str, tokens: u64, cost_usd: f64) -> Result<(), String> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| format!("Database error: {e}"))?;
 
         conn.execute(
             "UPDATE workers
              SET total_tasks_completed = total_tasks_completed + 1,
-                 total_tokens_used = total_tokens_used + ?1
-             WHERE id = ?2",
-            [&tokens.to_string(), worker_id],
+                 total_tokens_used = total_tokens_used + ?1,
+                 total_cost_usd = COALESCE(total_cost_usd, 0) + ?2
+             WHERE id = ?3",
+            rusqlite::params![tokens as i64, cost_usd, worker_id],
         ).map_err(|e| format!("Update error: {e}"))?;
 
         Ok(())
     }
 
+    pub fn save_specialty_scores(&self, worker_id: &str, scores: &std::collections::HashMap<String, f32>) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let json = serde_json::to_string(scores)
+            .map_err(|e| format!("JSON error: {e}"))?;
+
+        conn.execute(
+            "UPDATE workers SET specialty_scores_json = ?1 WHERE id = ?2",
+            [&json, worker_id],
+        ).map_err(|e| format!("Update error: {e}"))?;
+
+        Ok(())
+    }
+
+    pub fn save_profile(&self, worker_id: &str, profile: &crate::game::worker::profile::WorkerProfile) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let json = serde_json::to_string(profile)
+            .map_err(|e| format!("JSON error: {e}"))?;
+
+        conn.execute(
+            "UPDATE workers SET profile_json = ?1 WHERE id = ?2",
+            [&json, worker_id],
+        ).map_err(|e| format!("Update error: {e}"))?;
+
+        Ok(())
+    }
+
+    pub fn add_note(&self, worker_id: &str, mission_id: &str, note: &str) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        conn.execute(
+            "INSERT INTO worker_notes (worker_id, mission_id, note) VALUES (?1, ?2, ?3)",
+            [worker_id, mission_id, note],
+        ).map_err(|e| format!("Insert error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Replace the stored production queue with `orders`, keeping their order
+    pub fn save_production_queue(&self, orders: &[crate::game::systems::worker_spawner::WorkerProductionOrder]) -> Result<(), String> {
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let tx = conn.transaction()
+            .map_err(|e| format!("Transaction error: {e}"))?;
+
+        tx.execute("DELETE FROM production_orders", [])
+            .map_err(|e| format!("Delete error: {e}"))?;
+
+        for (position, order) in orders.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO production_orders (id, position, worker_name, queued_at, started_at, duration_secs, token_cost)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                rusqlite::params![
+                    order.id,
+                    position as i64,
+                    order.worker_name,
+                    order.queued_at.to_rfc3339(),
+                    order.started_at.map(|t| t.to_rfc3339()),
+                    order.duration,
+                    order.token_cost as i64,
+                ],
+            ).map_err(|e| format!("Insert error: {e}"))?;
+        }
+
+        tx.commit().map_err(|e| format!("Commit error: {e}"))
+    }
+
+    pub fn load_production_queue(&self) -> Result<Vec<crate::game::systems::worker_spawner::WorkerProductionOrder>, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, worker_name, queued_at, started_at, duration_secs, token_cost
+             FROM production_orders ORDER BY position ASC"
+        ).map_err(|e| format!("Query error: {e}"))?;
+
+        let parse_time = |s: String| chrono::DateTime::parse_from_rfc3339(&s).ok().map(|t| t.with_timezone(&chrono::Utc));
+
+        let orders = stmt.query_map([], |row| {
+            Ok(crate::game::systems::worker_spawner::WorkerProductionOrder {
+                id: row.get(0)?,
+                worker_name: row.get(1)?,
+                queued_at: parse_time(row.get(2)?).unwrap_or_else(chrono::Utc::now),
+                started_at: row.get::<_, Option<String>>(3)?.and_then(parse_time),
+                duration: row.get::<_, f64>(4)? as f32,
+                token_cost: row.get::<_, i64>(5)? as u64,
+            })
+        }).map_err(|e| format!("Map error: {e}"))?;
+
+        let mut result = Vec::new();
+        for order in orders {
+            result.push(order.map_err(|e| format!("Row error: {e}"))?);
+        }
+
+        Ok(result)
+    }
+
+    /// Names of all current workers
+    pub fn worker_names(&self) -> Result<Vec<String>, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let mut stmt = conn.prepare("SELECT name FROM workers")
+            .map_err(|e| format!("Query error: {e}"))?;
+
+        let names = stmt.query_map([], |row| row.get(0))
+            .map_err(|e| format!("Map error: {e}"))?
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(|e| format!("Row error: {e}"))?;
+
+        Ok(names)
+    }
+
+    /// Rename a worker, refusing names another worker already has
+    pub fn rename_worker(&self, worker_id: &str, name: &str) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let taken: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM workers WHERE name = ?1 AND id != ?2",
+            [name, worker_id],
+            |row| row.get(0),
+        ).map_err(|e| format!("Query error: {e}"))?;
+
+        if taken > 0 {
+            return Err(format!("Another worker is already named '{name}'"));
+        }
+
+        conn.execute("UPDATE workers SET name = ?1 WHERE id = ?2", [name, worker_id])
+            .map_err(|e| format!("Update error: {e}"))?;
+
+        Ok(())
+    }
+
+    pub fn recolor_worker(&self, worker_id: &str, color: Color) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let srgba = color.to_srgba();
+        conn.execute(
+            "UPDATE workers SET color_r = ?1, color_g = ?2, color_b = ?3 WHERE id = ?4",
+            rusqlite::params![srgba.red, srgba.green, srgba.blue, worker_id],
+        ).map_err(|e| format!("Update error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Remove a worker for good; its notes go with it
+    pub fn dismiss_worker(&self, worker_id: &str) -> Result<(), String> {
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let tx = conn.transaction()
+            .map_err(|e| format!("Transaction error: {e}"))?;
+
+        tx.execute("DELETE FROM worker_notes WHERE worker_id = ?1", [worker_id])
+            .map_err(|e| format!("Delete error: {e}"))?;
+        tx.execute("DELETE FROM workers WHERE id = ?1", [worker_id])
+            .map_err(|e| format!("Delete error: {e}"))?;
+
+        tx.commit().map_err(|e| format!("Commit error: {e}"))
+    }
+
+    /// Move a worker into the hall of fame, keeping its stats and notes
+    pub fn retire_worker(&self, worker_id: &str) -> Result<(), String> {
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let tx = conn.transaction()
+            .map_err(|e| format!("Transaction error: {e}"))?;
+
+        tx.execute(
+            "INSERT OR REPLACE INTO retired_workers (id, name, color_r, color_g, color_b, total_tasks_completed,
+                                                    total_tokens_used, total_cost_usd, specialty_scores_json, hired_at)
+             SELECT id, name, color_r, color_g, color_b, total_tasks_completed,
+                    total_tokens_used, total_cost_usd, specialty_scores_json, created_at
+             FROM workers WHERE id = ?1",
+            [worker_id],
+        ).map_err(|e| format!("Insert error: {e}"))?;
+        tx.execute("DELETE FROM workers WHERE id = ?1", [worker_id])
+            .map_err(|e| format!("Delete error: {e}"))?;
+
+        tx.commit().map_err(|e| format!("Commit error: {e}"))
+    }
+
+    /// Retired workers, most tasks first
+    pub fn load_hall_of_fame(&self, limit: usize) -> Result<Vec<RetiredWorker>, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT name, total_tasks_completed, total_tokens_used, total_cost_usd
+             FROM retired_workers ORDER BY total_tasks_completed DESC, retired_at ASC LIMIT ?1"
+        ).map_err(|e| format!("Query error: {e}"))?;
+
+        let retired = stmt.query_map([limit as i64], |row| {
+            Ok(RetiredWorker {
+                name: row.get(0)?,
+                total_tasks_completed: row.get::<_, i64>(1)? as u32,
+                total_tokens_used: row.get::<_, i64>(2)? as u64,
+                total_cost_usd: row.get::<_, Option<f64>>(3)?.unwrap_or(0.0),
+            })
+        }).map_err(|e| format!("Map error: {e}"))?;
+
+        let mut result = Vec::new();
+        for worker in retired {
+            result.push(worker.map_err(|e| format!("Row error: {e}"))?);
+        }
+
+        Ok(result)
+    }
+
     pub fn count_workers(&self) -> Result<usize, String> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| format!("Database error: {e}"))?;
@@ -191,6 +879,15 @@ impl WorkerManager {
     }
 }
 
+/// A worker in the hall of fame
+#[derive(Debug, Clone)]
+pub struct RetiredWorker {
+    pub name: String,
+    pub total_tasks_completed: u32,
+    pub total_tokens_used: u64,
+    pub total_cost_usd: f64,
+}
+
 /// Resource for Claude CLI manager
 #[derive(Resource)]
 pub struct CliManagerResource {
@@ -209,8 +906,12 @@ impl CliManagerResource {
 #[derive(Resource)]
 pub struct AutonomySettings {
     pub enabled: bool,
+    /// Concurrency outside every window
     pub max_concurrent_workers: usize,
     pub assignment_interval_secs: f32,
+    /// Time-of-day concurrency rules, first match wins
+    pub windows: Vec<AutonomyWindow>,
+    pub blackouts: Vec<ProjectBlackout>,
 }
 
 impl Default for AutonomySettings {
@@ -219,85 +920,543 @@ impl Default for AutonomySettings {
             enabled: false,  // Start disabled
             max_concurrent_workers: 5,
             assignment_interval_secs: 3.0,
+            windows: Vec::new(),
+            blackouts: Vec::new(),
+        }
+    }
+}
+
+impl AutonomySettings {
+    /// Settings from `[autonomy]`, keeping the last saved on/off toggle if there is one.
+    /// Invalid windows and blackouts are reported and skipped.
+    pub fn from_config(config: &crate::core::config::AutonomyConfig, saved_enabled: Option<bool>) -> Self {
+        let windows = config.windows.iter()
+            .filter_map(|w| AutonomyWindow::from_config(w)
+                .map_err(|e| eprintln!("⚠️  Skipping autonomy window '{}': {e}", w.name))
+                .ok())
+            .collect();
+        let blackouts = config.blackouts.iter()
+            .filter_map(|b| ProjectBlackout::from_config(b)
+                .map_err(|e| eprintln!("⚠️  Skipping blackout for '{}': {e}", b.project))
+                .ok())
+            .collect();
+
+        Self {
+            enabled: saved_enabled.unwrap_or(config.enabled),
+            max_concurrent_workers: config.max_concurrent_workers,
+            assignment_interval_secs: config.assignment_interval_secs,
+            windows,
+            blackouts,
+        }
+    }
+
+    /// Concurrency allowed at `now`, and the window that sets it
+    pub fn concurrency_at(&self, now: chrono::DateTime<chrono::Local>) -> (usize, Option<&str>) {
+        match self.windows.iter().find(|w| w.window.contains(now)) {
+            Some(window) => (window.max_concurrent_workers, Some(window.name.as_str())),
+            None => (self.max_concurrent_workers, None),
         }
     }
+
+    /// Whether autonomy should leave the named project alone at `now`
+    pub fn is_blacked_out(&self, project_name: &str, now: chrono::DateTime<chrono::Local>) -> bool {
+        self.blackouts.iter().any(|b| b.project == project_name && b.window.contains(now))
+    }
+
+    /// Next time after `now` a window or blackout starts or ends
+    pub fn next_change(&self, now: chrono::DateTime<chrono::Local>) -> Option<chrono::DateTime<chrono::Local>> {
+        self.windows.iter().map(|w| &w.window)
+            .chain(self.blackouts.iter().map(|b| &b.window))
+            .filter_map(|w| w.next_boundary(now))
+            .min()
+    }
+}
+
+/// A budget change not yet written to the `token_ledger` table
+#[derive(Debug, Clone)]
+pub struct UsageEntry {
+    /// Negative for refunds
+    pub tokens: i64,
+    pub cost_usd: f64,
+    /// What the tokens were spent on: "mission", "production", ...
+    pub source: &'static str,
+    pub mission_id: Option<String>,
+    pub project_id: Option<String>,
+    pub worker_id: Option<String>,
 }
 
-/// Resource for tracking token usage and budget
+/// Who a charge belongs to; caps are configured by name, usage is counted by id
+#[derive(Debug, Clone, Default)]
+pub struct UsageScope {
+    pub mission_id: Option<String>,
+    pub project_id: Option<String>,
+    pub project_name: Option<String>,
+    pub worker_id: Option<String>,
+    pub worker_name: Option<String>,
+}
+
+impl UsageScope {
+    pub fn mission(mission_id: &str, project_id: Option<&str>, worker_id: &str) -> Self {
+        Self {
+            mission_id: Some(mission_id.to_string()),
+            project_id: project_id.map(str::to_string),
+            worker_id: Some(worker_id.to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// Add the names the per-project and per-worker caps are looked up by
+    pub fn with_names(mut self, project_name: &str, worker_name: Option<&str>) -> Self {
+        self.project_name = Some(project_name.to_string());
+        self.worker_name = worker_name.map(str::to_string);
+        self
+    }
+}
+
+/// Length of a budget window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetWindowKind {
+    /// An hour from the last reset
+    RollingHour,
+    /// Calendar day, from local midnight
+    Day,
+    /// Calendar month, from local midnight on the 1st
+    Month,
+}
+
+impl BudgetWindowKind {
+    pub fn label(&self) -> &str {
+        match self {
+            BudgetWindowKind::RollingHour => "hourly",
+            BudgetWindowKind::Day => "daily",
+            BudgetWindowKind::Month => "monthly",
+        }
+    }
+
+    /// Start of a window of this kind that is open at `now`
+    pub fn start_at(&self, now: chrono::DateTime<chrono::Utc>) -> chrono::DateTime<chrono::Utc> {
+        use chrono::Datelike;
+
+        let today = now.with_timezone(&chrono::Local).date_naive();
+        let first_day = match self {
+            BudgetWindowKind::RollingHour => return now,
+            BudgetWindowKind::Day => Some(today),
+            BudgetWindowKind::Month => today.with_day(1),
+        };
+
+        first_day.and_then(local_midnight).unwrap_or(now)
+    }
+
+    /// When a window of this kind that started at `start` ends
+    pub fn end_of(&self, start: chrono::DateTime<chrono::Utc>) -> chrono::DateTime<chrono::Utc> {
+        use chrono::Datelike;
+
+        let day = start.with_timezone(&chrono::Local).date_naive();
+        match self {
+            BudgetWindowKind::RollingHour => start + chrono::Duration::hours(1),
+            BudgetWindowKind::Day => day.succ_opt()
+                .and_then(local_midnight)
+                .unwrap_or(start + chrono::Duration::days(1)),
+            BudgetWindowKind::Month => {
+                let (year, month) = if day.month() == 12 { (day.year() + 1, 1) } else { (day.year(), day.month() + 1) };
+                chrono::NaiveDate::from_ymd_opt(year, month, 1)
+                    .and_then(local_midnight)
+                    .unwrap_or(start + chrono::Duration::days(30))
+            }
+        }
+    }
+}
+
+fn local_midnight(date: chrono::NaiveDate) -> Option<chrono::DateTime<chrono::Utc>> {
+    date.and_hms_opt(0, 0, 0)?
+        .and_local_timezone(chrono::Local)
+        .earliest()
+        .map(|t| t.with_timezone(&chrono::Utc))
+}
+
+/// What a budget window counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetUnit {
+    Tokens,
+    /// Millionths of a US dollar
+    MicroDollars,
+}
+
+impl BudgetUnit {
+    /// Amount in this unit for a charge of `tokens` costing `cost_usd`
+    pub fn amount(&self, tokens: i64, cost_usd: f64) -> i64 {
+        match self {
+            BudgetUnit::Tokens => tokens,
+            BudgetUnit::MicroDollars => (cost_usd * 1_000_000.0).round() as i64,
+        }
+    }
+
+    /// Amount in this unit for `tokens` priced at `usd_per_token`
+    pub fn amount_for_tokens(&self, tokens: u64, usd_per_token: f64) -> u64 {
+        self.amount(tokens as i64, tokens as f64 * usd_per_token).max(0) as u64
+    }
+
+    /// Tokens `amount` buys at `usd_per_token`
+    pub fn tokens_for(&self, amount: u64, usd_per_token: f64) -> u64 {
+        match self {
+            BudgetUnit::Tokens => amount,
+            BudgetUnit::MicroDollars if usd_per_token > 0.0 => (amount as f64 / 1_000_000.0 / usd_per_token) as u64,
+            BudgetUnit::MicroDollars => u64::MAX,
+        }
+    }
+
+    pub fn format(&self, amount: u64) -> String {
+        match self {
+            BudgetUnit::Tokens => amount.to_string(),
+            BudgetUnit::MicroDollars => format!("${:.2}", amount as f64 / 1_000_000.0),
+        }
+    }
+}
+
+/// One spending limit over a stretch of time
+#[derive(Debug, Clone)]
+pub struct BudgetWindow {
+    pub kind: BudgetWindowKind,
+    pub unit: BudgetUnit,
+    pub limit: u64,
+    pub used: u64,
+    pub start: chrono::DateTime<chrono::Utc>,
+}
+
+impl BudgetWindow {
+    pub fn new(kind: BudgetWindowKind, limit: u64) -> Self {
+        Self {
+            kind,
+            unit: BudgetUnit::Tokens,
+            limit,
+            used: 0,
+            start: kind.start_at(chrono::Utc::now()),
+        }
+    }
+
+    /// Window limited to `limit_usd` dollars instead of tokens
+    pub fn dollars(kind: BudgetWindowKind, limit_usd: f64) -> Self {
+        Self {
+            unit: BudgetUnit::MicroDollars,
+            ..Self::new(kind, BudgetUnit::MicroDollars.amount(0, limit_usd).max(0) as u64)
+        }
+    }
+
+    /// `used/limit`, in tokens or dollars
+    pub fn usage_label(&self) -> String {
+        format!("{}/{}", self.unit.format(self.used), self.unit.format(self.limit))
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.limit.saturating_sub(self.used)
+    }
+
+    /// Share of the limit left, from 0.0 to 1.0
+    pub fn fraction_remaining(&self) -> f32 {
+        if self.limit == 0 {
+            return 1.0;
+        }
+        self.remaining() as f32 / self.limit as f32
+    }
+
+    pub fn ends_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.kind.end_of(self.start)
+    }
+}
+
+/// Daily caps for projects or workers, with today's usage
+#[derive(Debug, Clone, Default)]
+pub struct ScopedCaps {
+    /// Cap for everyone without their own
+    pub default_cap: Option<u64>,
+    /// Caps by project or worker name, or by id so a rename keeps them
+    pub caps: std::collections::HashMap<String, u64>,
+    /// Tokens used today, by project or worker id
+    pub used: std::collections::HashMap<String, u64>,
+}
+
+impl ScopedCaps {
+    /// Caps from config, where 0 means no cap
+    pub fn from_config(default_cap: u64, caps: &std::collections::HashMap<String, u64>) -> Self {
+        Self {
+            default_cap: (default_cap > 0).then_some(default_cap),
+            caps: caps.iter().filter(|(_, cap)| **cap > 0).map(|(k, v)| (k.clone(), *v)).collect(),
+            used: std::collections::HashMap::new(),
+        }
+    }
+
+    pub fn cap(&self, id: &str, name: &str) -> Option<u64> {
+        self.caps.get(id).or_else(|| self.caps.get(name)).copied().or(self.default_cap)
+    }
+
+    /// Move a cap set by name to the new name; returns it if there was one
+    pub fn rename(&mut self, old_name: &str, new_name: &str) -> Option<u64> {
+        let cap = self.caps.remove(old_name)?;
+        self.caps.insert(new_name.to_string(), cap);
+        Some(cap)
+    }
+
+    pub fn used(&self, id: &str) -> u64 {
+        self.used.get(id).copied().unwrap_or(0)
+    }
+
+    fn charge(&mut self, id: Option<&str>, tokens: i64) {
+        let Some(id) = id else { return };
+        let used = self.used.entry(id.to_string()).or_insert(0);
+        *used = (*used as i64 + tokens).max(0) as u64;
+    }
+
+    /// Refuse when `estimated_tokens` more would pass the named cap
+    pub fn check(&self, what: &str, id: Option<&str>, name: Option<&str>, estimated_tokens: u64) -> Result<(), String> {
+        let (Some(id), Some(name)) = (id, name) else { return Ok(()) };
+        let Some(cap) = self.cap(id, name) else { return Ok(()) };
+
+        let used = self.used(id);
+        if used + estimated_tokens > cap {
+            return Err(format!("{what} '{name}' would pass its daily cap ({used}/{cap} used, needs ~{estimated_tokens})"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Resource tracking token usage against every budget window and cap
 #[derive(Resource)]
 pub struct TokenBudget {
-    pub hourly_limit: u64,
-    pub current_period_used: u64,
-    pub period_start: chrono::DateTime<chrono::Utc>,
-    pub period_duration_hours: i64,
+    /// Rolling hour first, then the daily and monthly windows that are enabled
+    pub windows: Vec<BudgetWindow>,
+    pub projects: ScopedCaps,
+    pub workers: ScopedCaps,
+    /// Day the per-project and per-worker usage counts towards
+    pub scoped_day_start: chrono::DateTime<chrono::Utc>,
     pub warning_threshold: f32,  // 0.0 to 1.0
+    /// Price of a token for the default model, to compare tokens with dollar windows
+    pub usd_per_token: f64,
+    /// Bumped whenever a window resets, so deferred work knows to retry
+    pub resets: u32,
+    /// Charges and refunds waiting for `persist_token_budget`
+    pub unsaved: Vec<UsageEntry>,
 }
 
 impl TokenBudget {
     pub fn new(hourly_limit: u64) -> Self {
         Self {
-            hourly_limit,
-            current_period_used: 0,
-            period_start: chrono::Utc::now(),
-            period_duration_hours: 1,
+            windows: vec![BudgetWindow::new(BudgetWindowKind::RollingHour, hourly_limit)],
+            projects: ScopedCaps::default(),
+            workers: ScopedCaps::default(),
+            scoped_day_start: BudgetWindowKind::Day.start_at(chrono::Utc::now()),
             warning_threshold: 0.2,  // Warn at 20% remaining
+            usd_per_token: 0.0,
+            resets: 0,
+            unsaved: Vec::new(),
         }
     }
 
-    #[allow(dead_code)]
-    pub fn add_usage(&mut self, tokens: u64) {
-        self.current_period_used += tokens;
+    /// Budget with the windows, caps and warning threshold from `[tokens]`;
+    /// a dollar limit above 0 replaces the token limit of its window
+    pub fn from_config(config: &crate::core::config::TokensConfig, usd_per_token: f64) -> Self {
+        let mut budget = Self::new(config.hourly_budget);
+        budget.warning_threshold = config.warning_threshold;
+        budget.usd_per_token = usd_per_token;
+
+        if config.hourly_budget_usd > 0.0 {
+            budget.windows[0] = BudgetWindow::dollars(BudgetWindowKind::RollingHour, config.hourly_budget_usd);
+        }
+
+        for (kind, limit, limit_usd) in [
+            (BudgetWindowKind::Day, config.daily_budget, config.daily_budget_usd),
+            (BudgetWindowKind::Month, config.monthly_budget, config.monthly_budget_usd),
+        ] {
+            if limit_usd > 0.0 {
+                budget.windows.push(BudgetWindow::dollars(kind, limit_usd));
+            } else if limit > 0 {
+                budget.windows.push(BudgetWindow::new(kind, limit));
+            }
+        }
+
+        budget.projects = ScopedCaps::from_config(config.project_daily_cap, &config.project_caps);
+        budget.workers = ScopedCaps::from_config(config.worker_daily_cap, &config.worker_caps);
+        budget
     }
 
-    pub fn remaining(&self) -> u64 {
-        self.hourly_limit.saturating_sub(self.current_period_used)
+    /// Rebuild usage from the `token_ledger` table, continuing the saved hourly period
+    pub fn restore_from_ledger(&mut self, conn: &Connection, hourly_start: Option<chrono::DateTime<chrono::Utc>>) {
+        use crate::core::database;
+
+        if let Some(start) = hourly_start {
+            if chrono::Utc::now() < BudgetWindowKind::RollingHour.end_of(start) {
+                let (tokens, cost) = database::period_ledger_totals(conn, &start.to_rfc3339()).unwrap_or((0, 0.0));
+                let hourly = &mut self.windows[0];
+                hourly.start = start;
+                hourly.used = hourly.unit.amount(tokens, cost).max(0) as u64;
+            }
+        }
+
+        for window in self.windows.iter_mut().skip(1) {
+            let (tokens, cost) = database::ledger_totals_since(conn, &ledger_time(window.start)).unwrap_or((0, 0.0));
+            window.used = window.unit.amount(tokens, cost).max(0) as u64;
+        }
+
+        let since = ledger_time(self.scoped_day_start);
+        self.projects.used = database::token_usage_by(conn, "project_id", &since).unwrap_or_default();
+        self.workers.used = database::token_usage_by(conn, "worker_id", &since).unwrap_or_default();
     }
 
-    pub fn percentage_used(&self) -> f32 {
-        if self.hourly_limit == 0 {
-            return 0.0;
+    /// The rolling hour window
+    pub fn hourly(&self) -> &BudgetWindow {
+        &self.windows[0]
+    }
+
+    /// Window with the smallest share left
+    pub fn tightest(&self) -> &BudgetWindow {
+        self.windows.iter()
+            .min_by(|a, b| a.fraction_remaining().total_cmp(&b.fraction_remaining()))
+            .unwrap_or(&self.windows[0])
+    }
+
+    /// Charge `tokens` that cost `cost_usd` (negative when correcting an earlier estimate)
+    pub fn add_usage(&mut self, tokens: u64, cost_usd: f64, source: &'static str, scope: &UsageScope) {
+        self.charge(tokens as i64, cost_usd, source, scope);
+    }
+
+    /// Charge tokens spent outside a model run (e.g. training), priced at the default model
+    pub fn add_flat_usage(&mut self, tokens: u64, source: &'static str) {
+        self.charge(tokens as i64, tokens as f64 * self.usd_per_token, source, &UsageScope::default());
+    }
+
+    /// Give back tokens charged at `charged_at` with `add_flat_usage`. Nothing is
+    /// given back once any window has rolled past that charge, since the refund
+    /// would otherwise come out of a period that never paid for it
+    pub fn refund(&mut self, tokens: u64, charged_at: chrono::DateTime<chrono::Utc>, source: &'static str) -> bool {
+        if self.windows.iter().any(|w| charged_at < w.start) {
+            return false;
         }
-        (self.current_period_used as f32 / self.hourly_limit as f32) * 100.0
+
+        self.charge(-(tokens as i64), -(tokens as f64 * self.usd_per_token), source, &UsageScope::default());
+        true
+    }
+
+    fn charge(&mut self, tokens: i64, cost_usd: f64, source: &'static str, scope: &UsageScope) {
+        for window in self.windows.iter_mut() {
+            window.used = (window.used as i64 + window.unit.amount(tokens, cost_usd)).max(0) as u64;
+        }
+        self.projects.charge(scope.project_id.as_deref(), tokens);
+        self.workers.charge(scope.worker_id.as_deref(), tokens);
+
+        self.unsaved.push(UsageEntry {
+            tokens,
+            cost_usd,
+            source,
+            mission_id: scope.mission_id.clone(),
+            project_id: scope.project_id.clone(),
+            worker_id: scope.worker_id.clone(),
+        });
+    }
+
+    /// Tokens left in the tightest window, dollar windows priced at the default model
+    pub fn remaining(&self) -> u64 {
+        self.windows.iter()
+            .map(|w| w.unit.tokens_for(w.remaining(), self.usd_per_token))
+            .min()
+            .unwrap_or(0)
     }
 
     pub fn percentage_remaining(&self) -> f32 {
-        100.0 - self.percentage_used()
+        self.tightest().fraction_remaining() * 100.0
+    }
+
+    /// Whether a mission estimated at `estimated_tokens` may start, keeping
+    /// `headroom_per_mission` tokens for each of the `in_flight` missions already running.
+    /// Dollar windows price the tokens at `usd_per_token`.
+    pub fn check_launch(&self, estimated_tokens: u64, usd_per_token: f64, in_flight: usize, headroom_per_mission: u64, scope: &UsageScope) -> Result<(), String> {
+        if let Some(window) = self.windows.iter().find(|w| w.used >= w.limit) {
+            return Err(format!("{} budget depleted", window.kind.label()));
+        }
+
+        let reserved_tokens = in_flight as u64 * headroom_per_mission;
+        for window in &self.windows {
+            let needed = window.unit.amount_for_tokens(estimated_tokens, usd_per_token);
+            let reserved = window.unit.amount_for_tokens(reserved_tokens, usd_per_token);
+            let available = window.remaining().saturating_sub(reserved);
+            if needed > available {
+                return Err(format!("needs ~{}, {} left in the {} budget after reserving {} for {in_flight} running missions",
+                                   window.unit.format(needed), window.unit.format(available),
+                                   window.kind.label(), window.unit.format(reserved)));
+            }
+        }
+
+        self.projects.check("project", scope.project_id.as_deref(), scope.project_name.as_deref(), estimated_tokens)?;
+        self.workers.check("worker", scope.worker_id.as_deref(), scope.worker_name.as_deref(), estimated_tokens)?;
+
+        Ok(())
     }
 
     pub fn is_depleted(&self) -> bool {
-        self.current_period_used >= self.hourly_limit
+        self.windows.iter().any(|w| w.used >= w.limit)
     }
 
     pub fn is_low(&self) -> bool {
         self.percentage_remaining() / 100.0 <= self.warning_threshold
     }
 
+    /// Time until the tightest window resets
     pub fn time_until_reset(&self) -> chrono::Duration {
-        let next_reset = self.period_start + chrono::Duration::hours(self.period_duration_hours);
-        next_reset - chrono::Utc::now()
+        self.tightest().ends_at() - chrono::Utc::now()
     }
 
-    pub fn should_reset(&self) -> bool {
-        chrono::Utc::now() >= self.period_start + chrono::Duration::hours(self.period_duration_hours)
-    }
+    /// Start fresh windows for those that ended, returning which ones reset
+    pub fn roll_windows(&mut self) -> Vec<BudgetWindowKind> {
+        let now = chrono::Utc::now();
+        let mut reset = Vec::new();
+
+        for window in self.windows.iter_mut() {
+            if now >= window.ends_at() {
+                window.start = window.kind.start_at(now);
+                window.used = 0;
+                reset.push(window.kind);
+            }
+        }
 
-    pub fn reset_period(&mut self) {
-        self.current_period_used = 0;
-        self.period_start = chrono::Utc::now();
-        println!("🔄 Token budget reset! New period started.");
+        // Per-project and per-worker caps are daily
+        if now >= BudgetWindowKind::Day.end_of(self.scoped_day_start) {
+            self.scoped_day_start = BudgetWindowKind::Day.start_at(now);
+            self.projects.used.clear();
+            self.workers.used.clear();
+            if !reset.contains(&BudgetWindowKind::Day) {
+                reset.push(BudgetWindowKind::Day);
+            }
+        }
+
+        if !reset.is_empty() {
+            self.resets += 1;
+        }
+
+        reset
     }
 
     pub fn estimated_burn_rate_per_hour(&self) -> f32 {
-        let elapsed_hours = (chrono::Utc::now() - self.period_start).num_minutes() as f32 / 60.0;
+        let hourly = self.hourly();
+        let elapsed_hours = (chrono::Utc::now() - hourly.start).num_minutes() as f32 / 60.0;
 
         if elapsed_hours < 0.1 {
             return 0.0;
         }
 
-        self.current_period_used as f32 / elapsed_hours
+        match hourly.unit {
+            BudgetUnit::Tokens => hourly.used as f32 / elapsed_hours,
+            BudgetUnit::MicroDollars if self.usd_per_token > 0.0 => {
+                hourly.unit.tokens_for(hourly.used, self.usd_per_token) as f32 / elapsed_hours
+            }
+            BudgetUnit::MicroDollars => 0.0,
+        }
     }
 }
 
+/// `recorded_at` format in the ledger (SQLite's CURRENT_TIMESTAMP, UTC)
+fn ledger_time(time: chrono::DateTime<chrono::Utc>) -> String {
+    time.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
 impl Default for TokenBudget {
     fn default() -> Self {
         Self::new(50000)  // Default 50k tokens per hour
@@ -1,10 +1,19 @@
 use bevy::prelude::*;
-use crate::game::worker::{Worker, WorkerState};
+use std::collections::HashSet;
+use crate::game::worker::{specialty, Worker, WorkerState};
 use crate::game::project::Project;
 use crate::game::systems::{MissionManager, MovementTarget};
-use crate::game::resources::{WorkerManager, AutonomySettings};
+use crate::game::systems::estimation::MissionEstimator;
+use crate::game::systems::scheduling::{MissionCandidate, Scheduler};
+use crate::game::resources::{WorkerManager, AutonomySettings, TokenBudget, UsageScope};
+use crate::game::systems::token_tracker::BudgetGate;
+use crate::game::systems::zac_journal::{JournalEntryType, JournalManager};
+use crate::core::config::AppConfig;
+use crate::core::database;
+use crate::Database;
 
-/// System to automatically assign idle workers to available missions
+/// System to automatically assign idle workers to available missions, in the order
+/// the configured [`SchedulingStrategy`](crate::game::systems::scheduling::SchedulingStrategy) ranks them
 pub fn autonomous_task_assignment(
     mut commands: Commands,
     mut worker_query: Query<(Entity, &mut Worker, &Transform)>,
@@ -12,6 +21,12 @@ pub fn autonomous_task_assignment(
     mission_manager: Res<MissionManager>,
     worker_manager: Res<WorkerManager>,
     autonomy: Res<AutonomySettings>,
+    mut scheduler: ResMut<Scheduler>,
+    estimator: Res<MissionEstimator>,
+    token_budget: Res<TokenBudget>,
+    mut budget_gate: ResMut<BudgetGate>,
+    journal: Res<JournalManager>,
+    config: Res<AppConfig>,
     time: Res<Time>,
     mut last_assignment: Local<f32>,
 ) {
@@ -32,115 +47,182 @@ pub fn autonomous_task_assignment(
         .filter(|(_, w, _)| matches!(w.state, WorkerState::Working { .. } | WorkerState::MovingTo { .. }))
         .count();
 
-    if active_workers >= autonomy.max_concurrent_workers {
-        // At capacity
+    // Concurrency follows the time-of-day windows
+    let now = chrono::Local::now();
+    let (max_concurrent, _) = autonomy.concurrency_at(now);
+    if active_workers >= max_concurrent {
+        // At capacity, or in a quiet period
         return;
     }
 
+    if token_budget.is_depleted() {
+        // Resumes on its own once check_budget_reset starts a new period
+        return;
+    }
+
+    // Missions already claimed by a worker on its way or waiting out the budget
+    let claimed: HashSet<String> = worker_query.iter()
+        .filter_map(|(_, w, _)| w.current_task_id.clone())
+        .chain(budget_gate.deferred.iter().map(|d| d.mission_id.clone()))
+        .collect();
+
     // Find idle/ready workers
     let mut idle_workers: Vec<_> = worker_query.iter_mut()
-        .filter(|(_, w, _)| w.state == WorkerState::Idle || w.state == WorkerState::Ready)
+        .filter(|(_, w, _)| w.is_free())
         .collect();
 
     if idle_workers.is_empty() {
         return;
     }
 
-    // Build priority queue of available missions
-    let mut mission_candidates: Vec<(String, String, Vec3, f32)> = Vec::new();
+    // Gather available missions with what the strategy needs to score them
+    let mut candidates: Vec<MissionCandidate> = Vec::new();
 
     for (project, project_transform) in project_query.iter() {
+        if autonomy.is_blacked_out(&project.name, now) {
+            continue;
+        }
+
+        let project_missions = match mission_manager.load_missions(&project.id) {
+            Ok(missions) => missions,
+            Err(_) => continue,
+        };
         let available_missions = match mission_manager.get_available_missions(&project.id) {
             Ok(missions) if !missions.is_empty() => missions,
             _ => continue,
         };
+        let attempts = mission_manager.load_project_attempts(&project.id).unwrap_or_default();
 
-        for mission in available_missions {
-            // Calculate priority score
-            let priority = calculate_mission_priority(
-                project.completed_missions,
-                project.total_missions,
-                mission.mission_number,
-            );
-
-            mission_candidates.push((
-                mission.id.clone(),
-                project.id.clone(),
+        for mission in available_missions.into_iter().filter(|m| !claimed.contains(&m.id)) {
+            let estimate = estimator.estimate(&mission);
+            candidates.push(MissionCandidate::new(
+                mission,
+                &project.name,
+                &project_missions,
+                &attempts,
+                estimate,
                 project_transform.translation,
-                priority,
+                scheduler.project_assignments(&project.id),
             ));
         }
     }
 
-    // Sort by priority (highest first)
-    mission_candidates.sort_by(|a, b| b.3.partial_cmp(&a.3).unwrap());
-
-    // Assign workers to top missions
-    for (mission_id, _project_id, building_pos, priority) in mission_candidates.iter() {
-        if idle_workers.is_empty() {
-            break;
+    // Missions the budget can't cover right now, even on the cheapest idle worker's model,
+    // wait for the next period
+    let headroom = config.tokens.headroom_per_mission;
+    let rate = |worker: &Worker| config.pricing.estimate_rate(worker.profile.model.as_deref());
+    let usd_per_token = idle_workers.iter().map(|(_, w, _)| rate(w)).fold(f64::INFINITY, f64::min);
+    candidates.retain(|c| {
+        let scope = UsageScope {
+            project_id: Some(c.project_id.clone()),
+            project_name: Some(c.project_name.clone()),
+            ..UsageScope::default()
+        };
+        match token_budget.check_launch(c.estimate.gate_tokens(), usd_per_token, active_workers, headroom, &scope) {
+            Ok(()) => true,
+            Err(reason) => {
+                if budget_gate.announce(&c.mission.id) {
+                    println!("⏳ Zac^ deferred mission '{}': {reason}", c.mission.title);
+                    journal.record(JournalEntryType::Deferral, &format!("Deferred mission '{}': {reason}", c.mission.title),
+                                   Some(&c.project_id), Some(&c.mission.id));
+                }
+                false
+            }
         }
+    });
 
-        if let Some((worker_entity, mut worker, _)) = idle_workers.pop() {
-            // Assign worker to this mission
-            commands.entity(worker_entity).insert(MovementTarget::new(*building_pos));
+    // Tokens promised to missions assigned in this pass
+    let mut committed: u64 = 0;
 
-            worker.state = WorkerState::MovingTo { target: *building_pos };
-            worker.current_task_id = Some(mission_id.clone());
+    // Assign the best-scoring mission, rescoring after each pick
+    let mut assigned = 0;
+    while !idle_workers.is_empty() && active_workers + assigned < max_concurrent {
+        let Some((candidate_index, score)) = scheduler.pick(&candidates) else { break };
+        let candidate = candidates.swap_remove(candidate_index);
 
-            let _ = worker_manager.update_worker_state(
-                &worker.id,
-                &worker.state,
-                Some(mission_id)
-            );
+        let estimate = candidate.estimate.gate_tokens();
+        let domains = &candidate.mission.domains;
 
-            println!("🤖 Zac^ AUTO-ASSIGNED worker '{}' to mission (priority: {:.2})",
-                     worker.name, priority);
-        }
-    }
-}
+        // Pick the idle worker best suited to the mission's domains, among those the budget
+        // covers at their own model's rate (sharing it with missions assigned earlier in this pass)
+        // and under their cap
+        let best = idle_workers.iter()
+            .enumerate()
+            .filter(|(_, (_, w, _))| {
+                token_budget.check_launch(committed + estimate, rate(w), active_workers, headroom, &UsageScope::default()).is_ok()
+                    && token_budget.workers.check("worker", Some(&w.id), Some(&w.name), estimate).is_ok()
+            })
+            .max_by(|(_, (_, a, _)), (_, (_, b, _))| {
+                specialty::suitability(&a.specialty_scores, domains)
+                    .total_cmp(&specialty::suitability(&b.specialty_scores, domains))
+            })
+            .map(|(index, _)| index);
 
-/// Calculate mission priority score (higher = more urgent)
-fn calculate_mission_priority(
-    completed: u32,
-    total: u32,
-    mission_number: u32,
-) -> f32 {
-    let mut score = 100.0;
-
-    // Prefer projects closer to completion milestones
-    let completion_pct = if total > 0 {
-        completed as f32 / total as f32
-    } else {
-        0.0
-    };
-
-    // Projects near stage boundaries get priority boost
-    let stage = (completion_pct * 10.0).floor();
-    let distance_to_next_stage = ((stage + 1.0) / 10.0) - completion_pct;
-
-    if distance_to_next_stage < 0.1 {
-        score += 50.0; // Close to upgrade!
-    }
+        let Some(index) = best else {
+            if budget_gate.announce(&candidate.mission.id) {
+                println!("⏳ Zac^ deferred mission '{}': no idle worker fits the budget or its daily cap", candidate.mission.title);
+                journal.record(JournalEntryType::Deferral,
+                               &format!("Deferred mission '{}': no idle worker fits the budget or its daily cap", candidate.mission.title),
+                               Some(&candidate.project_id), Some(&candidate.mission.id));
+            }
+            continue;
+        };
+        let (worker_entity, mut worker, _) = idle_workers.swap_remove(index);
+
+        // Assign worker to this mission
+        commands.entity(worker_entity).insert(MovementTarget::new(candidate.building_pos));
+
+        worker.state = WorkerState::MovingTo { target: candidate.building_pos };
+        worker.current_task_id = Some(candidate.mission.id.clone());
 
-    // Earlier missions slightly preferred (unblock dependencies)
-    score -= mission_number as f32 * 0.5;
+        let _ = worker_manager.update_worker_state(
+            &worker.id,
+            &worker.state,
+            Some(&candidate.mission.id)
+        );
 
-    score
+        scheduler.record_assignment(&mut candidates, &candidate.project_id);
+        committed += estimate;
+        assigned += 1;
+
+        println!("🤖 Zac^ AUTO-ASSIGNED worker '{}' to mission {} '{}' (fit: {:.2})",
+                 worker.name, candidate.mission.mission_number, candidate.mission.title,
+                 specialty::suitability(&worker.specialty_scores, domains));
+        println!("   🧮 {}: {}", scheduler.strategy.name(), score);
+        println!("   📏 Estimate: {}", candidate.estimate);
+
+        // A retry is logged once the worker relaunches the mission
+        journal.record(JournalEntryType::Assignment,
+                       &format!("Assigned worker '{}' to mission {} '{}' ({}: {}, estimate {})",
+                                worker.name, candidate.mission.mission_number, candidate.mission.title,
+                                scheduler.strategy.name(), score, candidate.estimate),
+                       Some(&candidate.project_id), Some(&candidate.mission.id));
+    }
 }
 
 /// System to toggle autonomy with 'Z' key
 pub fn toggle_autonomy_keypress(
     keyboard: Res<ButtonInput<KeyCode>>,
     mut autonomy: ResMut<AutonomySettings>,
+    journal: Res<JournalManager>,
+    db: Res<Database>,
 ) {
     if keyboard.just_pressed(KeyCode::KeyZ) {
         autonomy.enabled = !autonomy.enabled;
 
+        // Remembered across restarts
+        if let Ok(conn) = db.0.lock() {
+            if let Err(e) = database::save_state(&conn, "autonomy_enabled", &autonomy.enabled.to_string()) {
+                eprintln!("❌ Failed to save autonomy toggle: {e}");
+            }
+        }
+
         if autonomy.enabled {
             println!("🤖 ZAC^ AUTONOMY ENABLED - Foreman is now assigning tasks automatically");
+            journal.record(JournalEntryType::UserOverride, "Autonomy enabled", None, None);
         } else {
             println!("⏸️  ZAC^ AUTONOMY DISABLED - Manual control restored");
+            journal.record(JournalEntryType::UserOverride, "Autonomy disabled - manual control", None, None);
         }
     }
 }
@@ -148,6 +230,7 @@ pub fn toggle_autonomy_keypress(
 /// System to show autonomy status
 pub fn display_autonomy_status(
     autonomy: Res<AutonomySettings>,
+    scheduler: Res<Scheduler>,
     worker_query: Query<&Worker>,
     time: Res<Time>,
     mut last_display: Local<f32>,
@@ -172,7 +255,11 @@ pub fn display_autonomy_status(
         .filter(|w| matches!(w.state, WorkerState::Working { .. }))
         .count();
 
-    println!("🤖 Zac^ Status: {} idle, {} working, autonomy {}",
+    let (max_concurrent, window) = autonomy.concurrency_at(chrono::Local::now());
+    println!("🤖 Zac^ Status: {} idle, {} working, autonomy {} ({} scheduling, max {} in {})",
              idle_count, working_count,
-             if autonomy.enabled { "ON" } else { "OFF" });
+             if autonomy.enabled { "ON" } else { "OFF" },
+             scheduler.strategy.name(),
+             max_concurrent,
+             window.unwrap_or("default hours"));
 }
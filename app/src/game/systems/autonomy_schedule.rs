@@ -0,0 +1,200 @@
+use bevy::prelude::*;
+use chrono::{DateTime, Datelike, NaiveTime, Weekday};
+use crate::core::config::{AutonomyWindowConfig, ProjectBlackoutConfig};
+use crate::game::resources::AutonomySettings;
+
+/// Days and hours of the week, in local time
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeWindow {
+    /// Empty for every day
+    pub days: Vec<Weekday>,
+    pub start: NaiveTime,
+    /// Before `start` when the window runs past midnight; equal to `start` for the whole day
+    pub end: NaiveTime,
+}
+
+impl TimeWindow {
+    /// Parse `mon`..`sun` day names and `HH:MM` times; `24:00` is the end of the day
+    pub fn parse(days: &[String], start: &str, end: &str) -> Result<Self, String> {
+        let parse_time = |time: &str| match time.trim() {
+            "24:00" => Ok(NaiveTime::MIN),
+            trimmed => NaiveTime::parse_from_str(trimmed, "%H:%M")
+                .map_err(|_| format!("invalid time '{time}' (expected HH:MM)")),
+        };
+
+        let days = days.iter()
+            .map(|day| day.trim().parse::<Weekday>().map_err(|_| format!("invalid day '{day}' (expected mon..sun)")))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            days,
+            start: parse_time(start)?,
+            end: parse_time(end)?,
+        })
+    }
+
+    fn on_day(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+
+    /// Whether `now` falls inside the window; overnight windows belong to the day they start
+    pub fn contains(&self, now: DateTime<chrono::Local>) -> bool {
+        let time = now.time();
+        let today = now.weekday();
+
+        if self.start == self.end {
+            self.on_day(today)
+        } else if self.start < self.end {
+            self.on_day(today) && time >= self.start && time < self.end
+        } else {
+            (self.on_day(today) && time >= self.start) || (self.on_day(today.pred()) && time < self.end)
+        }
+    }
+
+    /// Next time after `now` the window opens or closes, within a week
+    pub fn next_boundary(&self, now: DateTime<chrono::Local>) -> Option<DateTime<chrono::Local>> {
+        (0..=7)
+            .filter_map(|offset| now.date_naive().checked_add_days(chrono::Days::new(offset)))
+            .flat_map(|date| [date.and_time(self.start), date.and_time(self.end)])
+            .filter_map(|time| time.and_local_timezone(chrono::Local).earliest())
+            .filter(|time| *time > now)
+            .min()
+    }
+}
+
+/// Concurrency that applies during a time window
+#[derive(Debug, Clone)]
+pub struct AutonomyWindow {
+    pub name: String,
+    pub window: TimeWindow,
+    pub max_concurrent_workers: usize,
+}
+
+impl AutonomyWindow {
+    pub fn from_config(config: &AutonomyWindowConfig) -> Result<Self, String> {
+        Ok(Self {
+            name: config.name.clone(),
+            window: TimeWindow::parse(&config.days, &config.start, &config.end)?,
+            max_concurrent_workers: config.max_concurrent_workers,
+        })
+    }
+}
+
+/// A time autonomy leaves a project alone
+#[derive(Debug, Clone)]
+pub struct ProjectBlackout {
+    pub project: String,
+    pub window: TimeWindow,
+}
+
+impl ProjectBlackout {
+    pub fn from_config(config: &ProjectBlackoutConfig) -> Result<Self, String> {
+        Ok(Self {
+            project: config.project.clone(),
+            window: TimeWindow::parse(&config.days, &config.start, &config.end)?,
+        })
+    }
+}
+
+/// System to log when autonomy moves into a different schedule window
+pub fn announce_autonomy_schedule(
+    autonomy: Res<AutonomySettings>,
+    time: Res<Time>,
+    mut last_check: Local<f32>,
+    mut last_window: Local<Option<Option<String>>>,
+) {
+    *last_check += time.delta_seconds();
+
+    // Check every 30 seconds
+    if *last_check < 30.0 && last_window.is_some() {
+        return;
+    }
+    *last_check = 0.0;
+
+    let (max_workers, window) = autonomy.concurrency_at(chrono::Local::now());
+    let window = window.map(str::to_string);
+    if last_window.as_ref() == Some(&window) {
+        return;
+    }
+
+    // Stay quiet at startup unless autonomy is on
+    if last_window.is_some() || autonomy.enabled {
+        match (&window, max_workers) {
+            (Some(name), 0) => println!("🌙 Autonomy window '{name}' - quiet period, no new missions"),
+            (Some(name), n) => println!("🕒 Autonomy window '{name}' - up to {n} concurrent workers"),
+            (None, 0) => println!("🌙 Outside every autonomy window - no new missions"),
+            (None, n) => println!("🕒 Autonomy back to its default - up to {n} concurrent workers"),
+        }
+    }
+
+    *last_window = Some(window);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// A local time in the week of Friday 2026-06-19, clear of daylight saving changes
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<chrono::Local> {
+        chrono::Local.with_ymd_and_hms(2026, 6, day, hour, minute, 0).unwrap()
+    }
+
+    const THU: u32 = 18;
+    const FRI: u32 = 19;
+    const SAT: u32 = 20;
+    const SUN: u32 = 21;
+    const MON: u32 = 22;
+
+    fn window(days: &[&str], start: &str, end: &str) -> TimeWindow {
+        let days: Vec<String> = days.iter().map(|d| d.to_string()).collect();
+        TimeWindow::parse(&days, start, end).unwrap()
+    }
+
+    #[test]
+    fn daytime_window_on_weekdays() {
+        let office = window(&["mon", "tue", "wed", "thu", "fri"], "09:00", "18:00");
+
+        assert!(office.contains(at(FRI, 9, 0)));
+        assert!(office.contains(at(FRI, 17, 59)));
+        assert!(!office.contains(at(FRI, 18, 0)));
+        assert!(!office.contains(at(FRI, 8, 59)));
+        assert!(!office.contains(at(SAT, 10, 0)));
+    }
+
+    #[test]
+    fn overnight_window_belongs_to_its_start_day() {
+        let friday_night = window(&["fri"], "22:00", "06:00");
+
+        assert!(friday_night.contains(at(FRI, 23, 0)));
+        assert!(friday_night.contains(at(SAT, 5, 59)));
+        assert!(!friday_night.contains(at(SAT, 6, 0)));
+        assert!(!friday_night.contains(at(SAT, 23, 0)));
+        assert!(!friday_night.contains(at(THU, 23, 0)));
+        // Friday morning is Thursday's night
+        assert!(!friday_night.contains(at(FRI, 5, 0)));
+    }
+
+    #[test]
+    fn same_start_and_end_is_the_whole_day() {
+        let weekend = window(&["sat", "sun"], "00:00", "00:00");
+
+        assert!(weekend.contains(at(SAT, 0, 0)));
+        assert!(weekend.contains(at(SUN, 23, 59)));
+        assert!(!weekend.contains(at(FRI, 23, 59)));
+        assert!(!weekend.contains(at(MON, 0, 0)));
+
+        assert_eq!(window(&["sat", "sun"], "00:00", "24:00"), weekend);
+    }
+
+    #[test]
+    fn next_boundary_is_the_next_open_or_close() {
+        let daily = window(&[], "09:00", "18:00");
+        assert_eq!(daily.next_boundary(at(FRI, 10, 0)), Some(at(FRI, 18, 0)));
+        assert_eq!(daily.next_boundary(at(FRI, 19, 0)), Some(at(SAT, 9, 0)));
+
+        let overnight = window(&[], "22:00", "06:00");
+        assert_eq!(overnight.next_boundary(at(FRI, 23, 0)), Some(at(SAT, 6, 0)));
+        assert_eq!(overnight.next_boundary(at(SAT, 6, 0)), Some(at(SAT, 22, 0)));
+    }
+}
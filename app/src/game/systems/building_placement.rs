@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::camera::MainCamera;
+use crate::game::components::UtilityBuilding;
+use crate::game::project::Project;
+use crate::game::resources::{BuildingManager, ProjectManager};
+
+/// Side length of one building plot (world units)
+pub const PLOT_SIZE: f32 = 10.0;
+
+/// How many rings of plots around the Town Hall are searched for a free one
+const MAX_PLOT_RINGS: i32 = 12;
+
+/// Leisure zone footprint kept clear of buildings (see `spawn_leisure_zone`)
+const LEISURE_CENTER: Vec3 = Vec3::new(-20.0, 0.0, -20.0);
+const LEISURE_RADIUS: f32 = 8.0;
+
+/// How close the cursor must be to a building to pick it up
+pub const PICK_RADIUS: f32 = 3.0;
+
+/// Grid coordinate of the plot containing `position`
+pub fn plot_of(position: Vec3) -> IVec2 {
+    IVec2::new(
+        (position.x / PLOT_SIZE).round() as i32,
+        (position.z / PLOT_SIZE).round() as i32,
+    )
+}
+
+/// World position of the center of `plot`
+pub fn plot_center(plot: IVec2) -> Vec3 {
+    Vec3::new(plot.x as f32 * PLOT_SIZE, 0.0, plot.y as f32 * PLOT_SIZE)
+}
+
+/// Plots taken by the Town Hall or the leisure zone
+fn is_reserved(plot: IVec2) -> bool {
+    if plot == IVec2::ZERO {
+        return true;
+    }
+
+    plot_center(plot).distance(LEISURE_CENTER) < LEISURE_RADIUS + PLOT_SIZE * 0.5
+}
+
+/// Whether `plot` can take a building, given the positions of existing ones
+pub fn is_plot_free(plot: IVec2, occupied: &[Vec3]) -> bool {
+    !is_reserved(plot) && !occupied.iter().any(|pos| plot_of(*pos) == plot)
+}
+
+/// Find the free plot nearest the Town Hall
+pub fn find_free_plot(occupied: &[Vec3]) -> Option<Vec3> {
+    for ring in 1..=MAX_PLOT_RINGS {
+        let mut candidates: Vec<IVec2> = (-ring..=ring)
+            .flat_map(|x| (-ring..=ring).map(move |z| IVec2::new(x, z)))
+            .filter(|p| p.x.abs() == ring || p.y.abs() == ring)
+            .collect();
+
+        // Nearest first, so each ring fills in evenly around the Town Hall
+        candidates.sort_by_key(|p| p.length_squared());
+
+        if let Some(plot) = candidates.into_iter().find(|p| is_plot_free(*p, occupied)) {
+            return Some(plot_center(plot));
+        }
+    }
+
+    None
+}
+
+/// Positions of everything standing on a plot: projects and utility buildings
+pub type PlotOccupantQuery<'w, 's> = Query<'w, 's, &'static Transform, Or<(With<Project>, With<UtilityBuilding>)>>;
+
+/// Buildings that sit on a plot and can be dragged: projects and utility buildings
+type PlacedBuildingQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, Option<&'static Project>, Option<&'static UtilityBuilding>, &'static mut Transform),
+    Or<(With<Project>, With<UtilityBuilding>)>,
+>;
+
+/// System to let the user drag project and utility buildings to a new plot
+pub fn drag_project_buildings(
+    mouse: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut building_query: PlacedBuildingQuery,
+    project_manager: Res<ProjectManager>,
+    building_manager: Res<BuildingManager>,
+    mut dragging: Local<Option<(Entity, Vec3)>>,
+) {
+    let cursor_ground = cursor_ground_position(&window_query, &camera_query);
+
+    if mouse.just_pressed(MouseButton::Left) {
+        let Some(cursor_ground) = cursor_ground else { return };
+
+        *dragging = pick_building(
+            cursor_ground,
+            building_query.iter().map(|(entity, _, _, transform)| (entity, transform.translation)),
+        );
+        return;
+    }
+
+    let Some((entity, origin)) = *dragging else { return };
+
+    if mouse.pressed(MouseButton::Left) {
+        // Float the building over the plot under the cursor
+        if let (Some(cursor_ground), Ok((_, _, _, mut transform))) = (cursor_ground, building_query.get_mut(entity)) {
+            transform.translation = plot_center(plot_of(cursor_ground)) + Vec3::Y * 0.5;
+        }
+        return;
+    }
+
+    // Released: drop onto the plot if it is free, otherwise snap back
+    *dragging = None;
+
+    let occupied: Vec<Vec3> = building_query.iter()
+        .filter(|(e, _, _, _)| *e != entity)
+        .map(|(_, _, _, t)| t.translation)
+        .collect();
+
+    let Ok((_, project, utility, mut transform)) = building_query.get_mut(entity) else { return };
+    let name = match (project, utility) {
+        (Some(project), _) => project.name.clone(),
+        (_, Some(utility)) => utility.building_type.display_name().to_string(),
+        _ => return,
+    };
+
+    let Some(target_plot) = cursor_ground.map(plot_of) else {
+        transform.translation = origin;
+        return;
+    };
+
+    if plot_of(origin) == target_plot {
+        transform.translation = origin;
+        return;
+    }
+
+    if !is_plot_free(target_plot, &occupied) {
+        transform.translation = origin;
+        println!("⚠️ Plot {target_plot} is taken - '{name}' stays put");
+        return;
+    }
+
+    let new_position = plot_center(target_plot);
+    transform.translation = new_position;
+
+    let saved = match (project, utility) {
+        (Some(project), _) => project_manager.save_position(&project.id, new_position),
+        (_, Some(utility)) => building_manager.save_position(&utility.id, new_position),
+        _ => return,
+    };
+
+    match saved {
+        Ok(()) => println!("🏗️ Moved '{name}' to plot {target_plot}"),
+        Err(e) => eprintln!("Failed to save position for '{name}': {e}"),
+    }
+}
+
+/// Nearest building within pick range of `cursor_ground`
+pub fn pick_building(
+    cursor_ground: Vec3,
+    buildings: impl Iterator<Item = (Entity, Vec3)>,
+) -> Option<(Entity, Vec3)> {
+    buildings
+        .filter(|(_, pos)| pos.distance(cursor_ground) < PICK_RADIUS)
+        .min_by(|a, b| a.1.distance(cursor_ground).total_cmp(&b.1.distance(cursor_ground)))
+}
+
+/// Where the cursor ray hits the ground plane
+pub fn cursor_ground_position(
+    window_query: &Query<&Window, With<PrimaryWindow>>,
+    camera_query: &Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) -> Option<Vec3> {
+    let window = window_query.get_single().ok()?;
+    let cursor = window.cursor_position()?;
+    let (camera, camera_transform) = camera_query.get_single().ok()?;
+
+    let ray = camera.viewport_to_world(camera_transform, cursor)?;
+    let distance = ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y))?;
+
+    Some(ray.get_point(distance))
+}
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use crate::game::components::StagedBuilding;
 
-/// System to update building visuals when stage changes
+/// System to update building visuals when stage or condition changes
 pub fn update_building_visuals(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -17,24 +17,36 @@ pub fn update_building_visuals(
             }
         }
 
-        // Generate new mesh for current stage
-        let mesh = staged_building.current_stage.generate_mesh();
-        let color = staged_building.current_stage.get_color();
+        spawn_building_meshes(&mut commands, &mut meshes, &mut materials, entity, staged_building);
+    }
+}
+
+/// Spawn the themed stage geometry and condition overlays as children of `entity`
+pub fn spawn_building_meshes(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    entity: Entity,
+    building: &StagedBuilding,
+) {
+    let model = building.model();
+    let overlays = building.condition.overlay_parts(model.size);
 
-        // Spawn new mesh as child
-        let mesh_entity = commands
+    for (mesh, color, transform) in model.parts.into_iter().chain(overlays) {
+        let part_entity = commands
             .spawn(PbrBundle {
                 mesh: meshes.add(mesh),
                 material: materials.add(StandardMaterial {
                     base_color: color,
+                    alpha_mode: if color.alpha() < 1.0 { AlphaMode::Blend } else { AlphaMode::Opaque },
                     ..default()
                 }),
-                transform: Transform::from_xyz(0.0, 0.0, 0.0),
+                transform,
                 ..default()
             })
             .id();
 
-        commands.entity(entity).add_child(mesh_entity);
+        commands.entity(entity).add_child(part_entity);
     }
 }
 
@@ -43,32 +55,25 @@ pub fn spawn_initial_town_hall(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    town_hall: Res<crate::game::entities::town_hall::TownHall>,
+    worker_manager: Res<crate::game::resources::WorkerManager>,
 ) {
     use crate::game::entities::town_hall::TownHall as TownHallData;
     use crate::game::systems::TownHall as TownHallProduction;
 
-    // Spawn at stage 4 (complete basic structure) for M2 demo
-    let town_hall_entity = TownHallData::spawn(&mut commands, 4);
+    // Stage follows the saved Town Hall level
+    let stage = town_hall.stage();
+    let town_hall_entity = TownHallData::spawn(&mut commands, stage);
 
     // Add production component for M6
-    commands.entity(town_hall_entity).insert(TownHallProduction::new());
+    // Resume the production queue saved before shutdown
+    let queue = worker_manager.load_production_queue().unwrap_or_else(|e| {
+        eprintln!("Failed to load production queue: {e}");
+        Vec::new()
+    });
+    commands.entity(town_hall_entity).insert(TownHallProduction::with_queue(queue));
 
     // Add initial visual
-    let stage = crate::game::entities::building_stage::BuildingStage::from_u8(4);
-    let mesh = stage.generate_mesh();
-    let color = stage.get_color();
-
-    let mesh_entity = commands
-        .spawn(PbrBundle {
-            mesh: meshes.add(mesh),
-            material: materials.add(StandardMaterial {
-                base_color: color,
-                ..default()
-            }),
-            transform: Transform::from_xyz(0.0, 0.0, 0.0),
-            ..default()
-        })
-        .id();
-
-    commands.entity(town_hall_entity).add_child(mesh_entity);
+    let building = StagedBuilding::new(crate::game::components::BuildingType::TownHall, stage);
+    spawn_building_meshes(&mut commands, &mut meshes, &mut materials, town_hall_entity, &building);
 }
@@ -0,0 +1,256 @@
+use bevy::prelude::*;
+use std::fmt;
+use crate::game::project::Mission;
+use crate::game::systems::MissionManager;
+
+/// Token estimate for an average-length mission with no similar history
+const DEFAULT_MISSION_TOKENS: f32 = 5_000.0;
+
+/// Duration estimate (seconds) for an average-length mission with no similar history
+const DEFAULT_MISSION_SECS: f32 = 300.0;
+
+/// How much the defaults count against real samples
+const PRIOR_WEIGHT: f32 = 1.0;
+
+/// Spread (in log space) assumed before any history: ~80% of missions within 4x either way
+const PRIOR_SPREAD: f32 = 1.1;
+
+/// z-score of the reported interval (80%)
+const INTERVAL_Z: f32 = 1.28;
+
+/// A finished mission run the estimator learns from
+#[derive(Debug, Clone)]
+pub struct EstimationSample {
+    pub mission_id: String,
+    pub project_id: String,
+    pub domains: Vec<String>,
+    pub description_words: usize,
+    pub success: bool,
+    pub tokens: u32,
+    /// Missing for missions completed before attempts were recorded
+    pub duration_secs: Option<u64>,
+}
+
+/// A prediction with its 80% interval
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    pub expected: f32,
+    pub low: f32,
+    pub high: f32,
+}
+
+/// Predicted tokens and wall time of a mission
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MissionEstimate {
+    pub tokens: Estimate,
+    pub secs: Estimate,
+    /// Past runs that informed the estimate, weighted by similarity
+    pub samples: usize,
+}
+
+impl MissionEstimate {
+    /// Tokens a launch must fit: the top of the range, so a typical overrun still fits
+    pub fn gate_tokens(&self) -> u64 {
+        self.tokens.high as u64
+    }
+}
+
+impl fmt::Display for MissionEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let k = |tokens: f32| format!("{:.1}k", tokens / 1000.0);
+        let min = |secs: f32| (secs / 60.0).round().max(1.0);
+
+        write!(f, "~{} tokens ({}-{}), ~{} min ({}-{}), ",
+               k(self.tokens.expected), k(self.tokens.low), k(self.tokens.high),
+               min(self.secs.expected), min(self.secs.low), min(self.secs.high))?;
+
+        match self.samples {
+            0 => write!(f, "no similar runs yet"),
+            1 => write!(f, "from 1 similar run"),
+            n => write!(f, "from {n} similar runs"),
+        }
+    }
+}
+
+/// Resource predicting mission cost from past runs; learns from every finished mission
+#[derive(Resource, Default)]
+pub struct MissionEstimator {
+    samples: Vec<EstimationSample>,
+}
+
+impl MissionEstimator {
+    /// Estimator seeded with every recorded run
+    pub fn load(mission_manager: &MissionManager) -> Self {
+        let samples = mission_manager.load_estimation_samples().unwrap_or_else(|e| {
+            eprintln!("⚠️  Failed to load mission history for estimates: {e}");
+            Vec::new()
+        });
+
+        Self { samples }
+    }
+
+    pub fn record(&mut self, sample: EstimationSample) {
+        self.samples.push(sample);
+    }
+
+    /// Predict a mission from similar past runs, falling back to defaults scaled by its description
+    pub fn estimate(&self, mission: &Mission) -> MissionEstimate {
+        let words = mission.description.split_whitespace().count();
+        let size = size_factor(words);
+
+        let mut tokens = LogAccumulator::new((DEFAULT_MISSION_TOKENS * size).ln());
+        let mut secs = LogAccumulator::new((DEFAULT_MISSION_SECS * size).ln());
+        let mut samples = 0;
+
+        for sample in &self.samples {
+            let weight = similarity(mission, sample);
+            if weight <= 0.0 || sample.tokens == 0 {
+                continue;
+            }
+            samples += 1;
+
+            // Rescale to this mission's description length before comparing
+            let scale = (size / size_factor(sample.description_words)).ln() * 0.5;
+
+            tokens.add((sample.tokens as f32).ln() + scale, weight);
+            if let Some(duration) = sample.duration_secs.filter(|d| *d > 0) {
+                secs.add((duration as f32).ln() + scale, weight);
+            }
+        }
+
+        MissionEstimate {
+            tokens: tokens.estimate(),
+            secs: secs.estimate(),
+            samples,
+        }
+    }
+}
+
+/// Longer descriptions usually mean bigger missions
+fn size_factor(words: usize) -> f32 {
+    0.5 + (words as f32 / 200.0).min(2.0)
+}
+
+/// How much a past run says about `mission`; 0 when it says nothing
+fn similarity(mission: &Mission, sample: &EstimationSample) -> f32 {
+    // Earlier runs of this very mission, failed ones included, say the most
+    if sample.mission_id == mission.id {
+        return 3.0;
+    }
+    if !sample.success {
+        return 0.0;
+    }
+
+    let project = if sample.project_id == mission.project_id { 2.0 } else { 1.0 };
+
+    let shared = mission.domains.iter().filter(|d| sample.domains.contains(d)).count() as f32;
+    let union = (mission.domains.len() + sample.domains.len()) as f32 - shared;
+    let overlap = if union > 0.0 { shared / union } else { 1.0 };
+
+    project * (0.25 + overlap)
+}
+
+/// Weighted mean and spread of log values, starting from a prior
+struct LogAccumulator {
+    prior: f32,
+    values: Vec<(f32, f32)>,
+}
+
+impl LogAccumulator {
+    fn new(prior: f32) -> Self {
+        Self { prior, values: Vec::new() }
+    }
+
+    fn add(&mut self, value: f32, weight: f32) {
+        self.values.push((value, weight));
+    }
+
+    fn estimate(&self) -> Estimate {
+        let total_weight: f32 = PRIOR_WEIGHT + self.values.iter().map(|(_, w)| w).sum::<f32>();
+        let mean = (self.prior * PRIOR_WEIGHT
+            + self.values.iter().map(|(v, w)| v * w).sum::<f32>()) / total_weight;
+
+        // The prior's spread keeps a handful of samples from claiming certainty
+        let variance = (PRIOR_SPREAD.powi(2) * PRIOR_WEIGHT
+            + self.values.iter().map(|(v, w)| w * (v - mean).powi(2)).sum::<f32>()) / total_weight;
+
+        // Effective sample size, so a few heavy samples don't count as many
+        let squared_weights: f32 = PRIOR_WEIGHT.powi(2) + self.values.iter().map(|(_, w)| w * w).sum::<f32>();
+        let effective_n = total_weight.powi(2) / squared_weights;
+
+        let half_width = INTERVAL_Z * (variance * (1.0 + 1.0 / effective_n)).sqrt();
+
+        Estimate {
+            expected: mean.exp(),
+            low: (mean - half_width).exp(),
+            high: (mean + half_width).exp(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mission(id: &str, project_id: &str) -> Mission {
+        let mut mission = Mission::new(project_id.to_string(), 1, "Test mission".to_string());
+        mission.id = id.to_string();
+        mission
+    }
+
+    fn sample(mission_id: &str, project_id: &str, success: bool, tokens: u32) -> EstimationSample {
+        EstimationSample {
+            mission_id: mission_id.to_string(),
+            project_id: project_id.to_string(),
+            domains: Vec::new(),
+            description_words: 0,
+            success,
+            tokens,
+            duration_secs: Some(600),
+        }
+    }
+
+    #[test]
+    fn no_history_falls_back_to_the_prior() {
+        let estimate = MissionEstimator::default().estimate(&mission("m1", "p1"));
+
+        // An empty description is half an average mission
+        assert!((estimate.tokens.expected - DEFAULT_MISSION_TOKENS * 0.5).abs() < 1.0);
+        assert!((estimate.secs.expected - DEFAULT_MISSION_SECS * 0.5).abs() < 1.0);
+        assert!(estimate.tokens.low < estimate.tokens.expected && estimate.tokens.expected < estimate.tokens.high);
+        assert_eq!(estimate.samples, 0);
+    }
+
+    #[test]
+    fn same_mission_runs_outweigh_other_missions() {
+        let mut estimator = MissionEstimator::default();
+        estimator.record(sample("m1", "p1", false, 20_000));
+        estimator.record(sample("m2", "p2", true, 1_000));
+        // Failed runs of other missions say nothing
+        estimator.record(sample("m3", "p2", false, 1_000_000));
+
+        let estimate = estimator.estimate(&mission("m1", "p1"));
+
+        assert_eq!(estimate.samples, 2);
+        // Closer to the earlier run of this mission than to the other one
+        let midpoint = (20_000.0f32 * 1_000.0).sqrt();
+        assert!(estimate.tokens.expected > midpoint, "{estimate}");
+    }
+
+    #[test]
+    fn interval_narrows_as_samples_are_added() {
+        let prior = 5_000f32.ln();
+        let mut accumulator = LogAccumulator::new(prior);
+        let mut last_ratio = accumulator.estimate().high / accumulator.estimate().low;
+
+        for _ in 0..10 {
+            accumulator.add(prior, 1.0);
+            let estimate = accumulator.estimate();
+            let ratio = estimate.high / estimate.low;
+
+            assert!(ratio < last_ratio, "{ratio} should be below {last_ratio}");
+            assert!((estimate.expected - 5_000.0).abs() < 1.0);
+            last_ratio = ratio;
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use bevy::prelude::*;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use crate::core::config::AppConfig;
+use crate::game::components::StagedBuilding;
+use crate::game::entities::building_condition::BuildingCondition;
+use crate::game::health::{check_project, CheckStatus, HealthReport};
+use crate::game::project::Project;
+use crate::game::project::conquest::ProjectConquest;
+use crate::game::resources::{CliManagerResource, ProjectManager, TownFacilities};
+
+/// Latest health check of a project building
+#[derive(Component, Debug, Default)]
+pub struct ProjectHealth(pub Option<HealthReport>);
+
+impl ProjectHealth {
+    /// Condition to show when no worker is on the project
+    pub fn condition(&self) -> BuildingCondition {
+        match &self.0 {
+            Some(report) if report.build == CheckStatus::Failing => BuildingCondition::Damaged,
+            Some(report) if report.tests == CheckStatus::Passing => BuildingCondition::Flagged,
+            _ => BuildingCondition::Normal,
+        }
+    }
+}
+
+/// Resource tracking health probes running on background threads
+#[derive(Resource, Default)]
+pub struct HealthMonitor {
+    /// Reports finished by probe threads, drained each frame
+    pub finished: Arc<Mutex<Vec<HealthReport>>>,
+    /// Projects with a probe currently running
+    pub in_flight: HashSet<String>,
+}
+
+/// System to attach the last stored health check to new project buildings
+pub fn restore_project_health(
+    mut commands: Commands,
+    project_query: Query<(Entity, &Project), Without<ProjectHealth>>,
+    project_manager: Res<ProjectManager>,
+) {
+    for (entity, project) in project_query.iter() {
+        let latest = project_manager.load_latest_health(&project.id).unwrap_or_else(|e| {
+            eprintln!("Failed to load health for '{}': {e}", project.name);
+            None
+        });
+        commands.entity(entity).insert(ProjectHealth(latest));
+    }
+}
+
+/// System to start a health probe for every project whose last check is older than the interval.
+///
+/// Probes run in the Workshop: none without one, more at once as it grows.
+pub fn schedule_health_checks(
+    project_query: Query<(&Project, &ProjectHealth)>,
+    mut monitor: ResMut<HealthMonitor>,
+    cli_manager: Res<CliManagerResource>,
+    facilities: Res<TownFacilities>,
+    config: Res<AppConfig>,
+    time: Res<Time>,
+    mut last_scan: Local<f32>,
+) {
+    if !config.health.enabled {
+        return;
+    }
+
+    *last_scan += time.delta_seconds();
+
+    // Look for due projects every 10 seconds
+    if *last_scan < 10.0 {
+        return;
+    }
+    *last_scan = 0.0;
+
+    let busy_paths = active_project_paths(&cli_manager);
+
+    for (project, health) in project_query.iter() {
+        if monitor.in_flight.len() >= facilities.health_check_slots() {
+            break;
+        }
+
+        if monitor.in_flight.contains(&project.id) || !is_due(health, config.health.interval_secs) {
+            continue;
+        }
+
+        // Don't fight a working agent for the build directory
+        if busy_paths.contains(&project.path) {
+            continue;
+        }
+
+        let project_path = PathBuf::from(&project.path);
+        if !project_path.is_dir() {
+            continue;
+        }
+
+        monitor.in_flight.insert(project.id.clone());
+
+        let project_id = project.id.clone();
+        let health_config = config.health.clone();
+        let finished = monitor.finished.clone();
+
+        std::thread::spawn(move || {
+            let (_, build_system) = ProjectConquest::detect_build_system(Path::new(&project_path));
+            let report = check_project(&project_id, &project_path, &build_system, &health_config);
+            finished.lock().unwrap().push(report);
+        });
+    }
+}
+
+fn is_due(health: &ProjectHealth, interval_secs: u64) -> bool {
+    let Some(report) = &health.0 else { return true };
+
+    match chrono::NaiveDateTime::parse_from_str(&report.checked_at, "%Y-%m-%d %H:%M:%S") {
+        Ok(checked_at) => {
+            let age = chrono::Utc::now().naive_utc() - checked_at;
+            age.num_seconds() >= interval_secs as i64
+        }
+        Err(_) => true,
+    }
+}
+
+/// System to store finished health probes and attach them to their buildings
+pub fn apply_health_reports(
+    mut project_query: Query<(&Project, &mut ProjectHealth)>,
+    mut monitor: ResMut<HealthMonitor>,
+    project_manager: Res<ProjectManager>,
+) {
+    let reports = std::mem::take(&mut *monitor.finished.lock().unwrap());
+
+    for report in reports {
+        monitor.in_flight.remove(&report.project_id);
+
+        if let Err(e) = project_manager.record_health(&report) {
+            eprintln!("Failed to record health check: {e}");
+        }
+
+        if let Some((project, mut health)) = project_query.iter_mut().find(|(p, _)| p.id == report.project_id) {
+            println!("🩺 '{}': {}", project.name, report.summary_line());
+            health.0 = Some(report);
+        }
+    }
+}
+
+/// System to show scaffolding, damage or a flag on each project building
+pub fn update_building_condition(
+    mut project_query: Query<(&Project, &ProjectHealth, &mut StagedBuilding)>,
+    cli_manager: Res<CliManagerResource>,
+) {
+    let busy_paths = active_project_paths(&cli_manager);
+
+    for (project, health, mut building) in project_query.iter_mut() {
+        let condition = if busy_paths.contains(&project.path) {
+            BuildingCondition::Scaffolding
+        } else {
+            health.condition()
+        };
+
+        // Only touch the component on a real change so visuals aren't rebuilt every frame
+        if building.condition != condition {
+            building.condition = condition;
+        }
+    }
+}
+
+/// Paths of projects with a Claude CLI process running
+fn active_project_paths(cli_manager: &CliManagerResource) -> HashSet<String> {
+    let cli_lock = cli_manager.manager.lock().unwrap();
+    cli_lock.active_processes.iter().map(|p| p.project_path.clone()).collect()
+}
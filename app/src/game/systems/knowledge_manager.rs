@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+use rusqlite::Connection;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Resource for the knowledge base kept in the Library
+#[derive(Resource)]
+pub struct KnowledgeManager {
+    pub db_path: PathBuf,
+}
+
+/// A learning recorded from a finished mission
+#[derive(Debug, Clone)]
+pub struct KnowledgeEntry {
+    pub project_name: String,
+    pub mission_id: String,
+    pub task_description: String,
+    pub solution_applied: String,
+    /// What to do differently next time, from the worker's reflection
+    pub reasoning: String,
+    pub tokens_used: u32,
+    pub duration_secs: u64,
+    pub worker_id: String,
+    pub success: bool,
+}
+
+impl KnowledgeManager {
+    pub fn new(db_path: PathBuf) -> Self {
+        Self { db_path }
+    }
+
+    pub fn record_entry(&self, entry: &KnowledgeEntry) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let keywords: Vec<String> = keywords(&format!("{} {}", entry.project_name, entry.task_description))
+            .into_iter()
+            .collect();
+
+        conn.execute(
+            "INSERT INTO knowledge_entries (id, project_name, mission_id, task_description,
+                                            solution_applied, reasoning, tokens_used, duration_seconds,
+                                            worker_id, success, search_keywords)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            rusqlite::params![
+                uuid::Uuid::new_v4().to_string(),
+                entry.project_name,
+                entry.mission_id,
+                entry.task_description,
+                entry.solution_applied,
+                entry.reasoning,
+                entry.tokens_used,
+                entry.duration_secs as i64,
+                entry.worker_id,
+                entry.success,
+                keywords.join(" "),
+            ],
+        ).map_err(|e| format!("Insert error: {e}"))?;
+
+        Ok(())
+    }
+
+    pub fn count_entries(&self) -> Result<usize, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM knowledge_entries", [], |row| row.get(0))
+            .map_err(|e| format!("Query error: {e}"))?;
+
+        Ok(count as usize)
+    }
+
+    /// Entries sharing the most keywords with `text`, best first
+    pub fn find_relevant(&self, text: &str, limit: usize) -> Result<Vec<KnowledgeEntry>, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let wanted = keywords(text);
+
+        let mut stmt = conn.prepare(
+            "SELECT project_name, mission_id, task_description, solution_applied, tokens_used,
+                    duration_seconds, worker_id, success, search_keywords, reasoning
+             FROM knowledge_entries
+             ORDER BY timestamp DESC"
+        ).map_err(|e| format!("Query error: {e}"))?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                KnowledgeEntry {
+                    project_name: row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+                    mission_id: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                    task_description: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                    solution_applied: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                    reasoning: row.get::<_, Option<String>>(9)?.unwrap_or_default(),
+                    tokens_used: row.get::<_, Option<u32>>(4)?.unwrap_or(0),
+                    duration_secs: row.get::<_, Option<i64>>(5)?.unwrap_or(0) as u64,
+                    worker_id: row.get::<_, Option<String>>(6)?.unwrap_or_default(),
+                    success: row.get::<_, Option<bool>>(7)?.unwrap_or(false),
+                },
+                row.get::<_, Option<String>>(8)?.unwrap_or_default(),
+            ))
+        }).map_err(|e| format!("Map error: {e}"))?;
+
+        let mut scored = Vec::new();
+        for row in rows {
+            let (entry, entry_keywords) = row.map_err(|e| format!("Row error: {e}"))?;
+            let score = entry_keywords.split_whitespace().filter(|k| wanted.contains(*k)).count();
+            if score > 0 {
+                scored.push((score, entry));
+            }
+        }
+
+        // Stable sort keeps newer entries first among equal scores
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+        Ok(scored.into_iter().take(limit).map(|(_, entry)| entry).collect())
+    }
+}
+
+/// Lowercase words worth matching on
+fn keywords(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() > 3)
+        .map(|w| w.to_lowercase())
+        .collect()
+}
@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 use rusqlite::Connection;
 use std::path::PathBuf;
-use crate::game::project::{Mission, MissionStatus};
+use crate::game::project::{Mission, MissionAttempt, MissionStatus};
+use crate::game::git::GitMissionResult;
+use crate::game::cli::usage::UsageBreakdown;
+use crate::game::systems::estimation::EstimationSample;
 
 /// Resource for managing missions
 #[derive(Resource)]
@@ -14,17 +17,18 @@ impl MissionManager {
         Self { db_path }
     }
 
-    #[allow(dead_code)]
     pub fn create_mission(&self, mission: &Mission) -> Result<(), String> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| format!("Database error: {e}"))?;
 
         let deps_json = serde_json::to_string(&mission.dependencies)
             .map_err(|e| format!("JSON error: {e}"))?;
+        let domains_json = serde_json::to_string(&mission.domains_or_inferred())
+            .map_err(|e| format!("JSON error: {e}"))?;
 
         conn.execute(
-            "INSERT INTO missions (id, project_id, mission_number, title, description, status, dependencies, file_path)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            "INSERT INTO missions (id, project_id, mission_number, title, description, status, dependencies, file_path, domains)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
             [
                 &mission.id,
                 &mission.project_id,
@@ -34,6 +38,7 @@ impl MissionManager {
                 mission.status.as_str(),
                 &deps_json,
                 &mission.file_path.clone().unwrap_or_default(),
+                &domains_json,
             ],
         ).map_err(|e| format!("Insert error: {e}"))?;
 
@@ -41,20 +46,31 @@ impl MissionManager {
     }
 
     pub fn load_missions(&self, project_id: &str) -> Result<Vec<Mission>, String> {
+        self.query_missions("WHERE project_id = ?1 ORDER BY mission_number ASC", project_id)
+    }
+
+    pub fn load_mission(&self, mission_id: &str) -> Result<Option<Mission>, String> {
+        Ok(self.query_missions("WHERE id = ?1", mission_id)?.into_iter().next())
+    }
+
+    fn query_missions(&self, filter: &str, param: &str) -> Result<Vec<Mission>, String> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| format!("Database error: {e}"))?;
 
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare(&format!(
             "SELECT id, project_id, mission_number, title, description, status,
-                    dependencies, file_path, assigned_worker_id, tokens_used, completion_summary
-             FROM missions WHERE project_id = ?1 ORDER BY mission_number ASC"
-        ).map_err(|e| format!("Query error: {e}"))?;
+                    dependencies, file_path, assigned_worker_id, tokens_used, completion_summary, domains
+             FROM missions {filter}"
+        )).map_err(|e| format!("Query error: {e}"))?;
 
-        let missions = stmt.query_map([project_id], |row| {
+        let missions = stmt.query_map([param], |row| {
             let deps_str: String = row.get(6)?;
             let dependencies: Vec<u32> = serde_json::from_str(&deps_str).unwrap_or_default();
+            let domains: Vec<String> = row.get::<_, Option<String>>(11)?
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or_default();
 
-            Ok(Mission {
+            let mut mission = Mission {
                 id: row.get(0)?,
                 project_id: row.get(1)?,
                 mission_number: row.get(2)?,
@@ -66,7 +82,12 @@ impl MissionManager {
                 assigned_worker_id: row.get(8)?,
                 tokens_used: row.get::<_, i32>(9)? as u32,
                 completion_summary: row.get(10)?,
-            })
+                domains,
+            };
+
+            // Missions created before tagging get their domains inferred
+            mission.domains = mission.domains_or_inferred();
+            Ok(mission)
         }).map_err(|e| format!("Map error: {e}"))?;
 
         let mut result = Vec::new();
@@ -77,20 +98,250 @@ impl MissionManager {
         Ok(result)
     }
 
-    pub fn update_mission_status(&self, mission_id: &str, status: MissionStatus, summary: Option<String>, tokens: u32) -> Result<(), String> {
+    pub fn update_mission_status(&self, mission_id: &str, status: MissionStatus, summary: Option<String>, tokens: u32, cost_usd: f64) -> Result<(), String> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| format!("Database error: {e}"))?;
 
         conn.execute(
             "UPDATE missions
-             SET status = ?1, completion_summary = ?2, tokens_used = ?3, completed_at = CURRENT_TIMESTAMP
-             WHERE id = ?4",
-            [status.as_str(), &summary.unwrap_or_default(), &tokens.to_string(), mission_id],
+             SET status = ?1, completion_summary = ?2, tokens_used = ?3, cost_usd = ?4, completed_at = CURRENT_TIMESTAMP
+             WHERE id = ?5",
+            rusqlite::params![status.as_str(), summary.unwrap_or_default(), tokens, cost_usd, mission_id],
+        ).map_err(|e| format!("Update error: {e}"))?;
+
+        // Touch the owning project so `.zac/` reconciliation sees the change
+        conn.execute(
+            "UPDATE projects SET last_updated = CURRENT_TIMESTAMP
+             WHERE id = (SELECT project_id FROM missions WHERE id = ?1)",
+            [mission_id],
         ).map_err(|e| format!("Update error: {e}"))?;
 
         Ok(())
     }
 
+    /// Put a mission back in the queue after its run was cancelled
+    pub fn reset_mission(&self, mission_id: &str) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        conn.execute(
+            "UPDATE missions SET status = ?1, assigned_worker_id = NULL, started_at = NULL WHERE id = ?2",
+            [MissionStatus::NotStarted.as_str(), mission_id],
+        ).map_err(|e| format!("Update error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Merge a mission imported from `.zac/tasks.json`, matched on mission number.
+    ///
+    /// Completed or in-progress missions in the database keep their status, and
+    /// the larger token count wins.
+    pub fn import_mission(&self, mission: &Mission) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let deps_json = serde_json::to_string(&mission.dependencies)
+            .map_err(|e| format!("JSON error: {e}"))?;
+        let domains_json = serde_json::to_string(&mission.domains_or_inferred())
+            .map_err(|e| format!("JSON error: {e}"))?;
+
+        conn.execute(
+            "INSERT INTO missions (id, project_id, mission_number, title, description, status,
+                                   dependencies, tokens_used, completion_summary, domains)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+             ON CONFLICT(project_id, mission_number) DO UPDATE SET
+                 title = excluded.title,
+                 description = excluded.description,
+                 dependencies = excluded.dependencies,
+                 domains = excluded.domains,
+                 status = CASE WHEN missions.status IN ('completed', 'in_progress')
+                               THEN missions.status ELSE excluded.status END,
+                 tokens_used = MAX(missions.tokens_used, excluded.tokens_used),
+                 completion_summary = COALESCE(NULLIF(excluded.completion_summary, ''),
+                                               missions.completion_summary)",
+            rusqlite::params![
+                mission.id,
+                mission.project_id,
+                mission.mission_number,
+                mission.title,
+                mission.description,
+                mission.status.as_str(),
+                deps_json,
+                mission.tokens_used,
+                mission.completion_summary,
+                domains_json,
+            ],
+        ).map_err(|e| format!("Import error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Delete a project's missions whose numbers aren't in `keep`, returning how many went.
+    ///
+    /// In-progress missions stay. Their attempts are deleted; knowledge entries and
+    /// workers only lose their link to them.
+    pub fn delete_missions_except(&self, project_id: &str, keep: &[u32]) -> Result<usize, String> {
+        let doomed: Vec<String> = self.load_missions(project_id)?
+            .into_iter()
+            .filter(|m| !keep.contains(&m.mission_number) && m.status != MissionStatus::InProgress)
+            .map(|m| m.id)
+            .collect();
+
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let tx = conn.transaction()
+            .map_err(|e| format!("Transaction error: {e}"))?;
+
+        for mission_id in &doomed {
+            tx.execute("UPDATE knowledge_entries SET mission_id = NULL WHERE mission_id = ?1", [mission_id])
+                .map_err(|e| format!("Delete error: {e}"))?;
+            tx.execute("UPDATE workers SET current_task_id = NULL WHERE current_task_id = ?1", [mission_id])
+                .map_err(|e| format!("Delete error: {e}"))?;
+            tx.execute("DELETE FROM mission_attempts WHERE mission_id = ?1", [mission_id])
+                .map_err(|e| format!("Delete error: {e}"))?;
+            tx.execute("DELETE FROM missions WHERE id = ?1", [mission_id])
+                .map_err(|e| format!("Delete error: {e}"))?;
+        }
+
+        tx.commit().map_err(|e| format!("Commit error: {e}"))?;
+
+        Ok(doomed.len())
+    }
+
+    /// Record a finished mission run and its git results
+    pub fn record_attempt(&self, attempt: &MissionAttempt) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let git = &attempt.git;
+        conn.execute(
+            "INSERT INTO mission_attempts (mission_id, worker_id, started_at, success, duration_seconds,
+                                           tokens_used, head_before, head_after, commits_created,
+                                           files_changed, lines_added, lines_removed, tree_dirty,
+                                           diff_stat, auto_commit, input_tokens, output_tokens,
+                                           cache_read_tokens, cache_write_tokens, cost_usd, shared_repo)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21)",
+            rusqlite::params![
+                attempt.mission_id,
+                attempt.worker_id,
+                attempt.started_at,
+                attempt.success,
+                attempt.duration_secs as i64,
+                attempt.tokens_used,
+                git.head_before,
+                git.head_after,
+                git.commits_created,
+                git.files_changed,
+                git.lines_added,
+                git.lines_removed,
+                git.tree_dirty,
+                git.diff_stat,
+                git.auto_commit,
+                attempt.usage.input_tokens as i64,
+                attempt.usage.output_tokens as i64,
+                attempt.usage.cache_read_tokens as i64,
+                attempt.usage.cache_write_tokens as i64,
+                attempt.cost_usd,
+                git.shared_repo,
+            ],
+        ).map_err(|e| format!("Insert error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Load every recorded attempt for a project's missions, oldest first
+    pub fn load_project_attempts(&self, project_id: &str) -> Result<Vec<MissionAttempt>, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT a.mission_id, a.worker_id, a.started_at, a.success, a.duration_seconds,
+                    a.tokens_used, a.head_before, a.head_after, a.commits_created, a.files_changed,
+                    a.lines_added, a.lines_removed, a.tree_dirty, a.diff_stat, a.auto_commit,
+                    a.input_tokens, a.output_tokens, a.cache_read_tokens, a.cache_write_tokens, a.cost_usd,
+                    a.shared_repo
+             FROM mission_attempts a JOIN missions m ON m.id = a.mission_id
+             WHERE m.project_id = ?1 ORDER BY a.id ASC"
+        ).map_err(|e| format!("Query error: {e}"))?;
+
+        let attempts = stmt.query_map([project_id], |row| {
+            Ok(MissionAttempt {
+                mission_id: row.get(0)?,
+                worker_id: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                started_at: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                success: row.get::<_, Option<bool>>(3)?.unwrap_or(false),
+                duration_secs: row.get::<_, Option<i64>>(4)?.unwrap_or(0) as u64,
+                tokens_used: row.get(5)?,
+                usage: UsageBreakdown {
+                    input_tokens: row.get::<_, Option<i64>>(15)?.unwrap_or(0) as u64,
+                    output_tokens: row.get::<_, Option<i64>>(16)?.unwrap_or(0) as u64,
+                    cache_read_tokens: row.get::<_, Option<i64>>(17)?.unwrap_or(0) as u64,
+                    cache_write_tokens: row.get::<_, Option<i64>>(18)?.unwrap_or(0) as u64,
+                    reported_cost_usd: None,
+                },
+                cost_usd: row.get::<_, Option<f64>>(19)?.unwrap_or(0.0),
+                git: GitMissionResult {
+                    head_before: row.get(6)?,
+                    head_after: row.get(7)?,
+                    commits_created: row.get(8)?,
+                    files_changed: row.get(9)?,
+                    lines_added: row.get(10)?,
+                    lines_removed: row.get(11)?,
+                    tree_dirty: row.get(12)?,
+                    diff_stat: row.get::<_, Option<String>>(13)?.unwrap_or_default(),
+                    auto_commit: row.get(14)?,
+                    shared_repo: row.get::<_, Option<bool>>(20)?.unwrap_or(false),
+                },
+            })
+        }).map_err(|e| format!("Map error: {e}"))?;
+
+        let mut result = Vec::new();
+        for attempt in attempts {
+            result.push(attempt.map_err(|e| format!("Row error: {e}"))?);
+        }
+
+        Ok(result)
+    }
+
+    /// Every recorded run for the estimator, plus completed missions from before
+    /// attempts were recorded (tokens only)
+    pub fn load_estimation_samples(&self) -> Result<Vec<EstimationSample>, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT m.id, m.project_id, m.domains, m.description, a.success, a.tokens_used, a.duration_seconds
+             FROM mission_attempts a JOIN missions m ON m.id = a.mission_id
+             UNION ALL
+             SELECT id, project_id, domains, description, 1, tokens_used, NULL
+             FROM missions
+             WHERE status = 'completed' AND tokens_used > 0
+               AND id NOT IN (SELECT mission_id FROM mission_attempts)"
+        ).map_err(|e| format!("Query error: {e}"))?;
+
+        let samples = stmt.query_map([], |row| {
+            Ok(EstimationSample {
+                mission_id: row.get(0)?,
+                project_id: row.get(1)?,
+                domains: row.get::<_, Option<String>>(2)?
+                    .and_then(|s| serde_json::from_str(&s).ok())
+                    .unwrap_or_default(),
+                description_words: row.get::<_, Option<String>>(3)?.unwrap_or_default().split_whitespace().count(),
+                success: row.get::<_, Option<bool>>(4)?.unwrap_or(false),
+                tokens: row.get::<_, Option<i64>>(5)?.unwrap_or(0).max(0) as u32,
+                duration_secs: row.get::<_, Option<i64>>(6)?.map(|d| d.max(0) as u64),
+            })
+        }).map_err(|e| format!("Map error: {e}"))?;
+
+        let mut result = Vec::new();
+        for sample in samples {
+            result.push(sample.map_err(|e| format!("Row error: {e}"))?);
+        }
+
+        Ok(result)
+    }
+
     pub fn get_available_missions(&self, project_id: &str) -> Result<Vec<Mission>, String> {
         let all_missions = self.load_missions(project_id)?;
 
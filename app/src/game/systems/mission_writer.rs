@@ -1,6 +1,8 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use crate::game::git::GitMissionResult;
 use crate::game::project::Mission;
+use crate::game::systems::knowledge_manager::KnowledgeEntry;
 
 pub struct MissionWriter;
 
@@ -11,8 +13,7 @@ impl MissionWriter {
         fs::create_dir_all(&missions_dir)
             .map_err(|e| format!("Failed to create missions directory: {e}"))?;
 
-        let filename = format!("M{:02}.md", mission.mission_number);
-        let filepath = missions_dir.join(&filename);
+        let filepath = Self::mission_file_path(project_path, mission.mission_number);
 
         let content = Self::format_mission_content(mission);
 
@@ -22,6 +23,11 @@ impl MissionWriter {
         Ok(filepath.to_string_lossy().to_string())
     }
 
+    /// Path of a mission's file inside its project
+    pub fn mission_file_path(project_path: &str, mission_number: u32) -> PathBuf {
+        Path::new(project_path).join("missions").join(format!("M{mission_number:02}.md"))
+    }
+
     /// Format mission content as markdown
     fn format_mission_content(mission: &Mission) -> String {
         format!(
@@ -84,20 +90,87 @@ impl MissionWriter {
         Ok(())
     }
 
-    /// Mark mission as completed
-    #[allow(dead_code)]
-    pub fn mark_mission_completed(mission_file: &str, summary: &str) -> Result<(), String> {
+    /// Append past learnings from the Library, staying within a rough token budget
+    pub fn append_knowledge(mission_file: &str, entries: &[KnowledgeEntry], max_tokens: usize) -> Result<usize, String> {
+        // ~4 characters per token
+        let max_chars = max_tokens * 4;
+        let mut section = String::from("\n## Relevant Knowledge\nLearnings from earlier missions:\n");
+        let mut included = 0;
+
+        for entry in entries {
+            let mut item = format!(
+                "\n### {} ({}{})\n{}\n",
+                entry.task_description, entry.project_name,
+                if entry.success { "" } else { ", from a failed attempt" },
+                entry.solution_applied
+            );
+            if !entry.reasoning.is_empty() {
+                item += &format!("Next time: {}\n", entry.reasoning);
+            }
+            if section.len() + item.len() > max_chars {
+                break;
+            }
+            section += &item;
+            included += 1;
+        }
+
+        if included == 0 {
+            return Ok(0);
+        }
+
+        let content = fs::read_to_string(mission_file)
+            .map_err(|e| format!("Failed to read mission file: {e}"))?;
+
+        // Keep the section above the generated footer
+        let updated = match content.rfind("\n---\n") {
+            Some(index) => format!("{}{section}{}", &content[..index], &content[index..]),
+            None => content + &section,
+        };
+
+        fs::write(mission_file, updated)
+            .map_err(|e| format!("Failed to update mission file: {e}"))?;
+
+        Ok(included)
+    }
+
+    /// Mark mission as completed, appending what it did to the repository
+    pub fn mark_mission_completed(
+        mission_file: &str,
+        summary: &str,
+        git: &GitMissionResult,
+    ) -> Result<(), String> {
         let content = fs::read_to_string(mission_file)
             .map_err(|e| format!("Failed to read mission file: {e}"))?;
 
         let updated = content
             .replace("Completed: (not yet completed)", &format!("Completed: {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S")))
             .replace("Status\nin_progress", "Status\ncompleted")
-            + &format!("\n\n## Completion Summary\n{summary}\n");
+            + &format!("\n\n## Completion Summary\n{summary}\n")
+            + &Self::format_git_results(git);
 
         fs::write(mission_file, updated)
             .map_err(|e| format!("Failed to update mission file: {e}"))?;
 
         Ok(())
     }
+
+    /// Format the git results section of a mission file
+    fn format_git_results(git: &GitMissionResult) -> String {
+        let mut section = format!(
+            "\n## Git Results\n- HEAD before: {}\n- HEAD after: {}\n- {}\n",
+            git.head_before.as_deref().unwrap_or("(no commits)"),
+            git.head_after.as_deref().unwrap_or("(no commits)"),
+            git.summary_line(),
+        );
+
+        if let Some(commit) = &git.auto_commit {
+            section += &format!("- Auto-committed by Zac^: {commit}\n");
+        }
+
+        if !git.diff_stat.is_empty() {
+            section += &format!("\n```\n{}\n```\n", git.diff_stat);
+        }
+
+        section
+    }
 }
@@ -1,34 +1,67 @@
 pub mod autonomous_assignment;
+pub mod autonomy_schedule;
+pub mod building_placement;
 pub mod building_renderer;
+pub mod estimation;
+pub mod health_monitor;
+pub mod knowledge_manager;
 pub mod leisure_zone;
 pub mod mission_manager;
 pub mod mission_writer;
 pub mod movement;
 pub mod progress_tracker;
+pub mod reflection;
+pub mod scheduling;
+pub mod project_lifecycle;
 pub mod project_spawner;
 pub mod selection;
+pub mod simulation;
 pub mod stats_display;
 pub mod stats_updater;
 pub mod task_assignment;
+pub mod theme_preview;
 pub mod token_tracker;
+pub mod town_hall_progression;
+pub mod utility_buildings;
 pub mod worker_movement;
 pub mod worker_spawner;
+pub mod zac_chat;
+pub mod zac_hero;
+pub mod zac_journal;
+pub mod zac_state;
 
 pub use autonomous_assignment::{autonomous_task_assignment, toggle_autonomy_keypress, display_autonomy_status};
+pub use autonomy_schedule::announce_autonomy_schedule;
+pub use building_placement::drag_project_buildings;
 // Note: update_building_visuals is called directly in main.rs, not through re-export
 // We keep this commented for future modular use
 // pub use building_renderer::update_building_visuals;
 #[allow(unused_imports)]
 pub use building_renderer::spawn_initial_town_hall;
+pub use estimation::MissionEstimator;
+pub use health_monitor::{restore_project_health, schedule_health_checks, apply_health_reports, update_building_condition, HealthMonitor};
+pub use knowledge_manager::KnowledgeManager;
 pub use leisure_zone::spawn_leisure_zone;
 #[allow(unused_imports)]
 pub use leisure_zone::LeisureZone;
 pub use mission_manager::MissionManager;
 pub use progress_tracker::{track_project_progress, sync_project_data};
-pub use project_spawner::spawn_project_buildings;
+pub use project_lifecycle::{project_lifecycle_keys, apply_project_lifecycle_actions, ProjectLifecycleQueue};
+pub use reflection::{apply_reflections, show_reflecting_workers, ReflectionQueue};
+pub use project_spawner::{spawn_project_buildings, process_conquest_queue};
+pub use scheduling::Scheduler;
+pub use simulation::preview_autonomy_keypress;
+pub use selection::{select_project_on_click, select_worker_on_click, SelectedProject, SelectedWorker};
 pub use stats_display::display_comprehensive_stats;
 pub use stats_updater::update_game_stats;
 pub use task_assignment::{assign_worker_on_keypress, start_mission_on_arrival, check_cli_completions};
-pub use token_tracker::{check_budget_reset, display_budget_warnings, display_budget_status};
+pub use theme_preview::{preview_building_theme, ThemePreview};
+pub use token_tracker::{check_budget_reset, display_budget_warnings, display_budget_status, resume_deferred_missions, charge_streamed_usage, persist_token_budget, BudgetGate};
+pub use town_hall_progression::{upgrade_town_hall_keypress, play_stage_transitions};
+pub use utility_buildings::{spawn_utility_buildings, construct_utility_building_keys, progress_utility_buildings};
 pub use worker_movement::{move_workers, send_idle_to_leisure, MovementTarget};
 pub use worker_spawner::{process_worker_production, restore_workers, TownHall};
+pub use zac_chat::{handle_chat_commands, apply_chat_updates, run_zac_tool_calls, ZacChat};
+pub use zac_hero::{spawn_zac_hero, animate_zac_hero};
+pub use zac_journal::{write_daily_digest, JournalManager};
+pub use zac_state::{reconcile_zac_state_files, mirror_zac_state_files};
@@ -2,11 +2,13 @@ use bevy::prelude::*;
 use crate::game::project::Project;
 use crate::game::components::StagedBuilding;
 use crate::game::resources::ProjectManager;
+use crate::game::systems::zac_journal::{JournalEntryType, JournalManager};
 
 /// System that checks for mission completions and updates building stages
 pub fn track_project_progress(
     mut project_query: Query<(&mut Project, &mut StagedBuilding)>,
-    _project_manager: Res<ProjectManager>,
+    project_manager: Res<ProjectManager>,
+    journal: Res<JournalManager>,
 ) {
     for (project, mut building) in project_query.iter_mut() {
         // Calculate expected stage based on mission completion
@@ -16,7 +18,12 @@ pub fn track_project_progress(
         if expected_stage > current_stage {
             // Upgrade building
             building.set_stage(expected_stage);
+            let _ = project_manager.update_visual_stage(&project.id, expected_stage);
             println!("🎉 Project '{}' upgraded to stage {}!", project.name, expected_stage);
+            journal.record(JournalEntryType::StageUpgrade,
+                           &format!("Building upgraded from stage {current_stage} to {expected_stage} ({}/{} missions done)",
+                                    project.completed_missions, project.total_missions),
+                           Some(&project.id), None);
         }
     }
 }
@@ -0,0 +1,200 @@
+use bevy::prelude::*;
+use crate::AppPaths;
+use crate::game::components::UtilityBuilding;
+use crate::game::project::Project;
+use crate::game::project::lifecycle::delete_project_with_export;
+use crate::game::resources::{ProjectManager, WorkerManager};
+use crate::game::systems::{MissionManager, MovementTarget};
+use crate::game::systems::project_spawner::{assign_free_plot, spawn_project_entity};
+use crate::game::systems::building_placement::is_plot_free;
+use crate::game::systems::building_placement::plot_of;
+use crate::game::systems::selection::SelectedProject;
+use crate::game::worker::{Worker, WorkerState};
+
+/// Retire or bring back a project (by id)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectAction {
+    Archive(String),
+    Delete(String),
+    Restore(String),
+}
+
+/// Resource holding project lifecycle actions
+#[derive(Resource, Default)]
+pub struct ProjectLifecycleQueue {
+    /// Destructive action waiting for the user to confirm
+    pub pending: Option<ProjectAction>,
+    /// Actions ready to apply
+    pub confirmed: Vec<ProjectAction>,
+}
+
+/// System for lifecycle keys: X archives, Delete deletes, Enter confirms,
+/// Backspace cancels, R restores the most recently archived project
+pub fn project_lifecycle_keys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    selected: Res<SelectedProject>,
+    project_query: Query<&Project>,
+    project_manager: Res<ProjectManager>,
+    mut queue: ResMut<ProjectLifecycleQueue>,
+) {
+    let selected_project = selected.0.and_then(|e| project_query.get(e).ok());
+
+    if keyboard.just_pressed(KeyCode::KeyX) || keyboard.just_pressed(KeyCode::Delete) {
+        let Some(project) = selected_project else {
+            println!("⚠️ Select a project building first (left click)");
+            return;
+        };
+
+        if keyboard.just_pressed(KeyCode::Delete) {
+            queue.pending = Some(ProjectAction::Delete(project.id.clone()));
+            println!("🗑️  DELETE '{}' permanently? Its history will be exported first.", project.name);
+        } else {
+            queue.pending = Some(ProjectAction::Archive(project.id.clone()));
+            println!("📦 Archive '{}'? It can be restored later with R.", project.name);
+        }
+        println!("   Press Enter to confirm, Backspace to cancel");
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::Enter) {
+        if let Some(action) = queue.pending.take() {
+            queue.confirmed.push(action);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::Backspace) && queue.pending.take().is_some() {
+        println!("↩️  Cancelled");
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyR) {
+        match project_manager.load_archived_projects() {
+            Ok(archived) => match archived.first() {
+                Some(project) => queue.confirmed.push(ProjectAction::Restore(project.id.clone())),
+                None => println!("⚠️ No archived projects to restore"),
+            },
+            Err(e) => eprintln!("Failed to load archived projects: {e}"),
+        }
+    }
+}
+
+/// System to apply confirmed archive, delete and restore actions
+#[allow(clippy::too_many_arguments)]
+pub fn apply_project_lifecycle_actions(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut queue: ResMut<ProjectLifecycleQueue>,
+    mut selected: ResMut<SelectedProject>,
+    project_query: Query<(Entity, &Project, &Transform)>,
+    utility_query: Query<&Transform, With<UtilityBuilding>>,
+    mut worker_query: Query<(Entity, &mut Worker)>,
+    project_manager: Res<ProjectManager>,
+    mission_manager: Res<MissionManager>,
+    worker_manager: Res<WorkerManager>,
+    paths: Res<AppPaths>,
+) {
+    for action in std::mem::take(&mut queue.confirmed) {
+        match action {
+            ProjectAction::Archive(project_id) | ProjectAction::Delete(project_id)
+                if !release_project_workers(&project_id, &mut commands, &mut worker_query, &mission_manager, &worker_manager) =>
+            {
+                println!("⚠️ A worker is still working on this project - wait for it to finish");
+            }
+            ProjectAction::Archive(project_id) => {
+                let Some((entity, project, _)) = project_query.iter().find(|(_, p, _)| p.id == project_id) else {
+                    continue;
+                };
+
+                match project_manager.archive_project(&project_id) {
+                    Ok(()) => {
+                        commands.entity(entity).despawn_recursive();
+                        println!("📦 Archived '{}'", project.name);
+                    }
+                    Err(e) => eprintln!("Failed to archive '{}': {e}", project.name),
+                }
+            }
+            ProjectAction::Delete(project_id) => {
+                let Some((entity, project, _)) = project_query.iter().find(|(_, p, _)| p.id == project_id) else {
+                    continue;
+                };
+
+                let export_dir = paths.data_dir.join("exports");
+                match delete_project_with_export(project, &project_manager, &mission_manager, &export_dir) {
+                    Ok(export_path) => {
+                        commands.entity(entity).despawn_recursive();
+                        println!("🗑️  Deleted '{}' (history exported to {})", project.name, export_path.display());
+                    }
+                    Err(e) => eprintln!("Failed to delete '{}': {e}", project.name),
+                }
+            }
+            ProjectAction::Restore(project_id) => {
+                if let Err(e) = project_manager.restore_project(&project_id) {
+                    eprintln!("Failed to restore project: {e}");
+                    continue;
+                }
+
+                let Some(project) = project_manager.load_projects().ok()
+                    .and_then(|projects| projects.into_iter().find(|p| p.id == project_id)) else {
+                    continue;
+                };
+
+                // Its old plot may have been taken while it was archived
+                let occupied: Vec<Vec3> = project_query.iter().map(|(_, _, t)| t.translation)
+                    .chain(utility_query.iter().map(|t| t.translation))
+                    .collect();
+                let position = match project_manager.load_position(&project.id).ok().flatten() {
+                    Some(pos) if is_plot_free(plot_of(pos), &occupied) => Some(pos),
+                    _ => assign_free_plot(&project, &project_manager, &occupied),
+                };
+
+                let Some(position) = position else {
+                    eprintln!("No free plot left for project '{}'", project.name);
+                    continue;
+                };
+
+                spawn_project_entity(&mut commands, &mut meshes, &mut materials, &project, position);
+                println!("♻️  Restored '{}'", project.name);
+            }
+        }
+    }
+
+    // Drop a selection that points at a despawned building
+    if selected.0.is_some_and(|e| project_query.get(e).is_err()) {
+        selected.0 = None;
+    }
+}
+
+/// Send workers headed to this project back to idle.
+///
+/// Returns false (and changes nothing) if a worker is mid-mission on it.
+fn release_project_workers(
+    project_id: &str,
+    commands: &mut Commands,
+    worker_query: &mut Query<(Entity, &mut Worker)>,
+    mission_manager: &MissionManager,
+    worker_manager: &WorkerManager,
+) -> bool {
+    let mission_ids: Vec<String> = match mission_manager.load_missions(project_id) {
+        Ok(missions) => missions.into_iter().map(|m| m.id).collect(),
+        Err(_) => Vec::new(),
+    };
+
+    let on_project = |w: &Worker| w.current_task_id.as_ref().is_some_and(|id| mission_ids.contains(id));
+
+    if worker_query.iter().any(|(_, w)| on_project(w) && matches!(w.state, WorkerState::Working { .. })) {
+        return false;
+    }
+
+    for (entity, mut worker) in worker_query.iter_mut() {
+        if !on_project(&worker) {
+            continue;
+        }
+
+        commands.entity(entity).remove::<MovementTarget>();
+        worker.state = WorkerState::Idle;
+        worker.current_task_id = None;
+        let _ = worker_manager.update_worker_state(&worker.id, &WorkerState::Idle, None);
+    }
+
+    true
+}
@@ -1,7 +1,12 @@
 use bevy::prelude::*;
 use crate::game::project::Project;
+use crate::game::project::conquest::conquer_project;
 use crate::game::components::{StagedBuilding, BuildingType};
-use crate::game::resources::ProjectManager;
+use crate::game::entities::building_theme::BuildingTheme;
+use crate::game::systems::building_renderer::spawn_building_meshes;
+use crate::game::resources::{BuildingManager, ProjectManager, ConquestQueue};
+use crate::game::systems::MissionManager;
+use crate::game::systems::building_placement::{find_free_plot, PlotOccupantQuery};
 
 /// System to spawn project buildings in the world
 pub fn spawn_project_buildings(
@@ -9,6 +14,7 @@ pub fn spawn_project_buildings(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     project_manager: Res<ProjectManager>,
+    building_manager: Res<BuildingManager>,
     existing_projects: Query<&Project>,
 ) {
     // Load projects from database
@@ -25,68 +31,105 @@ pub fn spawn_project_buildings(
         .map(|p| p.id.clone())
         .collect();
 
+    // Saved positions (and utility buildings), so unplaced projects are kept off taken plots
+    let positions: Vec<(&Project, Option<Vec3>)> = projects.iter()
+        .map(|p| (p, project_manager.load_position(&p.id).ok().flatten()))
+        .collect();
+    let mut occupied: Vec<Vec3> = positions.iter().filter_map(|(_, pos)| *pos).collect();
+    occupied.extend(building_manager.load_buildings().unwrap_or_default().iter().map(|b| b.position));
+
     // Spawn new projects
-    for (index, project) in projects.iter().enumerate() {
+    for (project, saved_position) in positions.iter() {
         if existing_ids.contains(&project.id) {
             continue; // Already spawned
         }
 
-        // Calculate position in spiral pattern
-        let position = calculate_building_position(index);
-
-        // Determine visual stage
-        let stage = project.visual_stage();
-
-        // Spawn building entity
-        let building_entity = commands
-            .spawn((
-                Project {
-                    id: project.id.clone(),
-                    name: project.name.clone(),
-                    path: project.path.clone(),
-                    building_theme: project.building_theme.clone(),
-                    total_missions: project.total_missions,
-                    completed_missions: project.completed_missions,
-                },
-                StagedBuilding::new(BuildingType::TownHall, stage),
-                SpatialBundle::from_transform(Transform::from_translation(position)),
-                Name::new(format!("Project: {}", project.name)),
-            ))
-            .id();
-
-        // Add visual mesh
-        let building_stage = crate::game::entities::building_stage::BuildingStage::from_u8(stage);
-        let mesh = building_stage.generate_mesh();
-        let color = building_stage.get_color();
-
-        let mesh_entity = commands
-            .spawn(PbrBundle {
-                mesh: meshes.add(mesh),
-                material: materials.add(StandardMaterial {
-                    base_color: color,
-                    ..default()
-                }),
-                ..default()
-            })
-            .id();
-
-        commands.entity(building_entity).add_child(mesh_entity);
-
-        println!("Spawned project: {} at {:?}", project.name, position);
+        let Some(position) = saved_position.or_else(|| assign_free_plot(project, &project_manager, &occupied)) else {
+            eprintln!("No free plot left for project '{}'", project.name);
+            continue;
+        };
+        occupied.push(position);
+
+        spawn_project_entity(&mut commands, &mut meshes, &mut materials, project, position);
     }
 }
 
-/// Calculate building position in spiral pattern around origin
-fn calculate_building_position(index: usize) -> Vec3 {
-    let base_distance = 15.0;
-    let angle_step = std::f32::consts::PI * 0.618; // Golden angle for nice spacing
+/// System to conquer queued directories and raise their buildings
+pub fn process_conquest_queue(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut conquest_queue: ResMut<ConquestQueue>,
+    project_manager: Res<ProjectManager>,
+    mission_manager: Res<MissionManager>,
+    existing_projects: PlotOccupantQuery,
+) {
+    if conquest_queue.pending.is_empty() {
+        return;
+    }
+
+    let mut occupied: Vec<Vec3> = existing_projects.iter().map(|t| t.translation).collect();
 
-    let angle = angle_step * index as f32;
-    let distance = base_distance + (index as f32 * 3.0);
+    for path in std::mem::take(&mut conquest_queue.pending) {
+        match conquer_project(&path, &project_manager, &mission_manager) {
+            Ok(project) => {
+                let position = match project_manager.load_position(&project.id).ok().flatten() {
+                    Some(position) => position,
+                    None => match assign_free_plot(&project, &project_manager, &occupied) {
+                        Some(position) => position,
+                        None => {
+                            eprintln!("No free plot left for project '{}'", project.name);
+                            continue;
+                        }
+                    },
+                };
+                occupied.push(position);
+                spawn_project_entity(&mut commands, &mut meshes, &mut materials, &project, position);
+            }
+            Err(e) => {
+                eprintln!("❌ Failed to conquer {}: {e}", path.display());
+            }
+        }
+    }
+}
+
+/// Spawn a project building entity with its stage mesh
+pub fn spawn_project_entity(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    project: &Project,
+    position: Vec3,
+) {
+    // Determine visual stage
+    let stage = project.visual_stage();
+
+    let building = StagedBuilding::new(BuildingType::TownHall, stage)
+        .with_theme(BuildingTheme::from_str(&project.building_theme));
+
+    // Spawn building entity
+    let building_entity = commands
+        .spawn((
+            project.clone(),
+            SpatialBundle::from_transform(Transform::from_translation(position)),
+            Name::new(format!("Project: {}", project.name)),
+        ))
+        .id();
+
+    // Add visual meshes
+    spawn_building_meshes(commands, meshes, materials, building_entity, &building);
+    commands.entity(building_entity).insert(building);
+
+    println!("Spawned project: {} ({}) at {:?}", project.name, project.building_theme, position);
+}
+
+/// Pick the nearest free plot for a project and save it
+pub fn assign_free_plot(project: &Project, project_manager: &ProjectManager, occupied: &[Vec3]) -> Option<Vec3> {
+    let position = find_free_plot(occupied)?;
+
+    if let Err(e) = project_manager.save_position(&project.id, position) {
+        eprintln!("Failed to save position for '{}': {e}", project.name);
+    }
 
-    Vec3::new(
-        angle.cos() * distance,
-        0.0,
-        angle.sin() * distance,
-    )
+    Some(position)
 }
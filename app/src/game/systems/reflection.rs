@@ -0,0 +1,304 @@
+use bevy::prelude::*;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use crate::core::config::AppConfig;
+use crate::game::cli::usage::UsageBreakdown;
+use crate::game::resources::{TokenBudget, UsageScope, WorkerManager};
+use crate::game::systems::knowledge_manager::{KnowledgeEntry, KnowledgeManager};
+use crate::game::worker::{Worker, WorkerState};
+
+/// A reflection taking longer than this is dropped
+const REFLECTION_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Only the end of a transcript is sent along
+const MAX_TRANSCRIPT_CHARS: usize = 8000;
+
+/// Tokens a reflection is budgeted at, and charged when its run reports no usage
+pub const REFLECTION_TOKEN_ESTIMATE: u64 = 3000;
+
+/// A finished mission waiting to be reflected on
+#[derive(Debug, Clone)]
+pub struct ReflectionRequest {
+    pub worker_id: String,
+    pub mission_id: String,
+    pub mission_title: String,
+    pub project_id: String,
+    pub project_name: String,
+    pub project_path: String,
+    /// The worker's model, used for the reflection too
+    pub model: Option<String>,
+    pub success: bool,
+    pub tokens_used: u32,
+    pub duration_secs: u64,
+    pub transcript: String,
+}
+
+/// Answer to "what did you learn, what would you do differently"
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reflection {
+    pub learned: String,
+    pub differently: String,
+    /// The agent judged the learning worth keeping in the knowledge base
+    pub keep: bool,
+}
+
+impl Reflection {
+    /// Parse the `LEARNED:` / `DIFFERENTLY:` / `KEEP:` answer; free text counts as learned
+    pub fn parse(answer: &str) -> Option<Self> {
+        let mut learned = String::new();
+        let mut differently = String::new();
+        let mut keep = false;
+
+        for line in answer.lines().map(str::trim) {
+            if let Some(value) = field(line, "LEARNED:") {
+                learned = value.to_string();
+            } else if let Some(value) = field(line, "DIFFERENTLY:") {
+                differently = value.to_string();
+            } else if let Some(value) = field(line, "KEEP:") {
+                keep = value.to_lowercase().starts_with("yes");
+            }
+        }
+
+        if learned.is_empty() {
+            learned = answer.trim().to_string();
+        }
+
+        if learned.is_empty() {
+            return None;
+        }
+
+        Some(Self { learned, differently, keep })
+    }
+
+    /// Text stored as the worker note
+    pub fn note(&self) -> String {
+        if self.differently.is_empty() {
+            format!("Learned: {}", self.learned)
+        } else {
+            format!("Learned: {}\nNext time: {}", self.learned, self.differently)
+        }
+    }
+}
+
+/// Value after a case-insensitive `key` at the start of `line`
+fn field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let head = line.get(..key.len())?;
+    head.eq_ignore_ascii_case(key).then(|| line[key.len()..].trim())
+}
+
+type FinishedReflections = Arc<Mutex<Vec<(ReflectionRequest, Option<Reflection>, UsageBreakdown)>>>;
+
+/// Resource tracking reflections running in the background
+#[derive(Resource, Default)]
+pub struct ReflectionQueue {
+    finished: FinishedReflections,
+}
+
+impl ReflectionQueue {
+    /// Ask the agent to reflect on a finished mission, off the main thread
+    pub fn start(&self, request: ReflectionRequest) {
+        let finished = self.finished.clone();
+
+        std::thread::spawn(move || {
+            let (reflection, usage) = match reflect(&request) {
+                Ok((answer, usage)) => (Reflection::parse(&answer), usage),
+                Err(e) => {
+                    eprintln!("⚠️ Reflection failed: {e}");
+                    // A run that was cut short still spent tokens
+                    (None, UsageBreakdown::parse("", REFLECTION_TOKEN_ESTIMATE as u32))
+                }
+            };
+
+            if let Ok(mut finished) = finished.lock() {
+                finished.push((request, reflection, usage));
+            }
+        });
+    }
+}
+
+fn reflection_prompt(request: &ReflectionRequest) -> String {
+    let skip = request.transcript.chars().count().saturating_sub(MAX_TRANSCRIPT_CHARS);
+    let transcript: String = request.transcript.chars().skip(skip).collect();
+
+    format!(
+        "You just finished the mission \"{}\" in project {} ({}).\n\
+         Transcript (end):\n{}\n\n\
+         Reflect briefly and answer in exactly three lines:\n\
+         LEARNED: what you learned that would help on future missions\n\
+         DIFFERENTLY: what you would do differently next time\n\
+         KEEP: yes if the learning is worth keeping for other missions, otherwise no\n",
+        request.mission_title,
+        request.project_name,
+        if request.success { "succeeded" } else { "failed" },
+        transcript,
+    )
+}
+
+/// Run a short non-interactive agent call with the prompt on stdin,
+/// returning the answer and the tokens it used
+fn reflect(request: &ReflectionRequest) -> Result<(String, UsageBreakdown), String> {
+    let mut command = Command::new("claude-code");
+    command.args(["--print", "--output-format", "json"]);
+    if let Some(model) = &request.model {
+        command.args(["--model", model]);
+    }
+
+    let mut child = command
+        .current_dir(Path::new(&request.project_path))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn claude-code: {e}"))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(reflection_prompt(request).as_bytes())
+            .map_err(|e| format!("Failed to write prompt: {e}"))?;
+    }
+
+    // Drain stdout while waiting so a full pipe can't stall the agent
+    let reader = child.stdout.take().map(|mut stdout| {
+        std::thread::spawn(move || {
+            let mut output = Vec::new();
+            let _ = stdout.read_to_end(&mut output);
+            output
+        })
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() > REFLECTION_TIMEOUT => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err("timed out".to_string());
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(250)),
+            Err(e) => return Err(format!("Wait error: {e}")),
+        }
+    };
+
+    let output = reader
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default();
+
+    if !status.success() {
+        return Err(format!("claude-code exited with {status}"));
+    }
+
+    let output = String::from_utf8_lossy(&output);
+    let usage = UsageBreakdown::parse(&output, REFLECTION_TOKEN_ESTIMATE as u32);
+
+    // The answer is the `result` of the JSON result line
+    let answer = output.lines().rev()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line.trim()).ok())
+        .find(|result| result["type"] == "result")
+        .and_then(|result| result["result"].as_str().map(str::to_string))
+        .unwrap_or_else(|| output.to_string());
+
+    Ok((answer, usage))
+}
+
+/// System to store finished reflections, charge what they used and send their workers back to idle
+pub fn apply_reflections(
+    queue: Res<ReflectionQueue>,
+    mut worker_query: Query<&mut Worker>,
+    worker_manager: Res<WorkerManager>,
+    knowledge_manager: Res<KnowledgeManager>,
+    mut token_budget: ResMut<TokenBudget>,
+    config: Res<AppConfig>,
+) {
+    let finished: Vec<_> = match queue.finished.lock() {
+        Ok(mut finished) => finished.drain(..).collect(),
+        Err(_) => return,
+    };
+
+    for (request, reflection, usage) in finished {
+        let cost_usd = usage.cost_usd(&config.pricing.rates(request.model.as_deref()));
+        let scope = UsageScope::mission(&request.mission_id, Some(&request.project_id), &request.worker_id);
+        token_budget.add_usage(usage.total(), cost_usd, "reflection", &scope);
+
+        // A worker removed since keeps no notes, and its lesson goes with it
+        let Some(mut worker) = worker_query.iter_mut().find(|w| w.id == request.worker_id) else { continue };
+
+        if let Some(reflection) = &reflection {
+            if let Err(e) = worker_manager.add_note(&request.worker_id, &request.mission_id, &reflection.note()) {
+                eprintln!("❌ Failed to save worker note: {e}");
+            }
+
+            if reflection.keep {
+                if let Err(e) = knowledge_manager.record_entry(&KnowledgeEntry {
+                    project_name: request.project_name.clone(),
+                    mission_id: request.mission_id.clone(),
+                    task_description: request.mission_title.clone(),
+                    solution_applied: reflection.learned.clone(),
+                    reasoning: reflection.differently.clone(),
+                    tokens_used: request.tokens_used,
+                    duration_secs: request.duration_secs,
+                    worker_id: request.worker_id.clone(),
+                    success: request.success,
+                }) {
+                    eprintln!("❌ Failed to record reflection: {e}");
+                }
+            }
+        }
+
+        if worker.state == WorkerState::Reflecting {
+            worker.state = WorkerState::Idle;
+            let _ = worker_manager.update_worker_state(&worker.id, &WorkerState::Idle, None);
+        }
+
+        match &reflection {
+            Some(r) => println!("💭 Worker '{}' reflected: {}{}", worker.name, r.learned,
+                                if r.keep { " (kept)" } else { "" }),
+            None => println!("💭 Worker '{}' finished reflecting without a learning", worker.name),
+        }
+    }
+}
+
+/// Marker for the thought bubble above a reflecting worker
+#[derive(Component)]
+pub struct ThoughtBubble;
+
+/// System to show a thought bubble above workers while they reflect
+pub fn show_reflecting_workers(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    worker_query: Query<(Entity, &Worker, Option<&Children>), Changed<Worker>>,
+    bubble_query: Query<Entity, With<ThoughtBubble>>,
+) {
+    for (entity, worker, children) in worker_query.iter() {
+        let bubble = children.and_then(|c| c.iter().find(|child| bubble_query.contains(**child)).copied());
+        let reflecting = worker.state == WorkerState::Reflecting;
+
+        match (reflecting, bubble) {
+            (true, None) => {
+                commands.entity(entity).with_children(|parent| {
+                    parent.spawn((
+                        ThoughtBubble,
+                        PbrBundle {
+                            mesh: meshes.add(Sphere::new(0.25)),
+                            material: materials.add(StandardMaterial {
+                                base_color: Color::srgba(0.9, 0.9, 1.0, 0.8),
+                                alpha_mode: AlphaMode::Blend,
+                                emissive: LinearRgba::rgb(0.3, 0.3, 0.5),
+                                ..default()
+                            }),
+                            transform: Transform::from_xyz(0.3, 1.7, 0.0),
+                            ..default()
+                        },
+                    ));
+                });
+            }
+            (false, Some(bubble)) => {
+                commands.entity(bubble).despawn_recursive();
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::fmt;
+use crate::game::project::{Mission, MissionAttempt, MissionStatus};
+use crate::game::systems::estimation::MissionEstimate;
+
+/// Names accepted by `[scheduling] strategy`
+pub const STRATEGY_NAMES: [&str; 5] = [
+    "stage_rush",
+    "critical_path",
+    "shortest_job",
+    "round_robin",
+    "cost_minimizing",
+];
+
+/// An available mission together with what strategies need to score it
+#[derive(Debug, Clone)]
+pub struct MissionCandidate {
+    pub mission: Mission,
+    pub project_id: String,
+    pub project_name: String,
+    pub building_pos: Vec3,
+    pub completed_missions: u32,
+    pub total_missions: u32,
+    /// Unfinished missions that (transitively) wait on this one
+    pub dependents: u32,
+    /// Failed attempts of this mission so far
+    pub failed_attempts: u32,
+    pub estimate: MissionEstimate,
+    /// Missions of this project handed out by the scheduler this session
+    pub project_assignments: u32,
+}
+
+impl MissionCandidate {
+    /// Build a candidate from the project's missions and past attempts
+    pub fn new(
+        mission: Mission,
+        project_name: &str,
+        project_missions: &[Mission],
+        attempts: &[MissionAttempt],
+        estimate: MissionEstimate,
+        building_pos: Vec3,
+        project_assignments: u32,
+    ) -> Self {
+        let completed_missions = project_missions.iter()
+            .filter(|m| m.status == MissionStatus::Completed)
+            .count() as u32;
+
+        let failed_attempts = attempts.iter()
+            .filter(|a| a.mission_id == mission.id && !a.success)
+            .count() as u32;
+
+        Self {
+            dependents: count_dependents(&mission, project_missions),
+            project_id: mission.project_id.clone(),
+            project_name: project_name.to_string(),
+            building_pos,
+            completed_missions,
+            total_missions: project_missions.len() as u32,
+            failed_attempts,
+            estimate,
+            project_assignments,
+            mission,
+        }
+    }
+}
+
+/// Number of unfinished missions that depend on `mission`, directly or through others
+fn count_dependents(mission: &Mission, project_missions: &[Mission]) -> u32 {
+    let mut blocked = vec![mission.mission_number];
+    let mut index = 0;
+
+    while index < blocked.len() {
+        let number = blocked[index];
+        for other in project_missions {
+            if other.status != MissionStatus::Completed
+                && other.dependencies.contains(&number)
+                && !blocked.contains(&other.mission_number)
+            {
+                blocked.push(other.mission_number);
+            }
+        }
+        index += 1;
+    }
+
+    (blocked.len() - 1) as u32
+}
+
+/// Named parts of a mission's score, logged with each decision
+#[derive(Debug, Clone, Default)]
+pub struct ScoreBreakdown {
+    pub parts: Vec<(&'static str, f32)>,
+}
+
+impl ScoreBreakdown {
+    pub fn add(mut self, label: &'static str, value: f32) -> Self {
+        self.parts.push((label, value));
+        self
+    }
+
+    pub fn total(&self) -> f32 {
+        self.parts.iter().map(|(_, v)| v).sum()
+    }
+}
+
+impl fmt::Display for ScoreBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (label, value)) in self.parts.iter().enumerate() {
+            match (i, *value < 0.0) {
+                (0, _) => write!(f, "{label} {value:.1}")?,
+                (_, true) => write!(f, " - {label} {:.1}", -value)?,
+                (_, false) => write!(f, " + {label} {value:.1}")?,
+            }
+        }
+        write!(f, " = {:.1}", self.total())
+    }
+}
+
+/// Decides which available mission autonomous assignment picks next (highest score wins)
+pub trait SchedulingStrategy: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn score(&self, candidate: &MissionCandidate) -> ScoreBreakdown;
+}
+
+/// Rush projects that are about to reach their next building stage
+pub struct StageRush;
+
+impl SchedulingStrategy for StageRush {
+    fn name(&self) -> &'static str {
+        "stage_rush"
+    }
+
+    fn score(&self, c: &MissionCandidate) -> ScoreBreakdown {
+        let completion = if c.total_missions > 0 {
+            c.completed_missions as f32 / c.total_missions as f32
+        } else {
+            0.0
+        };
+
+        // Projects near stage boundaries get priority boost
+        let stage = (completion * 10.0).floor();
+        let distance_to_next_stage = ((stage + 1.0) / 10.0) - completion;
+        let boost = if distance_to_next_stage < 0.1 { 50.0 } else { 0.0 };
+
+        ScoreBreakdown::default()
+            .add("base", 100.0)
+            .add("stage boundary", boost)
+            // Earlier missions slightly preferred (unblock dependencies)
+            .add("mission number", -(c.mission.mission_number as f32) * 0.5)
+    }
+}
+
+/// Missions that unblock the most other missions first
+pub struct CriticalPathFirst;
+
+impl SchedulingStrategy for CriticalPathFirst {
+    fn name(&self) -> &'static str {
+        "critical_path"
+    }
+
+    fn score(&self, c: &MissionCandidate) -> ScoreBreakdown {
+        ScoreBreakdown::default()
+            .add("base", 100.0)
+            .add("dependents", c.dependents as f32 * 20.0)
+            .add("mission number", -(c.mission.mission_number as f32) * 0.5)
+    }
+}
+
+/// Quickest missions first, by past durations in the project
+pub struct ShortestEstimatedJob;
+
+impl SchedulingStrategy for ShortestEstimatedJob {
+    fn name(&self) -> &'static str {
+        "shortest_job"
+    }
+
+    fn score(&self, c: &MissionCandidate) -> ScoreBreakdown {
+        ScoreBreakdown::default()
+            .add("base", 100.0)
+            .add("estimated minutes", -c.estimate.secs.expected / 60.0)
+    }
+}
+
+/// Spread assignments evenly across projects
+pub struct RoundRobin;
+
+impl SchedulingStrategy for RoundRobin {
+    fn name(&self) -> &'static str {
+        "round_robin"
+    }
+
+    fn score(&self, c: &MissionCandidate) -> ScoreBreakdown {
+        ScoreBreakdown::default()
+            .add("base", 100.0)
+            .add("project turns", -(c.project_assignments as f32) * 25.0)
+            .add("mission number", -(c.mission.mission_number as f32) * 0.5)
+    }
+}
+
+/// Cheapest expected missions first, counting the retries a failing mission costs
+pub struct CostMinimizing;
+
+impl SchedulingStrategy for CostMinimizing {
+    fn name(&self) -> &'static str {
+        "cost_minimizing"
+    }
+
+    fn score(&self, c: &MissionCandidate) -> ScoreBreakdown {
+        let expected_runs = 1.0 + c.failed_attempts as f32 * 0.5;
+
+        ScoreBreakdown::default()
+            .add("base", 100.0)
+            .add("expected k tokens", -(c.estimate.tokens.expected * expected_runs) / 1000.0)
+    }
+}
+
+/// Built-in strategy by its config name
+pub fn strategy_from_name(name: &str) -> Option<Box<dyn SchedulingStrategy>> {
+    match name {
+        "stage_rush" => Some(Box::new(StageRush)),
+        "critical_path" => Some(Box::new(CriticalPathFirst)),
+        "shortest_job" => Some(Box::new(ShortestEstimatedJob)),
+        "round_robin" => Some(Box::new(RoundRobin)),
+        "cost_minimizing" => Some(Box::new(CostMinimizing)),
+        _ => None,
+    }
+}
+
+/// Resource holding the active strategy and the assignments it has made
+#[derive(Resource)]
+pub struct Scheduler {
+    pub strategy: Box<dyn SchedulingStrategy>,
+    assignments_per_project: HashMap<String, u32>,
+}
+
+impl Scheduler {
+    /// Scheduler for a configured strategy name, falling back to stage rush
+    pub fn from_config(name: &str) -> Self {
+        let strategy = strategy_from_name(name).unwrap_or_else(|| {
+            eprintln!("⚠️  Unknown scheduling strategy '{name}' (expected one of {}) - using stage_rush",
+                      STRATEGY_NAMES.join(", "));
+            Box::new(StageRush)
+        });
+
+        Self {
+            strategy,
+            assignments_per_project: HashMap::new(),
+        }
+    }
+
+    /// Independent copy with the same strategy and counts, for dry runs
+    pub fn snapshot(&self) -> Self {
+        Self {
+            strategy: strategy_from_name(self.strategy.name()).unwrap_or_else(|| Box::new(StageRush)),
+            assignments_per_project: self.assignments_per_project.clone(),
+        }
+    }
+
+    pub fn project_assignments(&self, project_id: &str) -> u32 {
+        self.assignments_per_project.get(project_id).copied().unwrap_or(0)
+    }
+
+    /// Index and score of the best candidate
+    pub fn pick(&self, candidates: &[MissionCandidate]) -> Option<(usize, ScoreBreakdown)> {
+        candidates.iter()
+            .map(|c| self.strategy.score(c))
+            .enumerate()
+            .max_by(|(ia, a), (ib, b)| a.total().total_cmp(&b.total()).then(ib.cmp(ia)))
+    }
+
+    /// Count an assignment so later candidates of the project see it
+    pub fn record_assignment(&mut self, candidates: &mut [MissionCandidate], project_id: &str) {
+        let count = self.assignments_per_project.entry(project_id.to_string()).or_insert(0);
+        *count += 1;
+
+        for candidate in candidates.iter_mut().filter(|c| c.project_id == project_id) {
+            candidate.project_assignments = *count;
+        }
+    }
+}
@@ -1,4 +1,92 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::camera::MainCamera;
+use crate::game::project::Project;
+use crate::game::worker::Worker;
+use crate::game::systems::building_placement::{cursor_ground_position, pick_building};
+
+/// Currently selected project building
+#[derive(Resource, Default)]
+pub struct SelectedProject(pub Option<Entity>);
+
+/// Worker shown in the worker panel
+#[derive(Resource, Default)]
+pub struct SelectedWorker(pub Option<Entity>);
+
+/// Clicks this close to a worker select it
+const WORKER_PICK_RADIUS: f32 = 1.0;
+
 // Placeholder selection system
 pub fn handle_selection() {
     // Will be implemented in M2
 }
+
+/// System to select a project building with a left click
+pub fn select_project_on_click(
+    mouse: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    building_query: Query<(Entity, &Project, &Transform)>,
+    mut selected: ResMut<SelectedProject>,
+) {
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let Some(cursor_ground) = cursor_ground_position(&window_query, &camera_query) else {
+        return;
+    };
+
+    let picked = pick_building(
+        cursor_ground,
+        building_query.iter().map(|(entity, _, transform)| (entity, transform.translation)),
+    ).map(|(entity, _)| entity);
+
+    if picked == selected.0 {
+        return;
+    }
+    selected.0 = picked;
+
+    if let Some(project) = picked.and_then(|e| building_query.get(e).ok()).map(|(_, p, _)| p) {
+        println!("🔎 Selected project '{}' ({}/{} missions)",
+                 project.name, project.completed_missions, project.total_missions);
+    }
+}
+
+/// System to select a worker with a left click, opening the worker panel
+pub fn select_worker_on_click(
+    mouse: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    worker_query: Query<(Entity, &Worker, &Transform)>,
+    interaction_query: Query<&Interaction>,
+    mut selected: ResMut<SelectedWorker>,
+) {
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    // Clicks on UI buttons keep the selection
+    if interaction_query.iter().any(|i| *i != Interaction::None) {
+        return;
+    }
+
+    let Some(cursor_ground) = cursor_ground_position(&window_query, &camera_query) else {
+        return;
+    };
+
+    let picked = worker_query.iter()
+        .map(|(entity, _, transform)| (entity, transform.translation.with_y(0.0).distance(cursor_ground)))
+        .filter(|(_, distance)| *distance < WORKER_PICK_RADIUS)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity);
+
+    if picked == selected.0 {
+        return;
+    }
+    selected.0 = picked;
+
+    if let Some((_, worker, _)) = picked.and_then(|e| worker_query.get(e).ok()) {
+        println!("🔎 Selected worker '{}' ({})", worker.name, worker.state.as_str());
+    }
+}
@@ -0,0 +1,466 @@
+use bevy::prelude::*;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::core::config::AppConfig;
+use crate::game::project::{Mission, MissionAttempt, MissionStatus, Project};
+use crate::game::resources::{AutonomySettings, BudgetWindowKind, TokenBudget};
+use crate::game::systems::estimation::{MissionEstimate, MissionEstimator};
+use crate::game::systems::scheduling::{MissionCandidate, Scheduler};
+use crate::game::systems::MissionManager;
+use crate::game::worker::{specialty, Worker, WorkerState};
+use crate::AppPaths;
+
+/// Furthest ahead a dry run looks
+const HORIZON_SECS: f32 = 7.0 * 24.0 * 3600.0;
+
+/// Time a reflecting or walking worker is assumed to need before its next step
+const SETTLE_SECS: f32 = 60.0;
+
+/// A project as the simulation sees it; missions change status as the run goes
+pub struct SimProject {
+    pub project: Project,
+    pub building_pos: Vec3,
+    pub missions: Vec<Mission>,
+    pub attempts: Vec<MissionAttempt>,
+}
+
+/// A worker and when it is next free
+pub struct SimWorker {
+    pub worker: Worker,
+    /// Seconds from now
+    pub free_at: f32,
+    /// Mission the worker is already on, finishing at `free_at`
+    pub current_mission: Option<String>,
+}
+
+/// A budget window in tokens, so dollar windows are comparable
+pub struct SimWindow {
+    pub kind: BudgetWindowKind,
+    pub limit: f32,
+    pub remaining: f32,
+    pub start: chrono::DateTime<chrono::Utc>,
+}
+
+impl SimWindow {
+    /// Windows of the live budget, dollar ones priced at its default rate
+    pub fn from_budget(budget: &TokenBudget) -> Vec<Self> {
+        budget.windows.iter()
+            .map(|w| Self {
+                kind: w.kind,
+                limit: w.unit.tokens_for(w.limit, budget.usd_per_token) as f32,
+                remaining: w.unit.tokens_for(w.remaining(), budget.usd_per_token) as f32,
+                start: w.start,
+            })
+            .collect()
+    }
+
+    fn resets_in(&self, base: chrono::DateTime<chrono::Utc>) -> f32 {
+        (self.kind.end_of(self.start) - base).num_seconds() as f32
+    }
+}
+
+/// One mission the scheduler would hand out
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulatedAssignment {
+    pub starts_in_secs: f32,
+    pub starts_at: String,
+    pub ends_in_secs: f32,
+    pub ends_at: String,
+    pub worker: String,
+    pub project: String,
+    pub mission_number: u32,
+    pub mission_title: String,
+    pub estimated_tokens: f32,
+    pub tokens_low: f32,
+    pub tokens_high: f32,
+    /// Tokens left in the tightest window once this mission is charged
+    pub budget_left: f32,
+    pub score: String,
+}
+
+/// A stretch where missions were ready but the budget made them wait
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulatedBudgetWait {
+    pub from_secs: f32,
+    pub until_secs: f32,
+    pub until: String,
+    pub window: String,
+}
+
+/// A building reaching its next stage
+#[derive(Debug, Clone, Serialize)]
+pub struct StageMilestone {
+    pub at_secs: f32,
+    pub at: String,
+    pub project: String,
+    pub stage: u8,
+}
+
+/// Projected timeline of a dry run
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationReport {
+    pub generated_at: String,
+    pub strategy: String,
+    pub assignments: Vec<SimulatedAssignment>,
+    pub budget_waits: Vec<SimulatedBudgetWait>,
+    pub stage_milestones: Vec<StageMilestone>,
+    pub total_tokens: f32,
+    /// When the last simulated mission finishes
+    pub finishes_in_secs: f32,
+    /// Missions still open when the run stopped
+    pub unscheduled: Vec<String>,
+}
+
+struct InFlight {
+    project_index: usize,
+    mission_id: String,
+    worker_index: usize,
+    ends_at: f32,
+}
+
+/// Run `scheduler` against the given state without touching the world or spawning anything
+pub fn simulate(
+    mut projects: Vec<SimProject>,
+    mut workers: Vec<SimWorker>,
+    mut windows: Vec<SimWindow>,
+    estimates: &HashMap<String, MissionEstimate>,
+    mut scheduler: Scheduler,
+    autonomy: &AutonomySettings,
+    headroom_per_mission: f32,
+) -> SimulationReport {
+    let base = chrono::Utc::now();
+    let local = |secs: f32| (base + chrono::Duration::milliseconds((secs * 1000.0) as i64)).with_timezone(&chrono::Local);
+    let at = |secs: f32| (base + chrono::Duration::seconds(secs as i64))
+        .with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string();
+    let estimate_of = |mission: &Mission| estimates.get(&mission.id).copied()
+        .unwrap_or_else(|| MissionEstimator::default().estimate(mission));
+
+    // Work already under way finishes first
+    let mut in_flight: Vec<InFlight> = Vec::new();
+    for (worker_index, sim_worker) in workers.iter().enumerate() {
+        let Some(mission_id) = &sim_worker.current_mission else { continue };
+        if let Some(project_index) = projects.iter().position(|p| p.missions.iter().any(|m| &m.id == mission_id)) {
+            in_flight.push(InFlight {
+                project_index,
+                mission_id: mission_id.clone(),
+                worker_index,
+                ends_at: sim_worker.free_at,
+            });
+            if let Some(mission) = projects[project_index].missions.iter_mut().find(|m| &m.id == mission_id) {
+                mission.status = MissionStatus::InProgress;
+            }
+        }
+    }
+
+    let mut report = SimulationReport {
+        generated_at: base.to_rfc3339(),
+        strategy: scheduler.strategy.name().to_string(),
+        assignments: Vec::new(),
+        budget_waits: Vec::new(),
+        stage_milestones: Vec::new(),
+        total_tokens: 0.0,
+        finishes_in_secs: 0.0,
+        unscheduled: Vec::new(),
+    };
+
+    let mut now = 0.0;
+    while now <= HORIZON_SECS {
+        // Finish missions due by now
+        in_flight.sort_by(|a, b| a.ends_at.total_cmp(&b.ends_at));
+        while in_flight.first().is_some_and(|f| f.ends_at <= now) {
+            let done = in_flight.remove(0);
+            let project = &mut projects[done.project_index];
+            if let Some(mission) = project.missions.iter_mut().find(|m| m.id == done.mission_id) {
+                mission.status = MissionStatus::Completed;
+            }
+
+            let stage_before = project.project.visual_stage();
+            project.project.completed_missions += 1;
+            let stage = project.project.visual_stage();
+            if stage > stage_before {
+                report.stage_milestones.push(StageMilestone {
+                    at_secs: done.ends_at,
+                    at: at(done.ends_at),
+                    project: project.project.name.clone(),
+                    stage,
+                });
+            }
+
+            workers[done.worker_index].current_mission = None;
+            report.finishes_in_secs = report.finishes_in_secs.max(done.ends_at);
+        }
+
+        // Start fresh budget windows
+        for window in windows.iter_mut() {
+            while window.resets_in(base) <= now {
+                window.start = window.kind.end_of(window.start);
+                window.remaining = window.limit;
+            }
+        }
+
+        // Hand out missions the way autonomy would, under the schedule in force
+        let (max_concurrent, _) = autonomy.concurrency_at(local(now));
+        let mut waiting_on_budget = None;
+        while in_flight.len() < max_concurrent {
+            let free: Vec<usize> = (0..workers.len())
+                .filter(|i| workers[*i].current_mission.is_none() && workers[*i].free_at <= now)
+                .collect();
+            if free.is_empty() {
+                break;
+            }
+
+            let mut candidates = Vec::new();
+            for sim_project in projects.iter().filter(|p| !autonomy.is_blacked_out(&p.project.name, local(now))) {
+                let completed: Vec<u32> = sim_project.missions.iter()
+                    .filter(|m| m.status == MissionStatus::Completed)
+                    .map(|m| m.mission_number)
+                    .collect();
+
+                for mission in sim_project.missions.iter().filter(|m| m.is_available(&completed)) {
+                    candidates.push(MissionCandidate::new(
+                        mission.clone(),
+                        &sim_project.project.name,
+                        &sim_project.missions,
+                        &sim_project.attempts,
+                        estimate_of(mission),
+                        sim_project.building_pos,
+                        scheduler.project_assignments(&sim_project.project.id),
+                    ));
+                }
+            }
+
+            // Only what fits in every window after the running missions' headroom, gated like a real launch
+            let reserved = in_flight.len() as f32 * headroom_per_mission;
+            candidates.retain(|c| {
+                let blocking = windows.iter().find(|w| c.estimate.gate_tokens() as f32 > w.remaining - reserved);
+                if let Some(window) = blocking {
+                    waiting_on_budget = Some(window.kind);
+                }
+                blocking.is_none()
+            });
+
+            let Some((index, score)) = scheduler.pick(&candidates) else { break };
+            let candidate = candidates.swap_remove(index);
+            scheduler.record_assignment(&mut candidates, &candidate.project_id);
+
+            let domains = &candidate.mission.domains;
+            let Some(worker_index) = free.iter().copied().max_by(|a, b| {
+                specialty::suitability(&workers[*a].worker.specialty_scores, domains)
+                    .total_cmp(&specialty::suitability(&workers[*b].worker.specialty_scores, domains))
+            }) else { break };
+
+            let tokens = candidate.estimate.tokens.expected;
+            for window in windows.iter_mut() {
+                window.remaining -= tokens;
+            }
+            let budget_left = windows.iter().map(|w| w.remaining).fold(f32::INFINITY, f32::min);
+
+            let project_index = projects.iter().position(|p| p.project.id == candidate.project_id).unwrap_or(0);
+            if let Some(mission) = projects[project_index].missions.iter_mut().find(|m| m.id == candidate.mission.id) {
+                mission.status = MissionStatus::InProgress;
+            }
+
+            let ends_at = now + candidate.estimate.secs.expected;
+            workers[worker_index].current_mission = Some(candidate.mission.id.clone());
+            workers[worker_index].free_at = ends_at;
+            in_flight.push(InFlight {
+                project_index,
+                mission_id: candidate.mission.id.clone(),
+                worker_index,
+                ends_at,
+            });
+
+            report.total_tokens += tokens;
+            report.assignments.push(SimulatedAssignment {
+                starts_in_secs: now,
+                starts_at: at(now),
+                ends_in_secs: ends_at,
+                ends_at: at(ends_at),
+                worker: workers[worker_index].worker.name.clone(),
+                project: candidate.project_name.clone(),
+                mission_number: candidate.mission.mission_number,
+                mission_title: candidate.mission.title.clone(),
+                estimated_tokens: tokens,
+                tokens_low: candidate.estimate.tokens.low,
+                tokens_high: candidate.estimate.tokens.high,
+                budget_left: budget_left.max(0.0),
+                score: score.to_string(),
+            });
+        }
+
+        // Jump to the next finish, or to a budget reset if work is waiting on it
+        let next_finish = in_flight.iter().map(|f| f.ends_at).fold(f32::INFINITY, f32::min);
+        let next_free = workers.iter()
+            .filter(|w| w.current_mission.is_none() && w.free_at > now)
+            .map(|w| w.free_at)
+            .fold(f32::INFINITY, f32::min);
+        let next_reset = match waiting_on_budget {
+            Some(_) => windows.iter().map(|w| w.resets_in(base)).fold(f32::INFINITY, f32::min),
+            None => f32::INFINITY,
+        };
+
+        // Rounded up so the next pass is past the boundary, not just short of it
+        let next_change = autonomy.next_change(local(now))
+            .map(|t| ((t.with_timezone(&chrono::Utc) - base).num_milliseconds() as f32 / 1000.0).ceil())
+            .filter(|secs| *secs > now)
+            .unwrap_or(f32::INFINITY);
+
+        let next = next_finish.min(next_free).min(next_reset).min(next_change);
+        if !next.is_finite() || next <= now {
+            break;
+        }
+
+        if let Some(kind) = waiting_on_budget.filter(|_| next_reset < next_finish.min(next_free).min(next_change)) {
+            // Back-to-back resets that don't free enough extend the same wait
+            match report.budget_waits.last_mut().filter(|w| w.until_secs >= now) {
+                Some(wait) => {
+                    wait.until_secs = next_reset;
+                    wait.until = at(next_reset);
+                }
+                None => report.budget_waits.push(SimulatedBudgetWait {
+                    from_secs: now,
+                    until_secs: next_reset,
+                    until: at(next_reset),
+                    window: kind.label().to_string(),
+                }),
+            }
+        }
+
+        now = next;
+    }
+
+    for sim_project in &projects {
+        for mission in sim_project.missions.iter().filter(|m| m.status != MissionStatus::Completed) {
+            if !report.assignments.iter().any(|a| a.project == sim_project.project.name && a.mission_number == mission.mission_number) {
+                report.unscheduled.push(format!("{} #{} {}", sim_project.project.name, mission.mission_number, mission.title));
+            }
+        }
+    }
+
+    report
+}
+
+/// Write a report to `<export_dir>/dry-run-<timestamp>.json`
+pub fn export_report(report: &SimulationReport, export_dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(export_dir)
+        .map_err(|e| format!("Failed to create simulation directory: {e}"))?;
+
+    let content = serde_json::to_string_pretty(report)
+        .map_err(|e| format!("JSON error: {e}"))?;
+
+    let filename = format!("dry-run-{}.json", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+    let export_path = export_dir.join(filename);
+
+    fs::write(&export_path, content)
+        .map_err(|e| format!("Failed to write simulation report: {e}"))?;
+
+    Ok(export_path)
+}
+
+/// System to dry-run autonomy on 'P': print the projected timeline and export it as JSON
+#[allow(clippy::too_many_arguments)]
+pub fn preview_autonomy_keypress(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    worker_query: Query<&Worker>,
+    project_query: Query<(&Project, &Transform)>,
+    mission_manager: Res<MissionManager>,
+    estimator: Res<MissionEstimator>,
+    scheduler: Res<Scheduler>,
+    token_budget: Res<TokenBudget>,
+    autonomy: Res<AutonomySettings>,
+    config: Res<AppConfig>,
+    paths: Res<AppPaths>,
+) {
+    if !keyboard.just_pressed(KeyCode::KeyP) {
+        return;
+    }
+
+    let mut estimates = HashMap::new();
+    let mut projects = Vec::new();
+    for (project, transform) in project_query.iter() {
+        let missions = mission_manager.load_missions(&project.id).unwrap_or_default();
+        for mission in &missions {
+            estimates.insert(mission.id.clone(), estimator.estimate(mission));
+        }
+
+        projects.push(SimProject {
+            project: project.clone(),
+            building_pos: transform.translation,
+            attempts: mission_manager.load_project_attempts(&project.id).unwrap_or_default(),
+            missions,
+        });
+    }
+
+    let workers: Vec<SimWorker> = worker_query.iter()
+        .filter(|w| !matches!(w.state, WorkerState::Crashed { .. }))
+        .map(|w| {
+            let estimated_secs = |mission_id: &str| estimates.get(mission_id).map(|e| e.secs.expected).unwrap_or(SETTLE_SECS);
+            let (free_at, current_mission) = match (&w.state, &w.current_task_id) {
+                (WorkerState::Working { mission_id, started_at }, _) => {
+                    let elapsed = chrono::DateTime::parse_from_rfc3339(started_at)
+                        .map(|t| (chrono::Local::now() - t.with_timezone(&chrono::Local)).num_seconds() as f32)
+                        .unwrap_or(0.0);
+                    ((estimated_secs(mission_id) - elapsed).max(SETTLE_SECS), Some(mission_id.clone()))
+                }
+                (WorkerState::MovingTo { .. }, Some(mission_id)) => (estimated_secs(mission_id) + SETTLE_SECS, Some(mission_id.clone())),
+                (WorkerState::Reflecting, _) => (SETTLE_SECS, None),
+                _ => (0.0, None),
+            };
+
+            SimWorker { worker: w.clone(), free_at, current_mission }
+        })
+        .collect();
+
+    let report = simulate(
+        projects,
+        workers,
+        SimWindow::from_budget(&token_budget),
+        &estimates,
+        scheduler.snapshot(),
+        &autonomy,
+        config.tokens.headroom_per_mission as f32,
+    );
+
+    print_report(&report);
+
+    match export_report(&report, &paths.data_dir.join("simulations")) {
+        Ok(path) => println!("💾 Dry run exported to {}", path.display()),
+        Err(e) => eprintln!("Failed to export dry run: {e}"),
+    }
+}
+
+fn print_report(report: &SimulationReport) {
+    let duration = |secs: f32| {
+        let minutes = (secs / 60.0).round() as i64;
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    };
+
+    println!("\n🔮 AUTONOMY DRY RUN ({}) - nothing is started", report.strategy);
+
+    if report.assignments.is_empty() {
+        println!("   No missions would be assigned right now");
+    }
+    for a in &report.assignments {
+        println!("   +{:>8}  {} → {} #{} '{}' (~{:.1}k tokens, done +{}, {:.1}k left)",
+                 duration(a.starts_in_secs), a.worker, a.project, a.mission_number, a.mission_title,
+                 a.estimated_tokens / 1000.0, duration(a.ends_in_secs), a.budget_left / 1000.0);
+    }
+
+    for wait in &report.budget_waits {
+        println!("   ⏳ +{}: waiting on the {} budget until {}", duration(wait.from_secs), wait.window, wait.until);
+    }
+
+    for milestone in &report.stage_milestones {
+        println!("   🏗️  {} reaches stage {} at {} (+{})",
+                 milestone.project, milestone.stage, milestone.at, duration(milestone.at_secs));
+    }
+
+    println!("   Total: ~{:.1}k tokens, last mission done in {}", report.total_tokens / 1000.0, duration(report.finishes_in_secs));
+    if !report.unscheduled.is_empty() {
+        println!("   {} missions not reached (blocked, failed or beyond the horizon)", report.unscheduled.len());
+    }
+}
@@ -1,5 +1,8 @@
 use bevy::prelude::*;
-use crate::game::resources::{GameStats, TokenBudget, AutonomySettings};
+use crate::game::resources::{GameStats, TokenBudget, AutonomySettings, WorkerManager};
+use crate::game::worker::{specialty, Worker};
+use crate::core::database;
+use crate::Database;
 
 /// System to display comprehensive stats
 pub fn display_comprehensive_stats(
@@ -7,6 +10,9 @@ pub fn display_comprehensive_stats(
     token_budget: Res<TokenBudget>,
     autonomy: Res<AutonomySettings>,
     keyboard: Res<ButtonInput<KeyCode>>,
+    worker_query: Query<&Worker>,
+    worker_manager: Res<WorkerManager>,
+    db: Res<Database>,
 ) {
     if keyboard.just_pressed(KeyCode::KeyS) {
         println!("\n╔════════════════════════════════════════╗");
@@ -19,6 +25,29 @@ pub fn display_comprehensive_stats(
         println!("║   Idle:  {:2}                           ║", stats.workers_idle);
         println!("║   Working: {:2}                         ║", stats.workers_working);
 
+        // Specialties per worker, best domains first
+        for worker in worker_query.iter() {
+            let line: String = format!("{}: {}", worker.name, specialty::summary(&worker.specialty_scores))
+                .chars()
+                .take(36)
+                .collect();
+            println!("║   {line:<37}║");
+        }
+
+        // Hall of fame
+        let hall_of_fame = worker_manager.load_hall_of_fame(3).unwrap_or_default();
+        if !hall_of_fame.is_empty() {
+            println!("║                                        ║");
+            println!("║ 🏆 HALL OF FAME                        ║");
+            for retired in &hall_of_fame {
+                let line: String = format!("{}: {} tasks, {} tok, ${:.2}", retired.name, retired.total_tasks_completed, retired.total_tokens_used, retired.total_cost_usd)
+                    .chars()
+                    .take(36)
+                    .collect();
+                println!("║   {line:<37}║");
+            }
+        }
+
         // Tasks
         println!("║                                        ║");
         println!("║ 📋 TASKS                               ║");
@@ -33,12 +62,14 @@ pub fn display_comprehensive_stats(
         // Token Budget
         println!("║                                        ║");
         println!("║ 💰 TOKEN BUDGET                        ║");
-        println!("║   Used: {}/{} ({:.1}%)      ║",
-                 token_budget.current_period_used,
-                 token_budget.hourly_limit,
-                 token_budget.percentage_used());
+        for window in &token_budget.windows {
+            let line = format!("{}: {}", window.kind.label(), window.usage_label());
+            println!("║   {line:<37}║");
+        }
 
-        let remaining_pct = token_budget.percentage_remaining();
+        // The food meter follows whichever budget is closest to running out
+        let tightest = token_budget.tightest();
+        let remaining_pct = tightest.fraction_remaining() * 100.0;
         let bar_length = 20;
         let filled = ((remaining_pct / 100.0) * bar_length as f32) as usize;
         let empty = bar_length - filled;
@@ -48,6 +79,7 @@ pub fn display_comprehensive_stats(
                          "░".repeat(empty));
 
         println!("║   Food: {bar} {remaining_pct:.0}%       ║");
+        println!("║   (tightest: {:<7})                  ║", tightest.kind.label());
 
         let time_until_reset = token_budget.time_until_reset();
         let hours = time_until_reset.num_hours();
@@ -58,12 +90,38 @@ pub fn display_comprehensive_stats(
         let burn_rate = token_budget.estimated_burn_rate_per_hour();
         println!("║   Burn rate: {burn_rate:.0} tok/hr             ║");
 
+        // Dollar costs over the last week
+        if let Ok(conn) = db.0.lock() {
+            let since = (chrono::Utc::now() - chrono::Duration::days(7)).format("%Y-%m-%d %H:%M:%S").to_string();
+            let sections = [
+                ("per day", database::cost_by_day(&conn, &since)),
+                ("top projects", database::cost_by_project(&conn, &since, 3)),
+                ("top workers", database::cost_by_worker(&conn, &since, 3)),
+            ];
+
+            println!("║                                        ║");
+            println!("║ 💵 COSTS (last 7 days)                 ║");
+            for (title, rows) in sections {
+                let rows = rows.unwrap_or_default();
+                if rows.is_empty() {
+                    continue;
+                }
+                println!("║   {title:<37}║");
+                for (name, cost) in rows {
+                    let line: String = format!("{name}: ${cost:.2}").chars().take(34).collect();
+                    println!("║     {line:<35}║");
+                }
+            }
+        }
+
         // Autonomy
         println!("║                                        ║");
         println!("║ 🤖 ZAC^ AUTONOMY                       ║");
         println!("║   Status: {}                      ║",
                  if autonomy.enabled { "ENABLED " } else { "DISABLED" });
-        println!("║   Max Concurrent: {}                   ║", autonomy.max_concurrent_workers);
+        let (max_concurrent, window) = autonomy.concurrency_at(chrono::Local::now());
+        println!("║   Max Concurrent: {}                   ║", max_concurrent);
+        println!("║   Window: {:<29}║", window.unwrap_or("default"));
 
         println!("╚════════════════════════════════════════╝\n");
     }
@@ -1,34 +1,79 @@
 use bevy::prelude::*;
-use crate::game::worker::{Worker, WorkerState};
-use crate::game::project::Project;
+use crate::game::worker::{specialty, Worker, WorkerState};
+use crate::game::project::{MissionAttempt, Project};
 use crate::game::systems::{MissionManager, MovementTarget};
-use crate::game::resources::{WorkerManager, CliManagerResource, ProjectManager};
+use crate::game::resources::{WorkerManager, CliManagerResource, ProjectManager, TokenBudget, TownFacilities, UsageScope};
+use crate::game::systems::knowledge_manager::{KnowledgeEntry, KnowledgeManager};
 use crate::game::systems::mission_writer::MissionWriter;
+use crate::game::systems::reflection::{ReflectionQueue, ReflectionRequest, REFLECTION_TOKEN_ESTIMATE};
+use crate::game::systems::estimation::{EstimationSample, MissionEstimator};
+use crate::game::systems::token_tracker::BudgetGate;
+use crate::game::systems::zac_journal::{JournalEntryType, JournalManager};
+use crate::game::git;
+use crate::game::cli::usage::UsageBreakdown;
+use crate::core::config::AppConfig;
+use std::path::Path;
 
 /// Temporary: Assign worker to project on 'A' key
+#[allow(clippy::too_many_arguments)]
 pub fn assign_worker_on_keypress(
     keyboard: Res<ButtonInput<KeyCode>>,
     mut worker_query: Query<(Entity, &mut Worker, &Transform)>,
     project_query: Query<(&Project, &Transform)>,
     mission_manager: Res<MissionManager>,
     worker_manager: Res<WorkerManager>,
+    estimator: Res<MissionEstimator>,
+    token_budget: Res<TokenBudget>,
+    journal: Res<JournalManager>,
+    config: Res<AppConfig>,
     mut commands: Commands,
 ) {
     if keyboard.just_pressed(KeyCode::KeyA) {
-        // Find first idle/ready worker
-        let worker_result = worker_query.iter_mut()
-            .find(|(_, w, _)| w.state == WorkerState::Idle || w.state == WorkerState::Ready)
-            .map(|(e, w, t)| (e, w.clone(), t.translation));
+        if token_budget.is_depleted() {
+            let minutes = token_budget.time_until_reset().num_minutes().max(0);
+            println!("🚫 Token budget depleted - no new missions until the reset in {minutes}m");
+            return;
+        }
+
+        let in_flight = worker_query.iter()
+            .filter(|(_, w, _)| matches!(w.state, WorkerState::Working { .. }))
+            .count();
+
+        let idle_workers: Vec<(Entity, Worker)> = worker_query.iter()
+            .filter(|(_, w, _)| w.is_free())
+            .map(|(e, w, _)| (e, w.clone()))
+            .collect();
+
+        // Missions another worker is walking to or waiting to launch
+        let claimed: Vec<String> = worker_query.iter()
+            .filter_map(|(_, w, _)| w.current_task_id.clone())
+            .collect();
 
-        if let Some((worker_entity, worker, _worker_pos)) = worker_result {
+        if !idle_workers.is_empty() {
             // Find first project with available missions
             for (project, project_transform) in project_query.iter() {
                 let available_missions = match mission_manager.get_available_missions(&project.id) {
-                    Ok(missions) if !missions.is_empty() => missions,
-                    _ => continue,
+                    Ok(missions) => missions,
+                    Err(_) => continue,
                 };
 
-                let mission = &available_missions[0];
+                let Some(mission) = available_missions.iter().find(|m| !claimed.contains(&m.id)) else { continue };
+
+                // Send the idle worker best suited to the mission's domains
+                let Some((worker_entity, worker)) = idle_workers.iter()
+                    .max_by(|(_, a), (_, b)| {
+                        specialty::suitability(&a.specialty_scores, &mission.domains)
+                            .total_cmp(&specialty::suitability(&b.specialty_scores, &mission.domains))
+                    })
+                    .cloned() else { return };
+
+                let estimate = estimator.estimate(mission);
+                let scope = UsageScope::mission(&mission.id, Some(&project.id), &worker.id)
+                    .with_names(&project.name, Some(&worker.name));
+                if let Err(reason) = token_budget.check_launch(estimate.gate_tokens(), config.pricing.estimate_rate(worker.profile.model.as_deref()), in_flight, config.tokens.headroom_per_mission, &scope) {
+                    println!("🚫 Not assigning mission '{}': {reason}", mission.title);
+                    return;
+                }
 
                 // Send worker to project building
                 commands.entity(worker_entity).insert(MovementTarget::new(project_transform.translation));
@@ -43,6 +88,12 @@ pub fn assign_worker_on_keypress(
 
                 println!("📋 Assigned worker '{}' to mission: {}", worker.name, mission.title);
                 println!("   Worker walking to project: {}", project.name);
+                println!("   📏 Estimate: {estimate}");
+
+                journal.record(JournalEntryType::UserOverride,
+                               &format!("Assigned worker '{}' to mission {} '{}' by hand (estimate {estimate})",
+                                        worker.name, mission.mission_number, mission.title),
+                               Some(&project.id), Some(&mission.id));
 
                 return;
             }
@@ -55,12 +106,20 @@ pub fn assign_worker_on_keypress(
 }
 
 /// System to start mission when worker arrives at building
+#[allow(clippy::too_many_arguments)]
 pub fn start_mission_on_arrival(
     mut worker_query: Query<(Entity, &mut Worker, &Transform), Changed<Worker>>,
     project_query: Query<(&Project, &Transform)>,
     mission_manager: Res<MissionManager>,
     worker_manager: Res<WorkerManager>,
     cli_manager: Res<CliManagerResource>,
+    knowledge_manager: Res<KnowledgeManager>,
+    estimator: Res<MissionEstimator>,
+    facilities: Res<TownFacilities>,
+    config: Res<AppConfig>,
+    token_budget: Res<TokenBudget>,
+    mut budget_gate: ResMut<BudgetGate>,
+    journal: Res<JournalManager>,
 ) {
     for (_entity, mut worker, worker_transform) in worker_query.iter_mut() {
         // Check if worker just became Ready (arrived at destination)
@@ -81,6 +140,25 @@ pub fn start_mission_on_arrival(
                     let distance = worker_transform.translation.distance(project_transform.translation);
 
                     if distance < 3.0 {
+                        // Wait at the building if the budget can't cover the mission
+                        let estimate = estimator.estimate(mission);
+                        let in_flight = cli_manager.manager.lock().map(|cli| cli.active_processes.len()).unwrap_or(0);
+
+                        let scope = UsageScope::mission(&mission.id, Some(&project.id), &worker.id)
+                            .with_names(&project.name, Some(&worker.name));
+
+                        if let Err(reason) = token_budget.check_launch(estimate.gate_tokens(), config.pricing.estimate_rate(worker.profile.model.as_deref()), in_flight, config.tokens.headroom_per_mission, &scope) {
+                            budget_gate.defer(&worker.id, &mission.id);
+                            let minutes = token_budget.time_until_reset().num_minutes().max(0);
+                            println!("⏳ Deferred mission '{}' for worker '{}': {reason} - resumes at budget reset in {minutes}m",
+                                     mission.title, worker.name);
+                            journal.record(JournalEntryType::Deferral,
+                                           &format!("Deferred mission '{}' with worker '{}' waiting at the building: {reason}",
+                                                    mission.title, worker.name),
+                                           Some(&project.id), Some(&mission.id));
+                            break;
+                        }
+
                         // Start the mission!
                         println!("🎬 Starting mission: {}", mission.title);
 
@@ -96,6 +174,21 @@ pub fn start_mission_on_arrival(
                         // Mark as started
                         let _ = MissionWriter::mark_mission_started(&mission_file, &worker.name);
 
+                        // The Library lends past learnings to the mission
+                        if facilities.library.is_some() && config.knowledge.injection_enabled {
+                            let query = format!("{} {} {}", project.name, mission.title, mission.description);
+                            match knowledge_manager.find_relevant(&query, 5)
+                                .and_then(|entries| MissionWriter::append_knowledge(&mission_file, &entries, config.knowledge.max_injection_tokens))
+                            {
+                                Ok(0) => {}
+                                Ok(count) => println!("📚 Injected {count} knowledge entries from the Library"),
+                                Err(e) => eprintln!("Failed to inject knowledge: {e}"),
+                            }
+                        }
+
+                        println!("   {} ({}, {}): \"{}\"", worker.name, worker.profile.model_name(),
+                                 worker.profile.personality.as_str(), worker.profile.personality.greeting());
+
                         // Spawn Claude CLI process
                         let mut cli_lock = cli_manager.manager.lock().unwrap();
                         match cli_lock.spawn_for_mission(
@@ -103,10 +196,22 @@ pub fn start_mission_on_arrival(
                             mission.id.clone(),
                             &project.path,
                             &mission_file,
+                            &worker.profile,
                         ) {
                             Ok(process_id) => {
                                 println!("✅ Claude CLI spawned (process: {process_id})");
 
+                                // Earlier runs that failed or were cancelled make this a retry
+                                let earlier_attempts = mission_manager.load_project_attempts(&project.id)
+                                    .map(|attempts| attempts.iter().filter(|a| a.mission_id == mission.id).count())
+                                    .unwrap_or(0);
+                                if earlier_attempts > 0 {
+                                    journal.record(JournalEntryType::Retry,
+                                                   &format!("Relaunched mission '{}' with worker '{}' (attempt {})",
+                                                            mission.title, worker.name, earlier_attempts + 1),
+                                                   Some(&project.id), Some(&mission.id));
+                                }
+
                                 // Update worker state
                                 worker.state = WorkerState::Working {
                                     mission_id: mission.id.clone(),
@@ -125,10 +230,14 @@ pub fn start_mission_on_arrival(
                                     crate::game::project::MissionStatus::InProgress,
                                     None,
                                     0,
+                                    0.0,
                                 );
                             }
                             Err(e) => {
                                 eprintln!("❌ Failed to spawn Claude CLI: {e}");
+                                journal.record(JournalEntryType::Failure,
+                                               &format!("Could not launch mission '{}' for worker '{}': {e}", mission.title, worker.name),
+                                               Some(&project.id), Some(&mission.id));
 
                                 // Reset worker to idle
                                 worker.state = WorkerState::Idle;
@@ -145,12 +254,19 @@ pub fn start_mission_on_arrival(
 }
 
 /// System to check for completed CLI processes
+#[allow(clippy::too_many_arguments)]
 pub fn check_cli_completions(
     mut worker_query: Query<&mut Worker>,
     mission_manager: Res<MissionManager>,
     worker_manager: Res<WorkerManager>,
     project_manager: Res<ProjectManager>,
     cli_manager: Res<CliManagerResource>,
+    knowledge_manager: Res<KnowledgeManager>,
+    reflection_queue: Res<ReflectionQueue>,
+    mut estimator: ResMut<MissionEstimator>,
+    mut token_budget: ResMut<TokenBudget>,
+    journal: Res<JournalManager>,
+    config: Res<AppConfig>,
 ) {
     let completions = {
         let mut cli_lock = cli_manager.manager.lock().unwrap();
@@ -166,9 +282,84 @@ pub fn check_cli_completions(
         let tokens = completion.extract_tokens();
         let summary = completion.extract_summary();
 
+        // Price the run at the worker's model rates
+        let worker = worker_query.iter().find(|w| w.id == completion.worker_id);
+        let worker_name = worker.map(|w| w.name.clone()).unwrap_or_else(|| completion.worker_id.clone());
+        let model = worker.and_then(|w| w.profile.model.clone());
+        let usage = UsageBreakdown::parse(completion.output.as_deref().unwrap_or_default(), tokens);
+        let cost_usd = usage.cost_usd(&config.pricing.rates(model.as_deref()));
+
         println!("   Tokens used: {tokens}");
+        println!("   Cost: ${cost_usd:.4}");
         println!("   Summary: {summary}");
 
+        let mission = mission_manager.load_mission(&completion.mission_id).ok().flatten();
+
+        // Charge what wasn't already streamed into the budget while the mission ran,
+        // correcting the streamed estimate to the real cost
+        let uncharged = tokens.saturating_sub(completion.charged_tokens);
+        let uncharged_cost = cost_usd - completion.charged_tokens as f64 * config.pricing.estimate_rate(model.as_deref());
+        if uncharged > 0 || uncharged_cost != 0.0 {
+            let project_id = mission.as_ref().map(|m| m.project_id.as_str());
+            let scope = UsageScope::mission(&completion.mission_id, project_id, &completion.worker_id);
+            token_budget.add_usage(uncharged as u64, uncharged_cost, "mission", &scope);
+        }
+
+        // Commit leftover changes on the agent's behalf when enabled, leaving
+        // alone whatever was uncommitted before the mission started
+        let repo = Path::new(&completion.project_path);
+        let skip = &completion.dirty_before;
+        let mut auto_commit = None;
+        let has_changes = git::dirty_paths(repo).iter().any(|path| !skip.contains(path));
+        if completion.success && config.git.auto_commit && has_changes {
+            if completion.shared_repo {
+                println!("   ⚠️ Skipped auto-commit: other missions worked in this repo at the same time");
+            } else if let Some(mission) = &mission {
+                let message = git::conventional_commit_message(mission.mission_number, &mission.title, &worker_name);
+                match git::auto_commit(repo, &message, skip) {
+                    Ok(commit) => auto_commit = Some(commit),
+                    Err(e) => eprintln!("   Auto-commit failed: {e}"),
+                }
+            }
+        }
+
+        let mut git_result = git::mission_result(repo, completion.head_before.clone(), skip);
+        git_result.auto_commit = auto_commit;
+        git_result.shared_repo = completion.shared_repo;
+
+        println!("   Git: {}", git_result.summary_line());
+        if !git_result.diff_stat.is_empty() {
+            println!("{}", git_result.diff_stat);
+        }
+
+        let started_at = chrono::Utc::now() - chrono::Duration::seconds(completion.duration_secs as i64);
+        if let Err(e) = mission_manager.record_attempt(&MissionAttempt {
+            mission_id: completion.mission_id.clone(),
+            worker_id: completion.worker_id.clone(),
+            started_at: started_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            success: completion.success,
+            duration_secs: completion.duration_secs,
+            tokens_used: tokens,
+            usage,
+            cost_usd,
+            git: git_result.clone(),
+        }) {
+            eprintln!("   Failed to record mission attempt: {e}");
+        }
+
+        // Later estimates learn from this run
+        if let Some(mission) = &mission {
+            estimator.record(EstimationSample {
+                mission_id: mission.id.clone(),
+                project_id: mission.project_id.clone(),
+                domains: mission.domains.clone(),
+                description_words: mission.description.split_whitespace().count(),
+                success: completion.success,
+                tokens,
+                duration_secs: Some(completion.duration_secs),
+            });
+        }
+
         // Update mission status
         let status = if completion.success {
             crate::game::project::MissionStatus::Completed
@@ -181,49 +372,134 @@ pub fn check_cli_completions(
             status.clone(),
             Some(summary.clone()),
             tokens,
+            cost_usd,
         );
 
+        if let Some(mission) = mission.as_ref().filter(|_| !completion.success) {
+            let summary: String = summary.chars().take(200).collect();
+            journal.record(JournalEntryType::Failure,
+                           &format!("Mission '{}' failed for worker '{}' after {}s and {tokens} tokens: {summary}",
+                                    mission.title, worker_name, completion.duration_secs),
+                           Some(&mission.project_id), Some(&mission.id));
+        }
+
+        if let Some(mission) = mission.as_ref().filter(|_| completion.success) {
+            let mission_file = MissionWriter::mission_file_path(&completion.project_path, mission.mission_number);
+            if mission_file.exists() {
+                if let Err(e) = MissionWriter::mark_mission_completed(&mission_file.to_string_lossy(), &summary, &git_result) {
+                    eprintln!("   Failed to update mission file: {e}");
+                }
+            }
+        }
+
+        let project_name = Path::new(&completion.project_path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
         // Update worker stats
         if completion.success {
-            let _ = worker_manager.increment_worker_stats(&completion.worker_id, tokens as u64);
+            let _ = worker_manager.increment_worker_stats(&completion.worker_id, tokens as u64, cost_usd);
         }
 
+        // Reflect on the mission before going idle when enabled and the budget covers it
+        let in_flight = worker_query.iter().filter(|w| matches!(w.state, WorkerState::Working { .. })).count();
+        let reflection = mission.as_ref()
+            .filter(|_| config.general.reflection_enabled)
+            .filter(|mission| {
+                let scope = UsageScope::mission(&mission.id, Some(&mission.project_id), &completion.worker_id)
+                    .with_names(&project_name, worker.map(|w| w.name.as_str()));
+                match token_budget.check_launch(REFLECTION_TOKEN_ESTIMATE, config.pricing.estimate_rate(model.as_deref()),
+                                                in_flight, config.tokens.headroom_per_mission, &scope) {
+                    Ok(()) => true,
+                    Err(reason) => {
+                        println!("   💭 Skipped reflection: {reason}");
+                        false
+                    }
+                }
+            })
+            .and_then(|mission| completion.output.as_ref().map(|output| ReflectionRequest {
+                worker_id: completion.worker_id.clone(),
+                mission_id: mission.id.clone(),
+                mission_title: mission.title.clone(),
+                project_id: mission.project_id.clone(),
+                project_name: project_name.clone(),
+                project_path: completion.project_path.clone(),
+                model: model.clone(),
+                success: completion.success,
+                tokens_used: tokens,
+                duration_secs: completion.duration_secs,
+                transcript: output.clone(),
+            }));
+
+        // Without a reflection to judge what is worth keeping, a successful
+        // mission's summary goes to the knowledge base as it is
+        if let Some(mission) = mission.as_ref().filter(|_| reflection.is_none() && completion.success) {
+            if let Err(e) = knowledge_manager.record_entry(&KnowledgeEntry {
+                project_name: project_name.clone(),
+                mission_id: mission.id.clone(),
+                task_description: mission.title.clone(),
+                solution_applied: summary.clone(),
+                reasoning: String::new(),
+                tokens_used: tokens,
+                duration_secs: completion.duration_secs,
+                worker_id: completion.worker_id.clone(),
+                success: true,
+            }) {
+                eprintln!("   Failed to record knowledge: {e}");
+            }
+        }
+
+        let next_state = if reflection.is_some() { WorkerState::Reflecting } else { WorkerState::Idle };
+
         // Update worker state
         for mut worker in worker_query.iter_mut() {
             if worker.id == completion.worker_id {
-                worker.state = WorkerState::Idle;
+                worker.state = next_state.clone();
                 worker.current_task_id = None;
                 worker.total_tasks_completed += 1;
                 worker.total_tokens_used += tokens as u64;
+                worker.total_cost_usd += cost_usd;
+
+                if let Some(limit) = worker.profile.max_tokens_per_mission.filter(|limit| tokens > *limit) {
+                    println!("   ⚠️ Worker '{}' used {tokens} tokens, over its {limit} token limit", worker.name);
+                }
+
+                // Learn from the attempt in the mission's domains
+                if let Some(mission) = &mission {
+                    let outcome = specialty::attempt_outcome(completion.success, completion.duration_secs, tokens);
+                    specialty::record_outcome(&mut worker.specialty_scores, &mission.domains, outcome);
+
+                    if let Err(e) = worker_manager.save_specialty_scores(&worker.id, &worker.specialty_scores) {
+                        eprintln!("   Failed to save specialties: {e}");
+                    }
+                }
 
                 let _ = worker_manager.update_worker_state(
                     &worker.id,
-                    &WorkerState::Idle,
+                    &next_state,
                     None,
                 );
 
-                println!("   Worker '{}' now idle (total tasks: {})", worker.name, worker.total_tasks_completed);
+                println!("   Worker '{}' now {} (total tasks: {})", worker.name, next_state.as_str(), worker.total_tasks_completed);
             }
         }
 
+        if let Some(request) = reflection {
+            reflection_queue.start(request);
+        }
+
         // Update project completion count
         if completion.success {
-            // Find project for this mission - load all missions to find project_id
-            if let Ok(all_missions) = mission_manager.load_missions("") {
-                if let Some(mission) = all_missions.iter().find(|m| m.id == completion.mission_id) {
-                    // Count completed missions for this project
-                    let completed_count = all_missions.iter()
-                        .filter(|m| m.project_id == mission.project_id
-                                && m.status == crate::game::project::MissionStatus::Completed)
+            if let Some(project_id) = mission.as_ref().map(|m| m.project_id.as_str()) {
+                if let Ok(project_missions) = mission_manager.load_missions(project_id) {
+                    let completed_count = project_missions.iter()
+                        .filter(|m| m.status == crate::game::project::MissionStatus::Completed)
                         .count() as u32;
 
-                    let _ = project_manager.update_mission_count(&mission.project_id, completed_count);
-
-                    let total_project_missions = all_missions.iter()
-                        .filter(|m| m.project_id == mission.project_id)
-                        .count();
+                    let _ = project_manager.update_mission_count(project_id, completed_count);
 
-                    println!("   Project progress: {completed_count}/{total_project_missions} missions complete");
+                    println!("   Project progress: {completed_count}/{} missions complete", project_missions.len());
                 }
             }
         }
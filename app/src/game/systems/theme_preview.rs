@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use crate::game::components::StagedBuilding;
+use crate::game::entities::building_theme::BuildingTheme;
+use crate::game::project::Project;
+use crate::game::resources::ProjectManager;
+use crate::game::systems::project_lifecycle::ProjectLifecycleQueue;
+use crate::game::systems::selection::SelectedProject;
+
+/// Theme being previewed on a building, with the theme to go back to
+#[derive(Resource, Default)]
+pub struct ThemePreview(pub Option<(Entity, BuildingTheme)>);
+
+/// System for theme keys: T previews the next theme on the selected building,
+/// Enter keeps it, Backspace (or selecting another building) reverts
+pub fn preview_building_theme(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    selected: Res<SelectedProject>,
+    lifecycle: Res<ProjectLifecycleQueue>,
+    mut building_query: Query<(&mut Project, &mut StagedBuilding)>,
+    project_manager: Res<ProjectManager>,
+    mut preview: ResMut<ThemePreview>,
+) {
+    // Selection moved away from the previewed building: put it back
+    if let Some((entity, original)) = preview.0 {
+        if selected.0 != Some(entity) {
+            if let Ok((_, mut building)) = building_query.get_mut(entity) {
+                building.theme = original;
+            }
+            preview.0 = None;
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyT) {
+        let Some(entity) = selected.0 else {
+            println!("⚠️ Select a project building first (left click)");
+            return;
+        };
+        let Ok((project, mut building)) = building_query.get_mut(entity) else { return };
+
+        let original = preview.0.map(|(_, original)| original).unwrap_or(building.theme);
+        building.theme = building.theme.next();
+        preview.0 = Some((entity, original));
+
+        println!("🎨 Previewing '{}' theme for '{}' - Enter to keep, Backspace to revert",
+                 building.theme.as_str(), project.name);
+        return;
+    }
+
+    // Enter and Backspace belong to a pending archive/delete first
+    if lifecycle.pending.is_some() {
+        return;
+    }
+
+    let Some((entity, original)) = preview.0 else { return };
+    let Ok((mut project, mut building)) = building_query.get_mut(entity) else { return };
+
+    if keyboard.just_pressed(KeyCode::Enter) {
+        preview.0 = None;
+
+        match project_manager.update_building_theme(&project.id, building.theme.as_str()) {
+            Ok(()) => {
+                project.building_theme = building.theme.as_str().to_string();
+                println!("🎨 '{}' is now a {} building", project.name, project.building_theme);
+            }
+            Err(e) => {
+                eprintln!("Failed to save theme for '{}': {e}", project.name);
+                building.theme = original;
+            }
+        }
+    } else if keyboard.just_pressed(KeyCode::Backspace) {
+        preview.0 = None;
+        building.theme = original;
+        println!("↩️  Kept '{}' theme for '{}'", original.as_str(), project.name);
+    }
+}
@@ -1,5 +1,110 @@
 use bevy::prelude::*;
-use crate::game::resources::TokenBudget;
+use std::collections::HashSet;
+use crate::game::resources::{CliManagerResource, TokenBudget, UsageScope};
+use crate::game::systems::MissionManager;
+use crate::game::worker::{Worker, WorkerState};
+use crate::core::config::AppConfig;
+use crate::core::database;
+use crate::Database;
+
+/// A mission launch put off until the budget resets
+#[derive(Debug, Clone)]
+pub struct DeferredLaunch {
+    pub worker_id: String,
+    pub mission_id: String,
+}
+
+/// Resource tracking missions held back by the token budget
+#[derive(Resource, Default)]
+pub struct BudgetGate {
+    /// Workers waiting at their building for the next budget period
+    pub deferred: Vec<DeferredLaunch>,
+    /// Missions already reported as skipped since the last reset, to keep the log quiet
+    pub announced: HashSet<String>,
+    seen_resets: Option<u32>,
+}
+
+impl BudgetGate {
+    /// Hold a worker's launch until the next reset
+    pub fn defer(&mut self, worker_id: &str, mission_id: &str) {
+        if !self.deferred.iter().any(|d| d.worker_id == worker_id) {
+            self.deferred.push(DeferredLaunch {
+                worker_id: worker_id.to_string(),
+                mission_id: mission_id.to_string(),
+            });
+        }
+    }
+
+    /// True the first time a mission is reported this period
+    pub fn announce(&mut self, mission_id: &str) -> bool {
+        self.announced.insert(mission_id.to_string())
+    }
+}
+
+/// System to charge tokens reported by running missions as they stream in,
+/// priced at the worker's model output rate until the run reports its real cost
+pub fn charge_streamed_usage(
+    cli_manager: Res<CliManagerResource>,
+    mission_manager: Res<MissionManager>,
+    worker_query: Query<&Worker>,
+    config: Res<AppConfig>,
+    mut token_budget: ResMut<TokenBudget>,
+    time: Res<Time>,
+    mut last_check: Local<f32>,
+) {
+    *last_check += time.delta_seconds();
+
+    // Check every second
+    if *last_check < 1.0 {
+        return;
+    }
+    *last_check = 0.0;
+
+    let usage = match cli_manager.manager.lock() {
+        Ok(mut cli) => cli.take_streamed_usage(),
+        Err(_) => return,
+    };
+
+    for (mission_id, worker_id, tokens) in usage {
+        let project_id = mission_manager.load_mission(&mission_id).ok().flatten().map(|m| m.project_id);
+        let scope = UsageScope::mission(&mission_id, project_id.as_deref(), &worker_id);
+        let model = worker_query.iter().find(|w| w.id == worker_id).and_then(|w| w.profile.model.as_deref());
+        let cost_usd = tokens as f64 * config.pricing.estimate_rate(model);
+        token_budget.add_usage(tokens as u64, cost_usd, "mission", &scope);
+    }
+}
+
+/// System to write budget charges to the ledger and remember the current period
+pub fn persist_token_budget(
+    db: Res<Database>,
+    mut token_budget: ResMut<TokenBudget>,
+    mut saved_period: Local<Option<chrono::DateTime<chrono::Utc>>>,
+) {
+    let hourly_start = token_budget.hourly().start;
+    let period_changed = *saved_period != Some(hourly_start);
+    if token_budget.unsaved.is_empty() && !period_changed {
+        return;
+    }
+
+    let Ok(conn) = db.0.lock() else { return };
+    let period_start = hourly_start.to_rfc3339();
+
+    if period_changed {
+        match serde_json::to_string(&hourly_start) {
+            Ok(json) => match database::save_state(&conn, "token_budget_period", &json) {
+                Ok(()) => *saved_period = Some(hourly_start),
+                Err(e) => eprintln!("❌ Failed to save token budget period: {e}"),
+            },
+            Err(e) => eprintln!("❌ Failed to serialize token budget period: {e}"),
+        }
+    }
+
+    for entry in token_budget.unsaved.drain(..) {
+        if let Err(e) = database::record_token_usage(&conn, &period_start, &entry) {
+            eprintln!("❌ Failed to record token usage: {e}");
+        }
+    }
+}
 
 /// System to check for budget reset
 pub fn check_budget_reset(
@@ -15,8 +120,43 @@ pub fn check_budget_reset(
     }
     *last_check = 0.0;
 
-    if token_budget.should_reset() {
-        token_budget.reset_period();
+    let reset = token_budget.roll_windows();
+    if !reset.is_empty() {
+        let labels: Vec<&str> = reset.iter().map(|kind| kind.label()).collect();
+        println!("🔄 Token budget reset! New {} period started.", labels.join(" and "));
+    }
+}
+
+/// System to resume deferred launches once a budget window resets
+pub fn resume_deferred_missions(
+    token_budget: Res<TokenBudget>,
+    mut gate: ResMut<BudgetGate>,
+    mut worker_query: Query<&mut Worker>,
+) {
+    if gate.seen_resets == Some(token_budget.resets) {
+        return;
+    }
+    gate.seen_resets = Some(token_budget.resets);
+    gate.announced.clear();
+
+    let deferred = std::mem::take(&mut gate.deferred);
+    if deferred.is_empty() {
+        return;
+    }
+
+    let mut resumed = 0;
+    for launch in deferred {
+        let Some(mut worker) = worker_query.iter_mut().find(|w| w.id == launch.worker_id) else { continue };
+
+        // Still waiting at the building for the same mission: retrigger the launch
+        if worker.state == WorkerState::Ready && worker.current_task_id.as_deref() == Some(launch.mission_id.as_str()) {
+            worker.set_changed();
+            resumed += 1;
+        }
+    }
+
+    if resumed > 0 {
+        println!("▶️  Token budget reset - resuming {resumed} deferred missions");
     }
 }
 
@@ -28,8 +168,9 @@ pub fn display_budget_warnings(
     mut warning_shown: Local<bool>,
 ) {
     if token_budget.is_low() && !*warning_shown {
-        println!("⚠️  TOKEN BUDGET LOW: {:.1}% remaining ({} tokens left)",
+        println!("⚠️  TOKEN BUDGET LOW: {:.1}% of the {} budget remaining ({} tokens left)",
                  token_budget.percentage_remaining(),
+                 token_budget.tightest().kind.label(),
                  token_budget.remaining());
 
         let time_until_reset = token_budget.time_until_reset();
@@ -80,10 +221,12 @@ pub fn display_budget_status(
 
     let burn_rate = token_budget.estimated_burn_rate_per_hour();
 
-    println!("💰 Token Budget: {}/{} used ({:.1}%)",
-             token_budget.current_period_used,
-             token_budget.hourly_limit,
-             token_budget.percentage_used());
+    for window in &token_budget.windows {
+        println!("💰 Token Budget ({}): {} used ({:.1}%)",
+                 window.kind.label(),
+                 window.usage_label(),
+                 100.0 - window.fraction_remaining() * 100.0);
+    }
 
     println!("   Burn rate: {burn_rate:.0} tokens/hour");
 
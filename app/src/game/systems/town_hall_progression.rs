@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+use crate::game::components::StagedBuilding;
+use crate::game::entities::town_hall::TownHall;
+use crate::game::resources::{ProjectManager, WorkerManager};
+use crate::game::systems::worker_spawner::TownHall as TownHallBuilding;
+use crate::game::systems::zac_journal::{JournalEntryType, JournalManager};
+use crate::Database;
+
+/// Seconds between each stage step of a transition
+const STAGE_STEP_SECS: f32 = 0.5;
+
+/// A building climbing one stage at a time towards `target`
+#[derive(Component)]
+pub struct StageTransition {
+    pub target: u8,
+    pub timer: Timer,
+}
+
+impl StageTransition {
+    pub fn to(target: u8) -> Self {
+        Self {
+            target,
+            timer: Timer::from_seconds(STAGE_STEP_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+/// System for U key: upgrade the Town Hall once the next level's requirements are met
+#[allow(clippy::too_many_arguments)]
+pub fn upgrade_town_hall_keypress(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut town_hall: ResMut<TownHall>,
+    project_manager: Res<ProjectManager>,
+    worker_manager: Res<WorkerManager>,
+    journal: Res<JournalManager>,
+    db: Res<Database>,
+    building_query: Query<Entity, With<TownHallBuilding>>,
+) {
+    if !keyboard.just_pressed(KeyCode::KeyU) {
+        return;
+    }
+
+    let Some(req) = town_hall.upgrade_requirements() else {
+        println!("🏛️ Town Hall is already at the top level ({})", town_hall.level);
+        return;
+    };
+
+    let completed = match project_manager.count_completed_projects() {
+        Ok(count) => count,
+        Err(e) => {
+            eprintln!("❌ Failed to count completed projects: {e}");
+            return;
+        }
+    };
+    let workers = match worker_manager.count_workers() {
+        Ok(count) => count,
+        Err(e) => {
+            eprintln!("❌ Failed to count workers: {e}");
+            return;
+        }
+    };
+
+    if !req.is_met(completed, workers) {
+        println!("🔒 Town Hall level {} needs {} completed projects ({completed}) and {} workers ({workers})",
+                 town_hall.level + 1, req.projects, req.workers);
+        return;
+    }
+
+    town_hall.level += 1;
+
+    match serde_json::to_string(&*town_hall) {
+        Ok(json) => {
+            if let Ok(conn) = db.0.lock() {
+                if let Err(e) = crate::core::database::save_state(&conn, "town_hall", &json) {
+                    eprintln!("❌ Failed to save Town Hall level: {e}");
+                }
+            }
+        }
+        Err(e) => eprintln!("❌ Failed to serialize Town Hall: {e}"),
+    }
+
+    for entity in building_query.iter() {
+        commands.entity(entity).insert(StageTransition::to(town_hall.stage()));
+    }
+
+    println!("🏛️ Town Hall upgraded to level {} - up to {} workers", town_hall.level, town_hall.max_workers());
+    journal.record(JournalEntryType::StageUpgrade,
+                   &format!("Town Hall upgraded to level {} (stage {}) - up to {} workers",
+                            town_hall.level, town_hall.stage(), town_hall.max_workers()),
+                   None, None);
+}
+
+/// System for stage transitions: step each building one stage at a time to its target
+pub fn play_stage_transitions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut StagedBuilding, &mut StageTransition)>,
+) {
+    for (entity, mut building, mut transition) in query.iter_mut() {
+        if !transition.timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        let current = building.current_stage.as_u8();
+        if current < transition.target {
+            building.set_stage(current + 1);
+        } else if current > transition.target {
+            building.set_stage(current - 1);
+        }
+
+        if building.current_stage.as_u8() == transition.target {
+            commands.entity(entity).remove::<StageTransition>();
+        }
+    }
+}
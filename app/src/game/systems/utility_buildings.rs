@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+use crate::game::components::{BuildingType, StagedBuilding, UtilityBuilding};
+use crate::game::entities::town_hall::TownHall;
+use crate::game::resources::{BuildingManager, ProjectManager, TownFacilities, UtilityBuildingRecord};
+use crate::game::systems::building_placement::{find_free_plot, PlotOccupantQuery};
+use crate::game::systems::building_renderer::spawn_building_meshes;
+use crate::game::systems::knowledge_manager::KnowledgeManager;
+use crate::game::worker::Worker;
+
+/// Startup system to raise the utility buildings stored in SQLite
+pub fn spawn_utility_buildings(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    building_manager: Res<BuildingManager>,
+) {
+    let buildings = match building_manager.load_buildings() {
+        Ok(b) => b,
+        Err(e) => {
+            eprintln!("Failed to load buildings: {e}");
+            return;
+        }
+    };
+
+    for record in &buildings {
+        spawn_utility_entity(&mut commands, &mut meshes, &mut materials, record);
+    }
+}
+
+/// Spawn a utility building entity with its stage mesh
+pub fn spawn_utility_entity(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    record: &UtilityBuildingRecord,
+) {
+    let building = StagedBuilding::new(record.building_type, record.stage);
+
+    let entity = commands
+        .spawn((
+            UtilityBuilding {
+                id: record.id.clone(),
+                building_type: record.building_type,
+            },
+            SpatialBundle::from_transform(Transform::from_translation(record.position)),
+            Name::new(record.building_type.display_name().to_string()),
+        ))
+        .id();
+
+    spawn_building_meshes(commands, meshes, materials, entity, &building);
+    commands.entity(entity).insert(building);
+}
+
+/// System for build keys: 1 Library, 2 Workshop, 3 Barracks
+#[allow(clippy::too_many_arguments)]
+pub fn construct_utility_building_keys(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    placed_query: PlotOccupantQuery,
+    utility_query: Query<&UtilityBuilding>,
+    building_manager: Res<BuildingManager>,
+    town_hall: Res<TownHall>,
+) {
+    let build_keys = [KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3];
+
+    let Some(building_type) = build_keys.iter()
+        .zip(BuildingType::UTILITY)
+        .find(|(key, _)| keyboard.just_pressed(**key))
+        .map(|(_, building_type)| building_type)
+    else {
+        return;
+    };
+
+    let required_level = building_type.required_town_hall_level();
+    if town_hall.level < required_level {
+        println!("🔒 The {} needs Town Hall level {required_level} (now {})",
+                 building_type.display_name(), town_hall.level);
+        return;
+    }
+
+    if utility_query.iter().any(|b| b.building_type == building_type) {
+        println!("⚠️ The town already has a {}", building_type.display_name());
+        return;
+    }
+
+    let occupied: Vec<Vec3> = placed_query.iter().map(|t| t.translation).collect();
+    let Some(position) = find_free_plot(&occupied) else {
+        println!("⚠️ No free plot left for a {}", building_type.display_name());
+        return;
+    };
+
+    match building_manager.create_building(building_type, position) {
+        Ok(id) => {
+            let record = UtilityBuildingRecord { id, building_type, stage: 1, position };
+            spawn_utility_entity(&mut commands, &mut meshes, &mut materials, &record);
+            println!("🏛️  Built a {} at {position:?}", building_type.display_name());
+        }
+        Err(e) => eprintln!("Failed to build {}: {e}", building_type.display_name()),
+    }
+}
+
+/// Knowledge entries per Library stage
+const ENTRIES_PER_LIBRARY_STAGE: usize = 5;
+/// Health checks per Workshop stage
+const CHECKS_PER_WORKSHOP_STAGE: usize = 10;
+/// Completed worker tasks per Barracks stage
+const TASKS_PER_BARRACKS_STAGE: usize = 5;
+
+/// System to grow utility buildings with their use and publish their stages
+#[allow(clippy::too_many_arguments)]
+pub fn progress_utility_buildings(
+    mut building_query: Query<(&UtilityBuilding, &mut StagedBuilding)>,
+    worker_query: Query<&Worker>,
+    building_manager: Res<BuildingManager>,
+    knowledge_manager: Res<KnowledgeManager>,
+    project_manager: Res<ProjectManager>,
+    mut facilities: ResMut<TownFacilities>,
+    time: Res<Time>,
+    mut last_check: Local<Option<f32>>,
+) {
+    let elapsed = last_check.get_or_insert(f32::MAX);
+    *elapsed += time.delta_seconds();
+
+    // Re-count every 5 seconds (and on the first frame)
+    if *elapsed >= 5.0 {
+        *elapsed = 0.0;
+
+        for (utility, mut building) in building_query.iter_mut() {
+            let uses = match utility.building_type {
+                BuildingType::Library => knowledge_manager.count_entries()
+                    .map(|n| n / ENTRIES_PER_LIBRARY_STAGE),
+                BuildingType::Workshop => project_manager.count_health_checks()
+                    .map(|n| n / CHECKS_PER_WORKSHOP_STAGE),
+                BuildingType::Barracks => Ok(worker_query.iter()
+                    .map(|w| w.total_tasks_completed as usize)
+                    .sum::<usize>() / TASKS_PER_BARRACKS_STAGE),
+                BuildingType::TownHall => continue,
+            };
+
+            let target = match uses {
+                Ok(n) => (1 + n).min(10) as u8,
+                Err(e) => {
+                    eprintln!("Failed to measure {}: {e}", utility.building_type.display_name());
+                    continue;
+                }
+            };
+
+            // Buildings never shrink
+            if target > building.current_stage.as_u8() {
+                building.set_stage(target);
+                let _ = building_manager.update_stage(&utility.id, target);
+                println!("🏛️  {} upgraded to stage {target}!", utility.building_type.display_name());
+            }
+        }
+    }
+
+    let stage_of = |building_type: BuildingType| {
+        building_query.iter()
+            .find(|(u, _)| u.building_type == building_type)
+            .map(|(_, b)| b.current_stage.as_u8())
+    };
+
+    let library = stage_of(BuildingType::Library);
+    let workshop = stage_of(BuildingType::Workshop);
+    let barracks = stage_of(BuildingType::Barracks);
+
+    if facilities.library != library || facilities.workshop != workshop || facilities.barracks != barracks {
+        *facilities = TownFacilities { library, workshop, barracks };
+    }
+}
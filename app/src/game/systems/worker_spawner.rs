@@ -1,7 +1,9 @@
 use bevy::prelude::*;
-use crate::game::worker::{Worker, WorkerVisual, NameGenerator};
-use crate::game::resources::WorkerManager;
+use crate::game::worker::{Worker, WorkerState, WorkerVisual, NameGenerator};
+use crate::game::resources::{ProjectManager, WorkerManager};
 use crate::game::systems::leisure_zone::LeisureZone;
+use crate::game::systems::{MissionManager, MovementTarget};
+use crate::game::systems::zac_journal::{JournalEntryType, JournalManager};
 
 /// Component for Town Hall
 #[derive(Component)]
@@ -9,78 +11,128 @@ pub struct TownHall {
     pub worker_production_queue: Vec<WorkerProductionOrder>,
 }
 
-/// Worker production order
+/// Worker production order, persisted in `production_orders`
 #[derive(Debug, Clone)]
 pub struct WorkerProductionOrder {
-    pub started_at: f64,
-    pub duration: f32,  // seconds
+    pub id: String,
     pub worker_name: String,
+    pub queued_at: chrono::DateTime<chrono::Utc>,
+    /// Set when the order reaches the front of the queue
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub duration: f32,  // seconds
+    /// Tokens charged to the budget when queued, refunded on cancel
+    pub token_cost: u64,
 }
 
-impl TownHall {
-    pub fn new() -> Self {
-        Self {
-            worker_production_queue: Vec::new(),
+impl WorkerProductionOrder {
+    /// Share of the training done, from 0.0 to 1.0
+    pub fn progress(&self, now: chrono::DateTime<chrono::Utc>) -> f32 {
+        let Some(started_at) = self.started_at else { return 0.0 };
+        if self.duration <= 0.0 {
+            return 1.0;
         }
+
+        let elapsed = (now - started_at).num_milliseconds() as f32 / 1000.0;
+        (elapsed / self.duration).clamp(0.0, 1.0)
     }
+}
+
+impl TownHall {
+    /// Town Hall with orders restored from the database
+    pub fn with_queue(worker_production_queue: Vec<WorkerProductionOrder>) -> Self {
+        Self { worker_production_queue }
+    }
+
+    /// Queue a worker whose name differs from `taken` and the other queued workers
+    pub fn start_worker_production(&mut self, taken: &[String], duration: f32, token_cost: u64) -> &WorkerProductionOrder {
+        let mut taken = taken.to_vec();
+        taken.extend(self.worker_production_queue.iter().map(|o| o.worker_name.clone()));
 
-    pub fn start_worker_production(&mut self, time: f64) {
-        let name = NameGenerator::random_name();
         self.worker_production_queue.push(WorkerProductionOrder {
-            started_at: time,
-            duration: 5.0,  // 5 seconds like WC3
-            worker_name: name,
+            id: uuid::Uuid::new_v4().to_string(),
+            worker_name: NameGenerator::unique_name(&taken),
+            queued_at: chrono::Utc::now(),
+            started_at: None,
+            duration,
+            token_cost,
         });
+
+        self.worker_production_queue.last().expect("order was just queued")
+    }
+
+    /// Remove an order, returning it so its cost can be refunded
+    pub fn cancel_order(&mut self, order_id: &str) -> Option<WorkerProductionOrder> {
+        let index = self.worker_production_queue.iter().position(|o| o.id == order_id)?;
+        Some(self.worker_production_queue.remove(index))
+    }
+
+    /// Move an order one place towards the front; an overtaken order restarts later
+    pub fn move_order_up(&mut self, order_id: &str) -> bool {
+        let Some(index) = self.worker_production_queue.iter().position(|o| o.id == order_id) else { return false };
+        if index == 0 {
+            return false;
+        }
+
+        self.worker_production_queue.swap(index - 1, index);
+        if index == 1 {
+            // Training only happens at the front, so the overtaken order starts over
+            self.worker_production_queue[1].started_at = None;
+        }
+
+        true
     }
 }
 
-/// System to process worker production queue
+/// System to process worker production queue: the front order trains, then the next one starts
 pub fn process_worker_production(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut town_hall_query: Query<(&mut TownHall, &Transform)>,
     worker_manager: Res<WorkerManager>,
-    time: Res<Time>,
 ) {
-    let current_time = time.elapsed_seconds_f64();
+    let now = chrono::Utc::now();
 
     for (mut town_hall, th_transform) in town_hall_query.iter_mut() {
-        let mut completed_orders = Vec::new();
-
-        for (index, order) in town_hall.worker_production_queue.iter().enumerate() {
-            let elapsed = current_time - order.started_at;
+        let Some(front) = town_hall.worker_production_queue.first() else { continue };
 
-            if elapsed >= order.duration as f64 {
-                completed_orders.push(index);
+        if front.started_at.is_none() {
+            town_hall.worker_production_queue[0].started_at = Some(now);
+            if let Err(e) = worker_manager.save_production_queue(&town_hall.worker_production_queue) {
+                eprintln!("Failed to save production queue: {e}");
             }
+            continue;
         }
 
-        // Process completed orders
-        for index in completed_orders.iter().rev() {
-            let order = town_hall.worker_production_queue.remove(*index);
-
-            // Create worker in database
-            let worker = Worker::new(order.worker_name.clone());
-            let color_tuple = (worker.color.to_srgba().red, worker.color.to_srgba().green, worker.color.to_srgba().blue);
-
-            match worker_manager.create_worker(worker.name.clone(), color_tuple) {
-                Ok(worker_id) => {
-                    // Spawn worker entity
-                    let spawn_pos = th_transform.translation + Vec3::new(2.0, 0.0, 2.0);
-                    spawn_worker_entity(
-                        &mut commands,
-                        &mut meshes,
-                        &mut materials,
-                        worker,
-                        spawn_pos,
-                    );
-
-                    println!("✅ Worker '{}' completed training! (ID: {})", order.worker_name, worker_id);
-                }
-                Err(e) => {
-                    eprintln!("Failed to create worker: {e}");
-                }
+        if front.progress(now) < 1.0 {
+            continue;
+        }
+
+        let order = town_hall.worker_production_queue.remove(0);
+        if let Err(e) = worker_manager.save_production_queue(&town_hall.worker_production_queue) {
+            eprintln!("Failed to save production queue: {e}");
+        }
+
+        // Create worker in database
+        let worker = Worker::new(order.worker_name.clone());
+        let color_tuple = (worker.color.to_srgba().red, worker.color.to_srgba().green, worker.color.to_srgba().blue);
+
+        match worker_manager.create_worker(worker.name.clone(), color_tuple) {
+            Ok(worker_id) => {
+                // Spawn worker entity
+                let spawn_pos = th_transform.translation + Vec3::new(2.0, 0.0, 2.0);
+                spawn_worker_entity(
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                    worker,
+                    spawn_pos,
+                );
+
+                println!("✅ Worker '{}' completed training! (ID: {})", order.worker_name, worker_id);
+            }
+            Err(e) => {
+                eprintln!("Failed to create worker: {e}");
             }
         }
     }
@@ -93,7 +145,7 @@ fn spawn_worker_entity(
     materials: &mut ResMut<Assets<StandardMaterial>>,
     worker: Worker,
     position: Vec3,
-) {
+) -> Entity {
     commands
         .spawn((
             worker.clone(),
@@ -115,15 +167,20 @@ fn spawn_worker_entity(
             ));
 
             // TODO M10: Add name tag text above worker
-        });
+        })
+        .id()
 }
 
 /// System to restore workers from database on startup
+#[allow(clippy::too_many_arguments)]
 pub fn restore_workers(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     worker_manager: Res<WorkerManager>,
+    mission_manager: Res<MissionManager>,
+    project_manager: Res<ProjectManager>,
+    journal: Res<JournalManager>,
     leisure_zone_query: Query<&LeisureZone>,
 ) {
     let workers = match worker_manager.load_workers() {
@@ -142,11 +199,58 @@ pub fn restore_workers(
         }
     };
 
-    for worker in workers {
-        // Spawn at leisure zone
-        let position = crate::game::systems::leisure_zone::random_leisure_position(leisure_zone);
+    // Workers with a mission stand next to its building
+    let mission_site = |mission_id: &str| -> Option<Vec3> {
+        let mission = mission_manager.load_mission(mission_id).ok().flatten()?;
+        let building = project_manager.load_position(&mission.project_id).ok().flatten()?;
+        Some(building + Vec3::new(1.5, 0.0, 1.5))
+    };
+
+    for mut worker in workers {
+        let leisure_position = crate::game::systems::leisure_zone::random_leisure_position(leisure_zone);
+        let task_site = worker.current_task_id.as_deref().and_then(mission_site);
+
+        let (position, destination) = match worker.state.clone() {
+            // Resume the walk, heading for the mission's building if it moved since
+            WorkerState::MovingTo { target } => (leisure_position, Some(task_site.unwrap_or(target))),
+            // The CLI process died with the app: wait at the building, which restarts the mission
+            WorkerState::Working { mission_id, .. } => match mission_site(&mission_id) {
+                Some(site) => {
+                    if let Ok(Some(mission)) = mission_manager.load_mission(&mission_id) {
+                        journal.record(JournalEntryType::Retry,
+                                       &format!("Restarting mission '{}' with worker '{}' - its run was cut off by shutdown",
+                                                mission.title, worker.name),
+                                       Some(&mission.project_id), Some(&mission.id));
+                    }
+
+                    worker.state = WorkerState::Ready;
+                    worker.current_task_id = Some(mission_id);
+                    (site, None)
+                }
+                None => {
+                    worker.state = WorkerState::Idle;
+                    worker.current_task_id = None;
+                    (leisure_position, None)
+                }
+            },
+            WorkerState::Ready => match task_site {
+                Some(site) => (site, None),
+                None => (leisure_position, None),
+            },
+            WorkerState::Crashed { last_mission_id, .. } => {
+                (mission_site(&last_mission_id).unwrap_or(leisure_position), None)
+            }
+            // A reflection interrupted by shutdown is not resumed
+            WorkerState::Reflecting => {
+                worker.state = WorkerState::Idle;
+                (leisure_position, None)
+            }
+            WorkerState::Idle => (leisure_position, None),
+        };
+
+        let _ = worker_manager.update_worker_state(&worker.id, &worker.state, worker.current_task_id.as_deref());
 
-        spawn_worker_entity(
+        let entity = spawn_worker_entity(
             &mut commands,
             &mut meshes,
             &mut materials,
@@ -154,6 +258,10 @@ pub fn restore_workers(
             position,
         );
 
-        println!("Restored worker: {}", worker.name);
+        if let Some(destination) = destination {
+            commands.entity(entity).insert(MovementTarget::new(destination));
+        }
+
+        println!("Restored worker: {} ({})", worker.name, worker.state.as_str());
     }
 }
@@ -0,0 +1,486 @@
+use bevy::prelude::*;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+use crate::agents::claude_api::{self, ClaudeClient, Message, StreamEvent, ToolDefinition};
+use crate::core::config::AppConfig;
+use crate::game::cli::usage::UsageBreakdown;
+use crate::game::project::{MissionStatus, Project};
+use crate::game::resources::{TokenBudget, UsageScope, WorkerManager};
+use crate::game::systems::estimation::MissionEstimator;
+use crate::game::systems::zac_journal::{JournalEntryType, JournalManager};
+use crate::game::systems::{MissionManager, MovementTarget};
+use crate::game::worker::{Worker, WorkerState};
+use crate::ui::UiCommand;
+
+/// A tool call the game world doesn't answer in this time fails
+const TOOL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Oldest messages are forgotten past this many
+const MAX_HISTORY_MESSAGES: usize = 40;
+
+/// Transcript lines kept for the chat panel
+const MAX_TRANSCRIPT_LINES: usize = 200;
+
+const SYSTEM_PROMPT: &str = "You are Zac^, the foreman of a town of Claude Code workers. \
+Each building is a software project, its missions are the tasks workers carry out, \
+and a worker walks to a building to start a mission. \
+Use the tools to look up projects, missions and workers instead of guessing. \
+Only assign missions when the user asks you to. \
+Keep replies short; they are shown in a small in-game chat panel.";
+
+/// Who a transcript line is from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatSpeaker {
+    User,
+    Zac,
+    Tool,
+    System,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChatLine {
+    pub speaker: ChatSpeaker,
+    pub text: String,
+}
+
+/// A tool call waiting for the game world to answer it
+struct ZacToolCall {
+    name: String,
+    input: Value,
+    reply: mpsc::Sender<Result<String, String>>,
+}
+
+/// Progress of the conversation running in the background
+enum ChatUpdate {
+    Stream(StreamEvent),
+    /// The history with the new turn, or why it failed
+    Finished(Result<Vec<Message>, String>),
+}
+
+type ToolQueue = Arc<Mutex<Vec<ZacToolCall>>>;
+
+/// Resource holding the conversation with Zac^
+#[derive(Resource, Default)]
+pub struct ZacChat {
+    /// Panel shown and taking keyboard input
+    pub open: bool,
+    pub input: String,
+    pub transcript: Vec<ChatLine>,
+    /// A reply is on its way
+    pub busy: bool,
+    history: Vec<Message>,
+    updates: Arc<Mutex<Vec<ChatUpdate>>>,
+    tool_calls: ToolQueue,
+}
+
+impl ZacChat {
+    fn push_line(&mut self, speaker: ChatSpeaker, text: impl Into<String>) {
+        self.transcript.push(ChatLine { speaker, text: text.into() });
+        let excess = self.transcript.len().saturating_sub(MAX_TRANSCRIPT_LINES);
+        self.transcript.drain(..excess);
+    }
+
+    /// Send a message to Zac^, or handle a `/key` or `/clear` chat command
+    pub fn send(&mut self, message: &str, config: &AppConfig) {
+        let message = message.trim();
+        if message.is_empty() {
+            return;
+        }
+
+        if let Some(key) = message.strip_prefix("/key ") {
+            match claude_api::store_api_key(key) {
+                Ok(()) => self.push_line(ChatSpeaker::System, "🔑 API key saved to the OS keychain"),
+                Err(e) => self.push_line(ChatSpeaker::System, format!("⚠️ {e}")),
+            }
+            return;
+        }
+
+        if message == "/clear" {
+            // The reply on its way would bring the old history back
+            if self.busy {
+                self.push_line(ChatSpeaker::System, "Zac^ is still answering - clear once the reply is in");
+                return;
+            }
+            self.history.clear();
+            self.transcript.clear();
+            return;
+        }
+
+        if self.busy {
+            self.push_line(ChatSpeaker::System, "Zac^ is still answering - wait for the reply");
+            return;
+        }
+
+        let client = match ClaudeClient::from_config(&config.chat) {
+            Ok(client) => client,
+            Err(e) => {
+                self.push_line(ChatSpeaker::System, format!("⚠️ {e}"));
+                return;
+            }
+        };
+
+        self.push_line(ChatSpeaker::User, message);
+        self.busy = true;
+
+        let mut history = self.history.clone();
+        history.push(Message::user(message));
+
+        let updates = self.updates.clone();
+        let tool_calls = self.tool_calls.clone();
+
+        std::thread::spawn(move || {
+            let push = |update| {
+                if let Ok(mut updates) = updates.lock() {
+                    updates.push(update);
+                }
+            };
+
+            let result = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| format!("Runtime error: {e}"))
+                .and_then(|runtime| runtime.block_on(client.converse(
+                    SYSTEM_PROMPT,
+                    &mut history,
+                    &zac_tools(),
+                    |event| push(ChatUpdate::Stream(event)),
+                    |name, input| call_tool(&tool_calls, name, input),
+                )));
+
+            push(ChatUpdate::Finished(result.map(|()| history)));
+        });
+    }
+}
+
+/// Hand a tool call to the game world and wait for the answer
+fn call_tool(queue: &ToolQueue, name: &str, input: &Value) -> Result<String, String> {
+    let (reply, answer) = mpsc::channel();
+
+    queue.lock()
+        .map_err(|_| "Tool queue unavailable".to_string())?
+        .push(ZacToolCall { name: name.to_string(), input: input.clone(), reply });
+
+    answer.recv_timeout(TOOL_TIMEOUT)
+        .map_err(|_| format!("Tool {name} timed out"))?
+}
+
+/// Tools Zac^ can use on the town
+fn zac_tools() -> Vec<ToolDefinition> {
+    vec![
+        ToolDefinition {
+            name: "list_projects",
+            description: "List the active projects with their id, building stage (1-10) and mission progress.",
+            input_schema: json!({ "type": "object", "properties": {} }),
+        },
+        ToolDefinition {
+            name: "list_missions",
+            description: "List a project's missions with id, status, the worker on it, and whether it can be assigned now.",
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project": { "type": "string", "description": "Project name or id" },
+                    "include_completed": { "type": "boolean", "description": "Also list completed missions" }
+                },
+                "required": ["project"]
+            }),
+        },
+        ToolDefinition {
+            name: "list_workers",
+            description: "List the workers with their id, state, current mission and model.",
+            input_schema: json!({ "type": "object", "properties": {} }),
+        },
+        ToolDefinition {
+            name: "assign_mission",
+            description: "Send an idle worker to start an available mission. The worker walks to the project's building and starts when it arrives.",
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "worker": { "type": "string", "description": "Worker name or id" },
+                    "mission_id": { "type": "string", "description": "Mission id from list_missions" }
+                },
+                "required": ["worker", "mission_id"]
+            }),
+        },
+    ]
+}
+
+/// Forget the oldest turns, always starting at something the user typed
+fn trim_history(history: &mut Vec<Message>) {
+    while history.len() > MAX_HISTORY_MESSAGES || history.first().is_some_and(|m| !m.is_user_text()) {
+        history.remove(0);
+    }
+}
+
+/// System to send chat messages from the UI to Zac^
+pub fn handle_chat_commands(
+    mut ui_commands: EventReader<UiCommand>,
+    mut chat: ResMut<ZacChat>,
+    config: Res<AppConfig>,
+) {
+    for command in ui_commands.read() {
+        match command {
+            UiCommand::ChatWithZac { message } => chat.send(message, &config),
+        }
+    }
+}
+
+/// System to show streamed replies and charge chat usage to the token budget
+pub fn apply_chat_updates(
+    mut chat: ResMut<ZacChat>,
+    mut token_budget: ResMut<TokenBudget>,
+    config: Res<AppConfig>,
+) {
+    let updates: Vec<ChatUpdate> = match chat.updates.lock() {
+        Ok(mut updates) => updates.drain(..).collect(),
+        Err(_) => return,
+    };
+
+    for update in updates {
+        match update {
+            ChatUpdate::Stream(StreamEvent::Text(text)) => {
+                match chat.transcript.last_mut() {
+                    Some(line) if line.speaker == ChatSpeaker::Zac => line.text.push_str(&text),
+                    _ => chat.push_line(ChatSpeaker::Zac, text),
+                }
+            }
+            ChatUpdate::Stream(StreamEvent::ToolUse { name, input }) => {
+                println!("🔧 Zac^ used {name} {input}");
+                chat.push_line(ChatSpeaker::Tool, format!("🔧 {name} {input}"));
+            }
+            ChatUpdate::Stream(StreamEvent::Usage(usage)) => {
+                let breakdown = UsageBreakdown {
+                    input_tokens: usage.input_tokens,
+                    output_tokens: usage.output_tokens,
+                    cache_read_tokens: usage.cache_read_input_tokens,
+                    cache_write_tokens: usage.cache_creation_input_tokens,
+                    reported_cost_usd: None,
+                };
+
+                // Pricing entry named in the model id, e.g. `sonnet` for `claude-sonnet-4-5`
+                let pricing_model = config.pricing.models.keys()
+                    .find(|name| config.chat.model.contains(name.as_str()));
+                let cost = breakdown.cost_usd(&config.pricing.rates(pricing_model.map(String::as_str)));
+
+                token_budget.add_usage(breakdown.total(), cost, "chat", &UsageScope::default());
+            }
+            ChatUpdate::Finished(Ok(mut history)) => {
+                trim_history(&mut history);
+                chat.history = history;
+                chat.busy = false;
+
+                // Mirror the reply to the console
+                let reply_start = chat.transcript.iter()
+                    .rposition(|line| line.speaker == ChatSpeaker::User)
+                    .map_or(0, |index| index + 1);
+                for line in &chat.transcript[reply_start..] {
+                    if line.speaker == ChatSpeaker::Zac {
+                        println!("💬 Zac^: {}", line.text.trim());
+                    }
+                }
+            }
+            ChatUpdate::Finished(Err(e)) => {
+                eprintln!("⚠️ Zac^ chat failed: {e}");
+                chat.push_line(ChatSpeaker::System, format!("⚠️ {e}"));
+                chat.busy = false;
+            }
+        }
+    }
+}
+
+/// System to answer Zac^'s tool calls from the game world
+#[allow(clippy::too_many_arguments)]
+pub fn run_zac_tool_calls(
+    chat: Res<ZacChat>,
+    mut worker_query: Query<(Entity, &mut Worker)>,
+    project_query: Query<(&Project, &Transform)>,
+    mission_manager: Res<MissionManager>,
+    worker_manager: Res<WorkerManager>,
+    estimator: Res<MissionEstimator>,
+    token_budget: Res<TokenBudget>,
+    journal: Res<JournalManager>,
+    config: Res<AppConfig>,
+    mut commands: Commands,
+) {
+    let calls: Vec<ZacToolCall> = match chat.tool_calls.lock() {
+        Ok(mut calls) => calls.drain(..).collect(),
+        Err(_) => return,
+    };
+
+    for call in calls {
+        let result = match call.name.as_str() {
+            "list_projects" => Ok(list_projects(&project_query)),
+            "list_missions" => list_missions(&call.input, &project_query, &worker_query, &mission_manager, &estimator),
+            "list_workers" => Ok(list_workers(&worker_query, &mission_manager)),
+            "assign_mission" => assign_mission(
+                &call.input,
+                &mut worker_query,
+                &project_query,
+                &mission_manager,
+                &worker_manager,
+                &estimator,
+                &token_budget,
+                &journal,
+                &config,
+                &mut commands,
+            ),
+            other => Err(format!("Unknown tool '{other}'")),
+        };
+
+        // The conversation may have timed out and stopped listening
+        let _ = call.reply.send(result);
+    }
+}
+
+/// String argument of a tool call
+fn text_arg<'a>(input: &'a Value, name: &str) -> Result<&'a str, String> {
+    input[name].as_str()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| format!("Missing '{name}'"))
+}
+
+fn find_project<'a>(project_query: &'a Query<(&Project, &Transform)>, key: &str) -> Result<(&'a Project, Vec3), String> {
+    project_query.iter()
+        .find(|(p, _)| p.id == key || p.name.eq_ignore_ascii_case(key))
+        .map(|(p, transform)| (p, transform.translation))
+        .ok_or_else(|| format!("No project named '{key}'"))
+}
+
+fn list_projects(project_query: &Query<(&Project, &Transform)>) -> String {
+    let lines: Vec<String> = project_query.iter()
+        .map(|(project, _)| format!("- {} (id {}): stage {}/10, {}/{} missions done",
+                                    project.name, project.id, project.visual_stage(),
+                                    project.completed_missions, project.total_missions))
+        .collect();
+
+    if lines.is_empty() {
+        "No projects yet.".to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+fn list_missions(
+    input: &Value,
+    project_query: &Query<(&Project, &Transform)>,
+    worker_query: &Query<(Entity, &mut Worker)>,
+    mission_manager: &MissionManager,
+    estimator: &MissionEstimator,
+) -> Result<String, String> {
+    let (project, _) = find_project(project_query, text_arg(input, "project")?)?;
+    let include_completed = input["include_completed"].as_bool().unwrap_or(false);
+
+    let missions = mission_manager.load_missions(&project.id)?;
+    let available: Vec<String> = mission_manager.get_available_missions(&project.id)?
+        .into_iter()
+        .map(|m| m.id)
+        .collect();
+    let holders: HashMap<&str, &str> = worker_query.iter()
+        .filter_map(|(_, w)| Some((w.current_task_id.as_deref()?, w.name.as_str())))
+        .collect();
+
+    let lines: Vec<String> = missions.iter()
+        .filter(|m| include_completed || m.status != MissionStatus::Completed)
+        .map(|m| {
+            let mut line = format!("- #{} {} [{}] id {}", m.mission_number, m.title, m.status.as_str(), m.id);
+            if let Some(worker) = holders.get(m.id.as_str()) {
+                line.push_str(&format!(" - worker {worker}"));
+            } else if available.contains(&m.id) {
+                line.push_str(&format!(" - available, {}", estimator.estimate(m)));
+            }
+            line
+        })
+        .collect();
+
+    if lines.is_empty() {
+        Ok(format!("{} has no open missions.", project.name))
+    } else {
+        Ok(lines.join("\n"))
+    }
+}
+
+fn list_workers(worker_query: &Query<(Entity, &mut Worker)>, mission_manager: &MissionManager) -> String {
+    let lines: Vec<String> = worker_query.iter()
+        .map(|(_, worker)| {
+            let mission = worker.current_task_id.as_deref()
+                .and_then(|id| mission_manager.load_mission(id).ok().flatten())
+                .map(|m| format!(", on '{}'", m.title))
+                .unwrap_or_default();
+            format!("- {} (id {}): {}{}, model {}, {} missions done",
+                    worker.name, worker.id, worker.state.as_str(), mission,
+                    worker.profile.model_name(), worker.total_tasks_completed)
+        })
+        .collect();
+
+    if lines.is_empty() {
+        "No workers yet - train some at the Town Hall.".to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+/// Send a worker to a mission the way the `A` key does, within the token budget
+#[allow(clippy::too_many_arguments)]
+fn assign_mission(
+    input: &Value,
+    worker_query: &mut Query<(Entity, &mut Worker)>,
+    project_query: &Query<(&Project, &Transform)>,
+    mission_manager: &MissionManager,
+    worker_manager: &WorkerManager,
+    estimator: &MissionEstimator,
+    token_budget: &TokenBudget,
+    journal: &JournalManager,
+    config: &AppConfig,
+    commands: &mut Commands,
+) -> Result<String, String> {
+    let worker_key = text_arg(input, "worker")?;
+    let mission_id = text_arg(input, "mission_id")?;
+
+    let mission = mission_manager.load_mission(mission_id)?
+        .ok_or_else(|| format!("No mission with id '{mission_id}'"))?;
+    let (project, building_pos) = find_project(project_query, &mission.project_id)?;
+
+    if !mission_manager.get_available_missions(&project.id)?.iter().any(|m| m.id == mission.id) {
+        return Err(format!("Mission '{}' is {} or waiting on other missions", mission.title, mission.status.as_str()));
+    }
+    if let Some((_, holder)) = worker_query.iter().find(|(_, w)| w.current_task_id.as_deref() == Some(&mission.id)) {
+        return Err(format!("Worker '{}' is already on mission '{}'", holder.name, mission.title));
+    }
+
+    let in_flight = worker_query.iter()
+        .filter(|(_, w)| matches!(w.state, WorkerState::Working { .. }))
+        .count();
+
+    let (worker_entity, mut worker) = worker_query.iter_mut()
+        .find(|(_, w)| w.id == worker_key || w.name.eq_ignore_ascii_case(worker_key))
+        .ok_or_else(|| format!("No worker named '{worker_key}'"))?;
+
+    if !worker.is_free() {
+        return Err(format!("Worker '{}' is busy ({})", worker.name, worker.state.as_str()));
+    }
+
+    let estimate = estimator.estimate(&mission);
+    let scope = UsageScope::mission(&mission.id, Some(&project.id), &worker.id)
+        .with_names(&project.name, Some(&worker.name));
+    token_budget.check_launch(estimate.gate_tokens(), config.pricing.estimate_rate(worker.profile.model.as_deref()),
+                              in_flight, config.tokens.headroom_per_mission, &scope)?;
+
+    commands.entity(worker_entity).insert(MovementTarget::new(building_pos));
+
+    worker.state = WorkerState::MovingTo { target: building_pos };
+    worker.current_task_id = Some(mission.id.clone());
+    let _ = worker_manager.update_worker_state(&worker.id, &worker.state, Some(&mission.id));
+
+    println!("💬 Zac^ assigned worker '{}' to mission: {}", worker.name, mission.title);
+    println!("   📏 Estimate: {estimate}");
+
+    journal.record(JournalEntryType::UserOverride,
+                   &format!("Assigned worker '{}' to mission {} '{}' as asked in chat (estimate {estimate})",
+                            worker.name, mission.mission_number, mission.title),
+                   Some(&project.id), Some(&mission.id));
+
+    Ok(format!("{} is walking to {} to start mission #{} '{}' ({estimate})",
+               worker.name, project.name, mission.mission_number, mission.title))
+}
@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+use crate::game::systems::zac_chat::ZacChat;
+
+/// Where Zac^ stands, beside the Town Hall
+const ZAC_POSITION: Vec3 = Vec3::new(5.0, 0.0, 5.0);
+
+/// Zac^, the foreman hero unit
+#[derive(Component)]
+pub struct ZacHero;
+
+/// Body and head of Zac^, animated apart from the hero's position
+#[derive(Component)]
+pub struct ZacHeroVisual;
+
+/// Startup system to spawn Zac^ next to the Town Hall
+pub fn spawn_zac_hero(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Larger than a worker, in gold to stand out
+    let body_material = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.95, 0.75, 0.2),
+        metallic: 0.4,
+        perceptual_roughness: 0.4,
+        ..default()
+    });
+    let head_material = materials.add(StandardMaterial {
+        base_color: Color::srgb(1.0, 0.88, 0.7),
+        ..default()
+    });
+
+    commands
+        .spawn((
+            ZacHero,
+            SpatialBundle::from_transform(Transform::from_translation(ZAC_POSITION)),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((ZacHeroVisual, SpatialBundle::default()))
+                .with_children(|visual| {
+                    // Body (capsule)
+                    visual.spawn(PbrBundle {
+                        mesh: meshes.add(Capsule3d::new(0.4, 1.1)),
+                        material: body_material,
+                        transform: Transform::from_translation(Vec3::new(0.0, 0.95, 0.0)),
+                        ..default()
+                    });
+
+                    // Head (sphere)
+                    visual.spawn(PbrBundle {
+                        mesh: meshes.add(Sphere::new(0.32)),
+                        material: head_material,
+                        transform: Transform::from_translation(Vec3::new(0.0, 2.2, 0.0)),
+                        ..default()
+                    });
+                });
+        });
+
+    println!("👑 Zac^ is standing by the Town Hall (press C to chat)");
+}
+
+/// System to animate Zac^: looking around when idle, bobbing while a reply is on its way
+pub fn animate_zac_hero(
+    time: Res<Time>,
+    chat: Res<ZacChat>,
+    mut visual_query: Query<&mut Transform, With<ZacHeroVisual>>,
+) {
+    let t = time.elapsed_seconds();
+
+    for mut transform in visual_query.iter_mut() {
+        let (bob_speed, bob_height) = if chat.busy { (6.0, 0.12) } else { (1.5, 0.04) };
+
+        transform.translation.y = (t * bob_speed).sin() * bob_height;
+        transform.rotation = Quat::from_rotation_y((t * 0.4).sin() * 0.8);
+    }
+}
@@ -0,0 +1,362 @@
+use bevy::prelude::*;
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use rusqlite::Connection;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use crate::core::database;
+use crate::{AppPaths, Database};
+
+/// Days without a digest that are caught up on after a long break
+const MAX_DIGEST_CATCH_UP_DAYS: u64 = 7;
+
+/// SQLite `CURRENT_TIMESTAMP` format, in UTC
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Kind of journal entry, stored as `entry_type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum JournalEntryType {
+    /// Autonomy sent a worker to a mission
+    Assignment,
+    /// A mission held back until the token budget allows it
+    Deferral,
+    /// A mission launched again after an earlier attempt or interruption
+    Retry,
+    /// A mission or its launch failed
+    Failure,
+    /// A project building or the Town Hall went up a stage
+    StageUpgrade,
+    /// The user took over: manual assignments and autonomy toggles
+    UserOverride,
+    /// Summary of a day's entries
+    Digest,
+}
+
+impl JournalEntryType {
+    pub const ALL: [JournalEntryType; 7] = [
+        JournalEntryType::Assignment,
+        JournalEntryType::Deferral,
+        JournalEntryType::Retry,
+        JournalEntryType::Failure,
+        JournalEntryType::StageUpgrade,
+        JournalEntryType::UserOverride,
+        JournalEntryType::Digest,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JournalEntryType::Assignment => "assignment",
+            JournalEntryType::Deferral => "deferral",
+            JournalEntryType::Retry => "retry",
+            JournalEntryType::Failure => "failure",
+            JournalEntryType::StageUpgrade => "stage_upgrade",
+            JournalEntryType::UserOverride => "user_override",
+            JournalEntryType::Digest => "digest",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.as_str() == s)
+    }
+
+    /// Count of entries of this type, e.g. "2 budget deferrals"
+    pub fn count_label(&self, count: usize) -> String {
+        let (one, many) = match self {
+            JournalEntryType::Assignment => ("assignment", "assignments"),
+            JournalEntryType::Deferral => ("budget deferral", "budget deferrals"),
+            JournalEntryType::Retry => ("retry", "retries"),
+            JournalEntryType::Failure => ("failure", "failures"),
+            JournalEntryType::StageUpgrade => ("stage upgrade", "stage upgrades"),
+            JournalEntryType::UserOverride => ("user override", "user overrides"),
+            JournalEntryType::Digest => ("digest", "digests"),
+        };
+        format!("{count} {}", if count == 1 { one } else { many })
+    }
+}
+
+/// A row of `zac_journal`
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    /// UTC, as stored by SQLite
+    pub timestamp: String,
+    pub entry_type: JournalEntryType,
+    pub content: String,
+    /// Name of the related project, or its id once the project is gone
+    pub project_name: Option<String>,
+}
+
+impl JournalEntry {
+    pub fn local_time(&self) -> Option<DateTime<chrono::Local>> {
+        NaiveDateTime::parse_from_str(&self.timestamp, TIMESTAMP_FORMAT).ok()
+            .map(|time| time.and_utc().with_timezone(&chrono::Local))
+    }
+
+    /// `HH:MM [type] Project: content`, with the time in `time_format`
+    pub fn line(&self, time_format: &str) -> String {
+        let time = self.local_time().map(|t| t.format(time_format).to_string()).unwrap_or_default();
+        match &self.project_name {
+            Some(project) => format!("{time} [{}] {project}: {}", self.entry_type.as_str(), self.content),
+            None => format!("{time} [{}] {}", self.entry_type.as_str(), self.content),
+        }
+    }
+}
+
+/// Entries to show; `None` matches everything
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JournalFilter {
+    pub project_id: Option<String>,
+    pub entry_type: Option<JournalEntryType>,
+}
+
+/// Resource for Zac^'s journal of decisions and events
+#[derive(Resource)]
+pub struct JournalManager {
+    pub db_path: PathBuf,
+}
+
+impl JournalManager {
+    pub fn new(db_path: PathBuf) -> Self {
+        Self { db_path }
+    }
+
+    /// Write an entry; a failure is logged rather than getting in the game's way
+    pub fn record(&self, entry_type: JournalEntryType, content: &str, project_id: Option<&str>, task_id: Option<&str>) {
+        if let Err(e) = self.insert(entry_type, content, project_id, task_id) {
+            eprintln!("⚠️ Failed to write journal entry: {e}");
+        }
+    }
+
+    fn insert(&self, entry_type: JournalEntryType, content: &str, project_id: Option<&str>, task_id: Option<&str>) -> Result<(), String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        conn.execute(
+            "INSERT INTO zac_journal (entry_type, content, related_project_id, related_task_id)
+             VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![entry_type.as_str(), content, project_id, task_id],
+        ).map_err(|e| format!("Insert error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Newest matching entries first
+    pub fn load_entries(&self, filter: &JournalFilter, limit: usize) -> Result<Vec<JournalEntry>, String> {
+        self.query_entries(
+            "WHERE (?1 IS NULL OR j.related_project_id = ?1) AND (?2 IS NULL OR j.entry_type = ?2)
+             ORDER BY j.id DESC LIMIT ?3",
+            rusqlite::params![filter.project_id, filter.entry_type.map(|t| t.as_str()), limit as i64],
+        )
+    }
+
+    /// Entries of a local calendar day, oldest first
+    pub fn load_day(&self, day: NaiveDate) -> Result<Vec<JournalEntry>, String> {
+        let start = utc_midnight(day).ok_or_else(|| format!("Invalid day {day}"))?;
+        let end = day.succ_opt().and_then(utc_midnight).ok_or_else(|| format!("Invalid day {day}"))?;
+
+        self.query_entries(
+            "WHERE j.timestamp >= ?1 AND j.timestamp < ?2 ORDER BY j.id ASC",
+            rusqlite::params![start, end],
+        )
+    }
+
+    fn query_entries(&self, clause: &str, params: impl rusqlite::Params) -> Result<Vec<JournalEntry>, String> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT j.timestamp, j.entry_type, j.content, COALESCE(p.name, j.related_project_id)
+             FROM zac_journal j LEFT JOIN projects p ON p.id = j.related_project_id {clause}"
+        )).map_err(|e| format!("Query error: {e}"))?;
+
+        let rows = stmt.query_map(params, |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
+        }).map_err(|e| format!("Query error: {e}"))?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            let (timestamp, entry_type, content, project_name) =
+                row.map_err(|e| format!("Row error: {e}"))?;
+
+            // Skip types written by a newer version
+            let Some(entry_type) = JournalEntryType::from_str(&entry_type) else { continue };
+
+            entries.push(JournalEntry { timestamp, entry_type, content, project_name });
+        }
+
+        Ok(entries)
+    }
+}
+
+/// Local midnight starting `day`, as a stored UTC timestamp
+fn utc_midnight(day: NaiveDate) -> Option<String> {
+    day.and_hms_opt(0, 0, 0)?
+        .and_local_timezone(chrono::Local)
+        .earliest()
+        .map(|time| time.with_timezone(&chrono::Utc).format(TIMESTAMP_FORMAT).to_string())
+}
+
+/// Summary of one day's journal
+pub struct JournalDigest {
+    pub day: NaiveDate,
+    entries: Vec<JournalEntry>,
+}
+
+impl JournalDigest {
+    /// Digest of the day's entries, leaving out earlier digests
+    pub fn new(day: NaiveDate, entries: Vec<JournalEntry>) -> Self {
+        let entries = entries.into_iter()
+            .filter(|e| e.entry_type != JournalEntryType::Digest)
+            .collect();
+        Self { day, entries }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn counts<'a>(entries: impl Iterator<Item = &'a JournalEntry>) -> String {
+        let mut counts: BTreeMap<JournalEntryType, usize> = BTreeMap::new();
+        for entry in entries {
+            *counts.entry(entry.entry_type).or_default() += 1;
+        }
+
+        counts.iter()
+            .map(|(entry_type, count)| entry_type.count_label(*count))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// One line, e.g. "2026-10-18: 9 entries - 5 assignments, 1 failure"
+    pub fn headline(&self) -> String {
+        if self.entries.is_empty() {
+            return format!("{}: no journal entries", self.day);
+        }
+        format!("{}: {} entries - {}", self.day, self.entries.len(), Self::counts(self.entries.iter()))
+    }
+
+    /// Counts per project and the entries needing attention
+    pub fn summary(&self) -> String {
+        let mut md = format!("# Zac^ Journal - {}\n\n{}\n", self.day, self.headline());
+
+        if self.entries.is_empty() {
+            return md;
+        }
+
+        // Per project, with town-wide entries (Town Hall, autonomy toggles) last
+        let mut projects: BTreeMap<Option<&str>, Vec<&JournalEntry>> = BTreeMap::new();
+        for entry in &self.entries {
+            projects.entry(entry.project_name.as_deref()).or_default().push(entry);
+        }
+
+        md.push_str("\n## Projects\n\n");
+        for (name, entries) in projects.iter().filter(|(name, _)| name.is_some()) {
+            md.push_str(&format!("- {}: {}\n", name.unwrap_or_default(), Self::counts(entries.iter().copied())));
+        }
+        if let Some(entries) = projects.get(&None) {
+            md.push_str(&format!("- Town: {}\n", Self::counts(entries.iter().copied())));
+        }
+
+        let attention: Vec<&JournalEntry> = self.entries.iter()
+            .filter(|e| matches!(e.entry_type, JournalEntryType::Failure | JournalEntryType::Retry))
+            .collect();
+        if !attention.is_empty() {
+            md.push_str("\n## Needs Attention\n\n");
+            for entry in attention {
+                md.push_str(&format!("- {}\n", entry.line("%H:%M")));
+            }
+        }
+
+        md
+    }
+
+    /// The summary followed by every entry of the day
+    pub fn to_markdown(&self) -> String {
+        let mut md = self.summary();
+        if self.entries.is_empty() {
+            return md;
+        }
+
+        md.push_str("\n## Timeline\n\n");
+        for entry in &self.entries {
+            md.push_str(&format!("- {}\n", entry.line("%H:%M")));
+        }
+
+        md
+    }
+
+    /// Write `<dir>/<day>.md`
+    pub fn write(&self, dir: &Path) -> Result<PathBuf, String> {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+
+        let path = dir.join(format!("{}.md", self.day));
+        std::fs::write(&path, self.to_markdown())
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+
+        Ok(path)
+    }
+}
+
+/// System to write a digest of each finished day, catching up after a break
+pub fn write_daily_digest(
+    journal: Res<JournalManager>,
+    paths: Res<AppPaths>,
+    db: Res<Database>,
+    time: Res<Time>,
+    mut last_check: Local<Option<f32>>,
+) {
+    let elapsed = last_check.get_or_insert(f32::MAX);
+    *elapsed += time.delta_seconds();
+
+    // Check every minute
+    if *elapsed < 60.0 {
+        return;
+    }
+    *elapsed = 0.0;
+
+    let Ok(conn) = db.0.lock() else { return };
+
+    let yesterday = chrono::Local::now().date_naive() - chrono::Days::new(1);
+    let last_digest = database::load_state(&conn, "journal_digest_day")
+        .ok()
+        .flatten()
+        .and_then(|s| serde_json::from_str::<NaiveDate>(&s).ok());
+
+    if last_digest.is_some_and(|day| day >= yesterday) {
+        return;
+    }
+
+    let first = last_digest
+        .and_then(|day| day.succ_opt())
+        .unwrap_or(yesterday)
+        .max(yesterday - chrono::Days::new(MAX_DIGEST_CATCH_UP_DAYS - 1));
+
+    for day in first.iter_days().take_while(|day| *day <= yesterday) {
+        let digest = match journal.load_day(day) {
+            Ok(entries) => JournalDigest::new(day, entries),
+            Err(e) => {
+                eprintln!("⚠️ Failed to read the journal for {day}: {e}");
+                return;
+            }
+        };
+        if digest.is_empty() {
+            continue;
+        }
+
+        match digest.write(&paths.data_dir.join("journal")) {
+            Ok(path) => println!("📓 Journal digest {} ({})", digest.headline(), path.display()),
+            Err(e) => eprintln!("⚠️ {e}"),
+        }
+        journal.record(JournalEntryType::Digest, &digest.headline(), None, None);
+    }
+
+    if let Ok(json) = serde_json::to_string(&yesterday) {
+        if let Err(e) = database::save_state(&conn, "journal_digest_day", &json) {
+            eprintln!("❌ Failed to save journal digest day: {e}");
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use crate::game::project::Project;
+use crate::game::project::state_sync::{export_project_state, reconcile_project, SyncOutcome};
+use crate::game::resources::ProjectManager;
+use crate::game::systems::MissionManager;
+
+/// Startup system to reconcile each project's `.zac/` files with SQLite
+pub fn reconcile_zac_state_files(
+    project_manager: Res<ProjectManager>,
+    mission_manager: Res<MissionManager>,
+) {
+    let projects = match project_manager.load_projects() {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("Failed to load projects for .zac/ reconciliation: {e}");
+            return;
+        }
+    };
+
+    for project in projects {
+        if !std::path::Path::new(&project.path).is_dir() {
+            println!("⚠️  Project '{}' directory missing - skipping .zac/ sync", project.name);
+            continue;
+        }
+
+        match reconcile_project(&project, &project_manager, &mission_manager) {
+            Ok(SyncOutcome::Imported) => println!("📥 '{}': .zac/ state was newer, imported", project.name),
+            Ok(SyncOutcome::Exported) => println!("📤 '{}': database state written to .zac/", project.name),
+            Ok(SyncOutcome::InSync) => {}
+            Err(e) => eprintln!("Failed to reconcile .zac/ for '{}': {e}", project.name),
+        }
+    }
+}
+
+/// System to mirror database state into `.zac/` periodically
+pub fn mirror_zac_state_files(
+    project_query: Query<&Project>,
+    project_manager: Res<ProjectManager>,
+    mission_manager: Res<MissionManager>,
+    time: Res<Time>,
+    mut last_mirror: Local<f32>,
+) {
+    *last_mirror += time.delta_seconds();
+
+    // Mirror every 30 seconds
+    if *last_mirror < 30.0 {
+        return;
+    }
+    *last_mirror = 0.0;
+
+    for project in project_query.iter() {
+        if let Err(e) = export_project_state(project, &project_manager, &mission_manager) {
+            eprintln!("Failed to mirror .zac/ for '{}': {e}", project.name);
+        }
+    }
+}
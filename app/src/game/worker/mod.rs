@@ -1,7 +1,11 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+pub mod profile;
+pub mod specialty;
+
 /// Worker entity - represents a Claude Code CLI instance
 #[derive(Component, Debug, Clone)]
 pub struct Worker {
@@ -12,6 +16,10 @@ pub struct Worker {
     pub current_task_id: Option<String>,
     pub total_tasks_completed: u32,
     pub total_tokens_used: u64,
+    pub total_cost_usd: f64,
+    /// Per-domain scores from 0.0 to 1.0, learned from mission attempts
+    pub specialty_scores: HashMap<String, f32>,
+    pub profile: profile::WorkerProfile,
 }
 
 impl Worker {
@@ -24,10 +32,13 @@ impl Worker {
             current_task_id: None,
             total_tasks_completed: 0,
             total_tokens_used: 0,
+            total_cost_usd: 0.0,
+            specialty_scores: HashMap::new(),
+            profile: profile::WorkerProfile::default(),
         }
     }
 
-    fn random_color() -> Color {
+    pub fn random_color() -> Color {
         use rand::Rng;
         let mut rng = rand::thread_rng();
         Color::srgb(
@@ -36,6 +47,11 @@ impl Worker {
             rng.gen_range(0.3..0.9),
         )
     }
+
+    /// Idle or ready without a mission; a launch deferred by the budget keeps its mission
+    pub fn is_free(&self) -> bool {
+        matches!(self.state, WorkerState::Idle | WorkerState::Ready) && self.current_task_id.is_none()
+    }
 }
 
 /// Worker state machine
@@ -78,14 +94,32 @@ impl WorkerState {
         }
     }
 
+    /// Parse a legacy `state` text value; variant fields come back empty
     pub fn from_str(s: &str) -> Self {
         match s {
             "ready" => WorkerState::Ready,
             "moving" => WorkerState::MovingTo { target: Vec3::ZERO },
+            "working" => WorkerState::Working { mission_id: String::new(), started_at: String::new() },
             "reflecting" => WorkerState::Reflecting,
+            "crashed" => WorkerState::Crashed { error: String::new(), last_mission_id: String::new() },
             _ => WorkerState::Idle,
         }
     }
+
+    /// Restore a saved state: the full JSON when present, else the legacy text
+    /// with the mission filled in from `current_task_id`
+    pub fn from_saved(json: Option<&str>, legacy: &str, task_id: Option<&str>) -> Self {
+        if let Some(state) = json.and_then(|j| serde_json::from_str(j).ok()) {
+            return state;
+        }
+
+        let task_id = task_id.unwrap_or_default().to_string();
+        match Self::from_str(legacy) {
+            WorkerState::Working { started_at, .. } => WorkerState::Working { mission_id: task_id, started_at },
+            WorkerState::Crashed { error, .. } => WorkerState::Crashed { error, last_mission_id: task_id },
+            state => state,
+        }
+    }
 }
 
 /// Component for worker visual representation
@@ -113,4 +147,23 @@ impl NameGenerator {
         let first = Self::FIRST_NAMES[rng.gen_range(0..Self::FIRST_NAMES.len())];
         first.to_string()
     }
+
+    /// A random name not in `taken`, numbered once every first name is used
+    pub fn unique_name(taken: &[String]) -> String {
+        use rand::seq::SliceRandom;
+        let mut rng = rand::thread_rng();
+
+        let free: Vec<&&str> = Self::FIRST_NAMES.iter()
+            .filter(|name| !taken.iter().any(|t| t == *name))
+            .collect();
+        if let Some(name) = free.choose(&mut rng) {
+            return name.to_string();
+        }
+
+        let first = Self::random_name();
+        (2..)
+            .map(|n| format!("{first} {n}"))
+            .find(|name| !taken.contains(name))
+            .unwrap_or(first)
+    }
 }
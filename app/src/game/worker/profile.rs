@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+
+/// Models a worker can run on (`None` leaves the CLI default)
+pub const MODELS: [Option<&str>; 4] = [None, Some("haiku"), Some("sonnet"), Some("opus")];
+
+/// Named personas and the system prompt each one appends
+pub const PERSONAS: [(&str, &str); 4] = [
+    ("none", ""),
+    ("docs writer", "You write clear, concise documentation. Prefer small, focused edits and keep the existing tone."),
+    ("careful refactorer", "You are a careful senior engineer. Read the surrounding code first, keep changes minimal, and run the build and tests before finishing."),
+    ("test author", "You focus on tests. Cover edge cases, follow the project's test layout, and never weaken existing assertions."),
+];
+
+/// Per-mission token limits the panel steps through (`None` is unlimited)
+pub const TOKEN_LIMITS: [Option<u32>; 5] = [None, Some(10_000), Some(25_000), Some(50_000), Some(100_000)];
+
+/// Which tools the agent may use during a mission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolPolicy {
+    /// Every tool, without permission prompts
+    #[default]
+    Full,
+    /// Everything except shell commands
+    NoShell,
+    /// Reading and searching only
+    ReadOnly,
+}
+
+impl ToolPolicy {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ToolPolicy::Full => "full",
+            ToolPolicy::NoShell => "no shell",
+            ToolPolicy::ReadOnly => "read only",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ToolPolicy::Full => ToolPolicy::NoShell,
+            ToolPolicy::NoShell => ToolPolicy::ReadOnly,
+            ToolPolicy::ReadOnly => ToolPolicy::Full,
+        }
+    }
+
+    /// `claude-code` arguments enforcing the policy
+    pub fn cli_args(&self) -> Vec<&str> {
+        match self {
+            ToolPolicy::Full => vec!["--dangerously-skip-permissions"],
+            // The `=` form keeps the list from swallowing the mission file after it
+            ToolPolicy::NoShell => vec!["--dangerously-skip-permissions", "--disallowedTools=Bash"],
+            ToolPolicy::ReadOnly => vec!["--dangerously-skip-permissions", "--disallowedTools=Bash,Edit,Write,NotebookEdit"],
+        }
+    }
+}
+
+/// Cosmetic flavor for console messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Personality {
+    #[default]
+    Steady,
+    Cheerful,
+    Grumpy,
+    Curious,
+}
+
+impl Personality {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Personality::Steady => "steady",
+            Personality::Cheerful => "cheerful",
+            Personality::Grumpy => "grumpy",
+            Personality::Curious => "curious",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Personality::Steady => Personality::Cheerful,
+            Personality::Cheerful => Personality::Grumpy,
+            Personality::Grumpy => Personality::Curious,
+            Personality::Curious => Personality::Steady,
+        }
+    }
+
+    /// What the worker says when it picks up a mission
+    pub fn greeting(&self) -> &str {
+        match self {
+            Personality::Steady => "On it.",
+            Personality::Cheerful => "Ooh, a new one! Let's go!",
+            Personality::Grumpy => "Fine. I'll do it.",
+            Personality::Curious => "Interesting... let me take a look.",
+        }
+    }
+}
+
+/// How a worker's agent is launched, stored in `workers.profile_json`
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkerProfile {
+    pub model: Option<String>,
+    /// Appended to the agent's system prompt
+    pub system_prompt: String,
+    pub tool_policy: ToolPolicy,
+    /// Missions reporting more tokens than this are stopped and fail
+    pub max_tokens_per_mission: Option<u32>,
+    pub personality: Personality,
+}
+
+impl WorkerProfile {
+    pub fn model_name(&self) -> &str {
+        self.model.as_deref().unwrap_or("default")
+    }
+
+    /// Persona whose prompt matches, or "custom"
+    pub fn persona_name(&self) -> &str {
+        PERSONAS.iter()
+            .find(|(_, prompt)| *prompt == self.system_prompt)
+            .map(|(name, _)| *name)
+            .unwrap_or("custom")
+    }
+
+    pub fn next_model(&mut self) {
+        let index = MODELS.iter().position(|m| *m == self.model.as_deref()).unwrap_or(0);
+        self.model = MODELS[(index + 1) % MODELS.len()].map(str::to_string);
+    }
+
+    pub fn next_persona(&mut self) {
+        let index = PERSONAS.iter().position(|(_, p)| *p == self.system_prompt).unwrap_or(0);
+        self.system_prompt = PERSONAS[(index + 1) % PERSONAS.len()].1.to_string();
+    }
+
+    /// Step the token limit up (`true`) or down, stopping at the ends
+    pub fn step_token_limit(&mut self, up: bool) {
+        let index = TOKEN_LIMITS.iter().position(|l| *l == self.max_tokens_per_mission).unwrap_or(0);
+        let index = if up { (index + 1).min(TOKEN_LIMITS.len() - 1) } else { index.saturating_sub(1) };
+        self.max_tokens_per_mission = TOKEN_LIMITS[index];
+    }
+
+    pub fn token_limit_label(&self) -> String {
+        match self.max_tokens_per_mission {
+            Some(limit) => format!("{limit} tokens"),
+            None => "unlimited".to_string(),
+        }
+    }
+
+    /// `claude-code` arguments applying the profile, before the mission file
+    pub fn cli_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = self.tool_policy.cli_args().into_iter().map(str::to_string).collect();
+
+        if let Some(model) = &self.model {
+            args.push("--model".to_string());
+            args.push(model.clone());
+        }
+
+        let mut prompt = self.system_prompt.clone();
+        if let Some(limit) = self.max_tokens_per_mission {
+            if !prompt.is_empty() {
+                prompt.push(' ');
+            }
+            prompt.push_str(&format!("Finish this mission within about {limit} tokens; stop and summarize if you run short."));
+        }
+        if !prompt.is_empty() {
+            args.push("--append-system-prompt".to_string());
+            args.push(prompt);
+        }
+
+        args
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+/// Score a worker starts from in a domain it has never worked in
+pub const NEUTRAL_SCORE: f32 = 0.5;
+
+/// Weight of the newest attempt in the moving average
+const LEARNING_RATE: f32 = 0.2;
+
+/// Duration and token count at which an attempt earns half the efficiency credit
+const REFERENCE_DURATION_SECS: f32 = 1800.0;
+const REFERENCE_TOKENS: f32 = 50_000.0;
+
+/// How well an attempt went, from 0.0 (failed) to 1.0 (fast and cheap success)
+pub fn attempt_outcome(success: bool, duration_secs: u64, tokens_used: u32) -> f32 {
+    if !success {
+        return 0.0;
+    }
+
+    let speed = 1.0 / (1.0 + duration_secs as f32 / REFERENCE_DURATION_SECS);
+    let thrift = 1.0 / (1.0 + tokens_used as f32 / REFERENCE_TOKENS);
+
+    0.6 + 0.2 * speed + 0.2 * thrift
+}
+
+/// Move the score of every domain towards the attempt's outcome
+pub fn record_outcome(scores: &mut HashMap<String, f32>, domains: &[String], outcome: f32) {
+    for domain in domains {
+        let score = scores.entry(domain.clone()).or_insert(NEUTRAL_SCORE);
+        *score += (outcome - *score) * LEARNING_RATE;
+    }
+}
+
+/// Average score over a mission's domains (neutral for untagged missions)
+pub fn suitability(scores: &HashMap<String, f32>, domains: &[String]) -> f32 {
+    if domains.is_empty() {
+        return NEUTRAL_SCORE;
+    }
+
+    let total: f32 = domains.iter()
+        .map(|d| scores.get(d).copied().unwrap_or(NEUTRAL_SCORE))
+        .sum();
+
+    total / domains.len() as f32
+}
+
+/// Domains sorted best first
+pub fn ranked(scores: &HashMap<String, f32>) -> Vec<(&str, f32)> {
+    let mut ranked: Vec<(&str, f32)> = scores.iter().map(|(d, s)| (d.as_str(), *s)).collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+    ranked
+}
+
+/// Short description like `rust 0.82, tests 0.64`
+pub fn summary(scores: &HashMap<String, f32>) -> String {
+    let ranked = ranked(scores);
+    if ranked.is_empty() {
+        return "no specialties yet".to_string();
+    }
+
+    ranked.iter()
+        .map(|(domain, score)| format!("{domain} {score:.2}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -15,6 +15,7 @@ use core::database;
 pub struct AppPaths {
     pub data_dir: PathBuf,
     pub db_path: PathBuf,
+    pub config_path: PathBuf,
 }
 
 impl Default for AppPaths {
@@ -25,6 +26,7 @@ impl Default for AppPaths {
 
         Self {
             db_path: data_dir.join("zac.db"),
+            config_path: home.join("zac-caret").join("config.toml"),
             data_dir,
         }
     }
@@ -34,8 +36,13 @@ impl Default for AppPaths {
 pub struct Database(pub std::sync::Arc<std::sync::Mutex<rusqlite::Connection>>);
 
 fn main() {
-    // Initialize paths and database
+    // Initialize paths, config and database
     let paths = AppPaths::default();
+    let config = core::config::AppConfig::load(&paths.config_path)
+        .unwrap_or_else(|e| {
+            eprintln!("⚠️  {e} - using default config");
+            core::config::AppConfig::defaults()
+        });
     let conn = database::init_database(&paths.db_path)
         .expect("Failed to initialize database");
 
@@ -46,18 +53,44 @@ fn main() {
         .and_then(|s| serde_json::from_str::<camera::CameraState>(&s).ok())
         .unwrap_or_default();
 
+    // Load saved Town Hall level
+    let town_hall = database::load_state(&conn, "town_hall")
+        .ok()
+        .flatten()
+        .and_then(|s| serde_json::from_str::<game::entities::town_hall::TownHall>(&s).ok())
+        .unwrap_or_default();
+
+    // Token budget from config, with usage so far rebuilt from the ledger
+    let mut token_budget = game::resources::TokenBudget::from_config(&config.tokens, config.pricing.estimate_rate(None));
+    let hourly_start = database::load_state(&conn, "token_budget_period")
+        .ok()
+        .flatten()
+        .and_then(|s| serde_json::from_str::<chrono::DateTime<chrono::Utc>>(&s).ok());
+    token_budget.restore_from_ledger(&conn, hourly_start);
+
+    // Autonomy rules from config, with the last Z toggle
+    let autonomy_enabled = database::load_state(&conn, "autonomy_enabled")
+        .ok()
+        .flatten()
+        .and_then(|s| serde_json::from_str::<bool>(&s).ok());
+    let autonomy_settings = game::resources::AutonomySettings::from_config(&config.autonomy, autonomy_enabled);
+
     let db = Database(std::sync::Arc::new(std::sync::Mutex::new(conn)));
 
     // Create managers
     let project_manager = game::resources::ProjectManager::new(paths.db_path.clone());
     let mission_manager = game::systems::MissionManager::new(paths.db_path.clone());
     let worker_manager = game::resources::WorkerManager::new(paths.db_path.clone());
+    let building_manager = game::resources::BuildingManager::new(paths.db_path.clone());
+    let knowledge_manager = game::systems::KnowledgeManager::new(paths.db_path.clone());
+    let journal_manager = game::systems::JournalManager::new(paths.db_path.clone());
     let cli_manager = game::resources::CliManagerResource::new(paths.data_dir.clone());
+    let conquest_queue = game::resources::ConquestQueue::from_args(std::env::args().skip(1));
 
-    // Create autonomy settings, token budget, and game stats
-    let autonomy_settings = game::resources::AutonomySettings::default();
-    let token_budget = game::resources::TokenBudget::default();
+    // Create game stats
     let game_stats = game::resources::GameStats::default();
+    let scheduler = game::systems::Scheduler::from_config(&config.scheduling.strategy);
+    let estimator = game::systems::MissionEstimator::load(&mission_manager);
 
     App::new()
         .add_plugins(DefaultPlugins.set(WindowPlugin {
@@ -69,25 +102,56 @@ fn main() {
             ..default()
         }))
         .insert_resource(paths)
+        .insert_resource(config)
         .insert_resource(db)
         .insert_resource(camera_state)
         .insert_resource(project_manager)
         .insert_resource(mission_manager)
         .insert_resource(worker_manager)
+        .insert_resource(building_manager)
+        .insert_resource(knowledge_manager)
+        .insert_resource(journal_manager)
         .insert_resource(cli_manager)
+        .insert_resource(conquest_queue)
         .insert_resource(autonomy_settings)
         .insert_resource(token_budget)
         .insert_resource(game_stats)
+        .insert_resource(scheduler)
+        .insert_resource(estimator)
+        .insert_resource(town_hall)
         .init_resource::<camera::CameraSettings>()
+        .init_resource::<game::systems::SelectedProject>()
+        .init_resource::<game::systems::SelectedWorker>()
+        .init_resource::<game::systems::ProjectLifecycleQueue>()
+        .init_resource::<game::systems::HealthMonitor>()
+        .init_resource::<game::systems::ThemePreview>()
+        .init_resource::<game::resources::TownFacilities>()
+        .init_resource::<game::systems::ReflectionQueue>()
+        .init_resource::<game::systems::BudgetGate>()
+        .init_resource::<game::systems::ZacChat>()
+        .init_resource::<ui::JournalView>()
+        .init_resource::<ui::WorkerTextEdit>()
+        .add_event::<ui::UiCommand>()
+        .add_systems(PreStartup, game::systems::reconcile_zac_state_files)
         .add_systems(Startup, (
             game::world::setup_world,
             camera::spawn_camera_from_state,
             game::systems::building_renderer::spawn_initial_town_hall,
             game::systems::spawn_leisure_zone,
             game::systems::spawn_project_buildings,
+            game::systems::spawn_utility_buildings,
             game::systems::restore_workers,
+            game::systems::spawn_zac_hero,
             ui::spawn_building_controls,
+            ui::spawn_worker_panel,
+            ui::spawn_production_queue_panel,
+            ui::spawn_chat_panel,
+            ui::spawn_journal_panel,
         ))
+        .add_systems(PreUpdate, (
+            ui::chat_keyboard_input,
+            ui::worker_text_keyboard_input,
+        ).chain().after(bevy::input::InputSystem))
         .add_systems(Update, (
             camera::camera_pan,
             camera::camera_zoom,
@@ -112,10 +176,53 @@ fn main() {
             game::systems::toggle_autonomy_keypress,
             game::systems::display_autonomy_status,
             game::systems::check_budget_reset,
+            game::systems::resume_deferred_missions,
+            game::systems::charge_streamed_usage,
+            game::systems::persist_token_budget,
             game::systems::display_budget_warnings,
             game::systems::display_budget_status,
             game::systems::update_game_stats,
             game::systems::display_comprehensive_stats,
+            game::systems::select_worker_on_click,
+            ui::update_worker_panel,
+            ui::handle_worker_panel_buttons,
+            ui::handle_worker_lifecycle_buttons,
+            ui::rebuild_production_queue_list,
+            ui::update_production_progress,
+            ui::handle_production_order_buttons,
+        ))
+        .add_systems(Update, (
+            game::systems::process_conquest_queue,
+            game::systems::mirror_zac_state_files,
+            game::systems::select_project_on_click,
+            game::systems::drag_project_buildings,
+            game::systems::project_lifecycle_keys,
+            game::systems::apply_project_lifecycle_actions,
+            game::systems::restore_project_health,
+            game::systems::schedule_health_checks,
+            game::systems::apply_health_reports,
+            game::systems::update_building_condition,
+            // Sees a pending archive/delete before Enter confirms it
+            game::systems::preview_building_theme.before(game::systems::project_lifecycle_keys),
+            game::systems::construct_utility_building_keys,
+            game::systems::progress_utility_buildings,
+            game::systems::upgrade_town_hall_keypress,
+            game::systems::play_stage_transitions,
+            game::systems::apply_reflections,
+            game::systems::show_reflecting_workers,
+            game::systems::preview_autonomy_keypress,
+            game::systems::announce_autonomy_schedule,
+        ))
+        .add_systems(Update, (
+            game::systems::handle_chat_commands,
+            game::systems::apply_chat_updates,
+            game::systems::run_zac_tool_calls,
+            game::systems::animate_zac_hero,
+            ui::update_chat_panel,
+            game::systems::write_daily_digest,
+            ui::toggle_journal_keypress,
+            ui::handle_journal_buttons,
+            ui::update_journal_panel,
         ))
         .run();
 }
@@ -0,0 +1,164 @@
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use crate::game::systems::zac_chat::{ChatSpeaker, ZacChat};
+use crate::ui::components::worker_panel::WorkerTextEdit;
+use crate::ui::UiCommand;
+
+/// Transcript lines shown at once
+const VISIBLE_LINES: usize = 14;
+
+/// Chat panel for talking to Zac^
+#[derive(Component)]
+pub struct ChatPanelUI;
+
+/// Marker component for the conversation text
+#[derive(Component)]
+pub struct ChatTranscriptText;
+
+/// Marker component for the line being typed
+#[derive(Component)]
+pub struct ChatInputText;
+
+/// System to spawn the (initially hidden) chat panel
+pub fn spawn_chat_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            ChatPanelUI,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(20.0),
+                    bottom: Val::Px(20.0),
+                    width: Val::Px(440.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(10.0)),
+                    row_gap: Val::Px(8.0),
+                    display: Display::None,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.9)),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "💬 Zac^  (Enter to send, Esc to close)",
+                TextStyle {
+                    font_size: 16.0,
+                    color: Color::srgb(0.95, 0.75, 0.2),
+                    ..default()
+                },
+            ));
+
+            parent.spawn((
+                ChatTranscriptText,
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 14.0,
+                        color: Color::srgb(0.8, 0.8, 0.8),
+                        ..default()
+                    },
+                ),
+            ));
+
+            parent.spawn((
+                ChatInputText,
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 14.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+            ));
+        });
+}
+
+/// System to open the chat with 'C' and, while it is open, type into it
+/// instead of triggering game shortcuts
+pub fn chat_keyboard_input(
+    mut key_events: EventReader<KeyboardInput>,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut chat: ResMut<ZacChat>,
+    text_edit: Res<WorkerTextEdit>,
+    mut ui_commands: EventWriter<UiCommand>,
+) {
+    let presses: Vec<&KeyboardInput> = key_events.read()
+        .filter(|event| event.state == ButtonState::Pressed)
+        .collect();
+
+    if !chat.open {
+        // A 'C' typed into a worker's name or prompt isn't a shortcut
+        if text_edit.editing.is_none() && presses.iter().any(|event| event.key_code == KeyCode::KeyC) {
+            chat.open = true;
+        }
+        return;
+    }
+
+    for event in presses {
+        match &event.logical_key {
+            Key::Escape => chat.open = false,
+            Key::Enter => {
+                let message = std::mem::take(&mut chat.input);
+                if !message.trim().is_empty() {
+                    ui_commands.send(UiCommand::ChatWithZac { message });
+                }
+            }
+            Key::Backspace => {
+                chat.input.pop();
+            }
+            Key::Space => chat.input.push(' '),
+            Key::Character(text) => {
+                chat.input.extend(text.chars().filter(|c| !c.is_control()));
+            }
+            _ => {}
+        }
+    }
+
+    // Keys typed into the chat don't reach the rest of the game
+    keyboard.reset_all();
+}
+
+/// System to show the conversation and the line being typed
+pub fn update_chat_panel(
+    chat: Res<ZacChat>,
+    mut panel_query: Query<&mut Style, With<ChatPanelUI>>,
+    mut transcript_query: Query<&mut Text, (With<ChatTranscriptText>, Without<ChatInputText>)>,
+    mut input_query: Query<&mut Text, With<ChatInputText>>,
+) {
+    if !chat.is_changed() {
+        return;
+    }
+
+    for mut style in panel_query.iter_mut() {
+        style.display = if chat.open { Display::Flex } else { Display::None };
+    }
+
+    let skip = chat.transcript.len().saturating_sub(VISIBLE_LINES);
+    let mut transcript: Vec<String> = chat.transcript[skip..].iter()
+        .map(|line| match line.speaker {
+            ChatSpeaker::User => format!("You: {}", line.text),
+            ChatSpeaker::Zac => format!("Zac^: {}", line.text.trim()),
+            ChatSpeaker::Tool | ChatSpeaker::System => line.text.clone(),
+        })
+        .collect();
+    if chat.busy && !chat.transcript.last().is_some_and(|line| line.speaker == ChatSpeaker::Zac) {
+        transcript.push("Zac^ is thinking...".to_string());
+    }
+
+    for mut text in transcript_query.iter_mut() {
+        text.sections[0].value = transcript.join("\n");
+    }
+
+    // An API key being typed isn't shown
+    let input = match chat.input.strip_prefix("/key ") {
+        Some(key) => format!("/key {}", "*".repeat(key.chars().count())),
+        None => chat.input.clone(),
+    };
+    for mut text in input_query.iter_mut() {
+        text.sections[0].value = format!("> {input}_");
+    }
+}
@@ -0,0 +1,251 @@
+use bevy::prelude::*;
+use crate::game::project::Project;
+use crate::game::systems::zac_journal::{JournalDigest, JournalEntryType, JournalFilter, JournalManager};
+
+/// Entries shown at once
+const VISIBLE_ENTRIES: usize = 18;
+
+/// Seconds between refreshes while the journal is open
+const REFRESH_SECS: f32 = 2.0;
+
+const BUTTON_COLOR: Color = Color::srgb(0.25, 0.3, 0.45);
+const BUTTON_HOVER_COLOR: Color = Color::srgb(0.35, 0.4, 0.6);
+
+/// Resource with what the journal viewer shows
+#[derive(Resource, Default)]
+pub struct JournalView {
+    pub open: bool,
+    pub filter: JournalFilter,
+    /// Showing today's digest instead of the entry list
+    pub digest: bool,
+}
+
+/// Journal viewer panel
+#[derive(Component)]
+pub struct JournalPanelUI;
+
+/// Marker component for the active filters line
+#[derive(Component)]
+pub struct JournalFilterText;
+
+/// Marker component for the entries (or digest) text
+#[derive(Component)]
+pub struct JournalEntriesText;
+
+#[derive(Component, Clone, Copy)]
+pub enum JournalPanelButton {
+    /// Cycle through the projects
+    Project,
+    /// Cycle through the entry types
+    Type,
+    Digest,
+}
+
+impl JournalPanelButton {
+    fn label(&self) -> &str {
+        match self {
+            JournalPanelButton::Project => "Project",
+            JournalPanelButton::Type => "Type",
+            JournalPanelButton::Digest => "Today's Digest",
+        }
+    }
+}
+
+/// System to spawn the (initially hidden) journal panel
+pub fn spawn_journal_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            JournalPanelUI,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(25.0),
+                    top: Val::Px(20.0),
+                    width: Val::Percent(50.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(10.0)),
+                    row_gap: Val::Px(8.0),
+                    display: Display::None,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.9)),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                JournalFilterText,
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 16.0,
+                        color: Color::srgb(0.95, 0.75, 0.2),
+                        ..default()
+                    },
+                ),
+            ));
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        column_gap: Val::Px(8.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    for button in [JournalPanelButton::Project, JournalPanelButton::Type, JournalPanelButton::Digest] {
+                        row.spawn((
+                            button,
+                            ButtonBundle {
+                                style: Style {
+                                    padding: UiRect::all(Val::Px(6.0)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                background_color: BackgroundColor(BUTTON_COLOR),
+                                ..default()
+                            },
+                        ))
+                        .with_children(|b| {
+                            b.spawn(TextBundle::from_section(
+                                button.label(),
+                                TextStyle {
+                                    font_size: 14.0,
+                                    color: Color::WHITE,
+                                    ..default()
+                                },
+                            ));
+                        });
+                    }
+                });
+
+            parent.spawn((
+                JournalEntriesText,
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 13.0,
+                        color: Color::srgb(0.8, 0.8, 0.8),
+                        ..default()
+                    },
+                ),
+            ));
+        });
+}
+
+/// System to toggle the journal with 'J'
+pub fn toggle_journal_keypress(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut view: ResMut<JournalView>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyJ) {
+        view.open = !view.open;
+    }
+}
+
+/// System to change the journal filters from the panel buttons
+pub fn handle_journal_buttons(
+    mut interaction_query: Query<(&Interaction, &JournalPanelButton, &mut BackgroundColor), Changed<Interaction>>,
+    project_query: Query<&Project>,
+    mut view: ResMut<JournalView>,
+) {
+    for (interaction, button, mut color) in interaction_query.iter_mut() {
+        *color = BackgroundColor(match interaction {
+            Interaction::None => BUTTON_COLOR,
+            _ => BUTTON_HOVER_COLOR,
+        });
+
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            JournalPanelButton::Project => {
+                let mut projects: Vec<&Project> = project_query.iter().collect();
+                projects.sort_by(|a, b| a.name.cmp(&b.name));
+
+                // All -> each project -> All
+                let next = match &view.filter.project_id {
+                    None => projects.first(),
+                    Some(id) => projects.iter()
+                        .position(|p| &p.id == id)
+                        .and_then(|index| projects.get(index + 1)),
+                };
+                view.filter.project_id = next.map(|p| p.id.clone());
+                view.digest = false;
+            }
+            JournalPanelButton::Type => {
+                let types = JournalEntryType::ALL;
+                let next = match view.filter.entry_type {
+                    None => types.first(),
+                    Some(current) => types.iter()
+                        .position(|t| *t == current)
+                        .and_then(|index| types.get(index + 1)),
+                };
+                view.filter.entry_type = next.copied();
+                view.digest = false;
+            }
+            JournalPanelButton::Digest => view.digest = !view.digest,
+        }
+    }
+}
+
+/// System to show the filtered journal, or today's digest so far
+#[allow(clippy::too_many_arguments)]
+pub fn update_journal_panel(
+    view: Res<JournalView>,
+    journal: Res<JournalManager>,
+    project_query: Query<&Project>,
+    time: Res<Time>,
+    mut since_refresh: Local<f32>,
+    mut panel_query: Query<&mut Style, With<JournalPanelUI>>,
+    mut filter_query: Query<&mut Text, (With<JournalFilterText>, Without<JournalEntriesText>)>,
+    mut entries_query: Query<&mut Text, With<JournalEntriesText>>,
+) {
+    *since_refresh += time.delta_seconds();
+
+    if view.is_changed() {
+        for mut style in panel_query.iter_mut() {
+            style.display = if view.open { Display::Flex } else { Display::None };
+        }
+    } else if !view.open || *since_refresh < REFRESH_SECS {
+        return;
+    }
+    *since_refresh = 0.0;
+
+    if !view.open {
+        return;
+    }
+
+    let project = view.filter.project_id.as_ref()
+        .map(|id| project_query.iter().find(|p| &p.id == id).map_or(id.clone(), |p| p.name.clone()))
+        .unwrap_or_else(|| "All".to_string());
+    let entry_type = view.filter.entry_type.map_or("all", |t| t.as_str());
+    let filters = if view.digest {
+        "📓 Zac^ Journal - today's digest".to_string()
+    } else {
+        format!("📓 Zac^ Journal - project: {project}, type: {entry_type}")
+    };
+
+    let content = if view.digest {
+        let today = chrono::Local::now().date_naive();
+        journal.load_day(today).map(|entries| JournalDigest::new(today, entries).summary())
+    } else {
+        journal.load_entries(&view.filter, VISIBLE_ENTRIES).map(|entries| {
+            if entries.is_empty() {
+                "No entries yet.".to_string()
+            } else {
+                entries.iter().map(|e| e.line("%m-%d %H:%M")).collect::<Vec<_>>().join("\n")
+            }
+        })
+    }.unwrap_or_else(|e| format!("Failed to read the journal: {e}"));
+
+    for mut text in filter_query.iter_mut() {
+        text.sections[0].value = filters.clone();
+    }
+    for mut text in entries_query.iter_mut() {
+        text.sections[0].value = content.clone();
+    }
+}
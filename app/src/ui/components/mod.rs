@@ -1,5 +1,9 @@
 pub mod building_controls;
+pub mod chat_panel;
+pub mod journal_panel;
+pub mod production_queue;
 pub mod town_hall_controls;
+pub mod worker_panel;
 
 pub use building_controls::{
     spawn_building_controls,
@@ -8,4 +12,8 @@ pub use building_controls::{
     update_stage_display,
     button_hover_system,
 };
+pub use chat_panel::{spawn_chat_panel, chat_keyboard_input, update_chat_panel};
+pub use journal_panel::{spawn_journal_panel, toggle_journal_keypress, handle_journal_buttons, update_journal_panel, JournalView};
+pub use production_queue::{spawn_production_queue_panel, rebuild_production_queue_list, update_production_progress, handle_production_order_buttons};
 pub use town_hall_controls::spawn_worker_on_keypress;
+pub use worker_panel::{spawn_worker_panel, update_worker_panel, handle_worker_panel_buttons, handle_worker_lifecycle_buttons, worker_text_keyboard_input, WorkerTextEdit};
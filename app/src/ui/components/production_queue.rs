@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+use crate::game::resources::{TokenBudget, WorkerManager};
+use crate::game::systems::worker_spawner::TownHall;
+
+/// UI panel listing the Town Hall's production queue
+#[derive(Component)]
+pub struct ProductionQueueUI;
+
+/// Container the queue rows are rebuilt into
+#[derive(Component)]
+pub struct ProductionQueueList;
+
+/// Fill of an order's progress bar
+#[derive(Component)]
+pub struct ProductionProgressBar(pub String);
+
+/// Per-order button: move up or cancel
+#[derive(Component)]
+pub struct ProductionOrderButton {
+    pub order_id: String,
+    pub action: ProductionAction,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ProductionAction {
+    MoveUp,
+    Cancel,
+}
+
+/// System to spawn the production queue panel
+pub fn spawn_production_queue_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            ProductionQueueUI,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(20.0),
+                    bottom: Val::Px(20.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(10.0)),
+                    row_gap: Val::Px(6.0),
+                    min_width: Val::Px(260.0),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.9)),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Worker Production (W)",
+                TextStyle {
+                    font_size: 16.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+
+            parent.spawn((
+                ProductionQueueList,
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(4.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+            ));
+        });
+}
+
+/// System to rebuild the queue rows whenever orders are added, removed or reordered
+pub fn rebuild_production_queue_list(
+    mut commands: Commands,
+    town_hall_query: Query<&TownHall>,
+    list_query: Query<Entity, With<ProductionQueueList>>,
+    mut shown: Local<Vec<String>>,
+) {
+    let Ok(town_hall) = town_hall_query.get_single() else { return };
+    let Ok(list) = list_query.get_single() else { return };
+
+    let ids: Vec<String> = town_hall.worker_production_queue.iter().map(|o| o.id.clone()).collect();
+    if *shown == ids {
+        return;
+    }
+    *shown = ids;
+
+    commands.entity(list).despawn_descendants();
+    commands.entity(list).with_children(|list| {
+        if town_hall.worker_production_queue.is_empty() {
+            list.spawn(TextBundle::from_section("Queue empty", small_text()));
+            return;
+        }
+
+        for (index, order) in town_hall.worker_production_queue.iter().enumerate() {
+            list.spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(6.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|row| {
+                row.spawn(TextBundle::from_section(
+                    format!("{}. {} ({} tok)", index + 1, order.worker_name, order.token_cost),
+                    small_text(),
+                ).with_style(Style { width: Val::Px(130.0), ..default() }));
+
+                // Progress bar
+                row.spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(60.0),
+                        height: Val::Px(8.0),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+                    ..default()
+                })
+                .with_children(|bar| {
+                    bar.spawn((
+                        ProductionProgressBar(order.id.clone()),
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(0.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            background_color: BackgroundColor(Color::srgb(0.3, 0.75, 0.3)),
+                            ..default()
+                        },
+                    ));
+                });
+
+                if index > 0 {
+                    spawn_order_button(row, &order.id, ProductionAction::MoveUp, "Up");
+                }
+                spawn_order_button(row, &order.id, ProductionAction::Cancel, "X");
+            });
+        }
+    });
+}
+
+fn small_text() -> TextStyle {
+    TextStyle {
+        font_size: 14.0,
+        color: Color::srgb(0.8, 0.8, 0.8),
+        ..default()
+    }
+}
+
+fn spawn_order_button(row: &mut ChildBuilder, order_id: &str, action: ProductionAction, label: &str) {
+    row.spawn((
+        ProductionOrderButton { order_id: order_id.to_string(), action },
+        ButtonBundle {
+            style: Style {
+                padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+                ..default()
+            },
+            background_color: BackgroundColor(Color::srgb(0.3, 0.3, 0.4)),
+            ..default()
+        },
+    ))
+    .with_children(|button| {
+        button.spawn(TextBundle::from_section(label, small_text()));
+    });
+}
+
+/// System to fill the progress bars from the orders' wall-clock progress
+pub fn update_production_progress(
+    town_hall_query: Query<&TownHall>,
+    mut bar_query: Query<(&ProductionProgressBar, &mut Style)>,
+) {
+    let Ok(town_hall) = town_hall_query.get_single() else { return };
+    let now = chrono::Utc::now();
+
+    for (bar, mut style) in bar_query.iter_mut() {
+        let progress = town_hall.worker_production_queue.iter()
+            .find(|o| o.id == bar.0)
+            .map(|o| o.progress(now))
+            .unwrap_or(0.0);
+
+        let width = Val::Percent(progress * 100.0);
+        if style.width != width {
+            style.width = width;
+        }
+    }
+}
+
+/// System to move orders up or cancel them (refunding their token cost)
+pub fn handle_production_order_buttons(
+    interaction_query: Query<(&Interaction, &ProductionOrderButton), Changed<Interaction>>,
+    mut town_hall_query: Query<&mut TownHall>,
+    mut token_budget: ResMut<TokenBudget>,
+    worker_manager: Res<WorkerManager>,
+) {
+    let Ok(mut town_hall) = town_hall_query.get_single_mut() else { return };
+
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button.action {
+            ProductionAction::MoveUp => {
+                if !town_hall.move_order_up(&button.order_id) {
+                    continue;
+                }
+            }
+            ProductionAction::Cancel => {
+                let Some(order) = town_hall.cancel_order(&button.order_id) else { continue };
+                if token_budget.refund(order.token_cost, order.queued_at, "production") {
+                    println!("🚫 Cancelled training of '{}' ({} tokens refunded)", order.worker_name, order.token_cost);
+                } else {
+                    println!("🚫 Cancelled training of '{}' (queued in an earlier budget period, nothing refunded)", order.worker_name);
+                }
+            }
+        }
+
+        if let Err(e) = worker_manager.save_production_queue(&town_hall.worker_production_queue) {
+            eprintln!("Failed to save production queue: {e}");
+        }
+    }
+}
@@ -1,31 +1,67 @@
 use bevy::prelude::*;
 use crate::game::systems::worker_spawner::TownHall;
-use crate::game::resources::WorkerManager;
+use crate::game::entities::town_hall::TownHall as TownHallLevel;
+use crate::game::resources::{TokenBudget, TownFacilities, WorkerManager};
+use crate::core::config::AppConfig;
 
 /// Temporary: Spawn worker on 'W' key press
+///
+/// Queued workers count towards the cap, and each order is charged to the token budget.
 pub fn spawn_worker_on_keypress(
     keyboard: Res<ButtonInput<KeyCode>>,
     mut town_hall_query: Query<&mut TownHall>,
     worker_manager: Res<WorkerManager>,
-    time: Res<Time>,
+    facilities: Res<TownFacilities>,
+    town_hall_level: Res<TownHallLevel>,
+    mut token_budget: ResMut<TokenBudget>,
+    config: Res<AppConfig>,
 ) {
-    if keyboard.just_pressed(KeyCode::KeyW) {
-        // Check worker limit
-        match worker_manager.count_workers() {
-            Ok(count) if count >= worker_manager.max_workers => {
-                println!("⚠️ Worker limit reached ({}/{})", count, worker_manager.max_workers);
-                return;
-            }
-            Err(e) => {
-                eprintln!("Failed to count workers: {e}");
-                return;
-            }
-            _ => {}
+    if !keyboard.just_pressed(KeyCode::KeyW) {
+        return;
+    }
+
+    let Ok(mut town_hall) = town_hall_query.get_single_mut() else { return };
+
+    // Check worker limit (set by the Town Hall level, raised by the Barracks). Workers
+    // hired before the cap came from the Town Hall stay, they just block new ones
+    let worker_cap = facilities.worker_cap(town_hall_level.max_workers());
+    let queued = town_hall.worker_production_queue.len();
+    match worker_manager.count_workers() {
+        Ok(count) if count > worker_cap => {
+            println!("⚠️ {count} workers is above the cap of {worker_cap} - they all stay, but no new ones until the cap passes {count}");
+            return;
+        }
+        Ok(count) if count + queued >= worker_cap => {
+            println!("⚠️ Worker limit reached ({count} + {queued} queued / {worker_cap}) - upgrade the Town Hall (U) or grow the Barracks (3)");
+            return;
+        }
+        Err(e) => {
+            eprintln!("Failed to count workers: {e}");
+            return;
         }
+        _ => {}
+    }
+
+    let cost = config.production.worker_token_cost;
+    if token_budget.remaining() < cost {
+        println!("⚠️ Not enough tokens to train a worker ({cost} needed, {} left this period)", token_budget.remaining());
+        return;
+    }
 
-        for mut town_hall in town_hall_query.iter_mut() {
-            town_hall.start_worker_production(time.elapsed_seconds_f64());
-            println!("🏗️ Started worker production (5 seconds)...");
+    let taken = match worker_manager.worker_names() {
+        Ok(names) => names,
+        Err(e) => {
+            eprintln!("Failed to load worker names: {e}");
+            return;
         }
+    };
+
+    let order = town_hall.start_worker_production(&taken, config.production.worker_build_secs, cost);
+    println!("🏗️ Queued worker '{}' ({} tokens, {:.0} seconds) - {} in queue",
+             order.worker_name, cost, config.production.worker_build_secs, queued + 1);
+
+    token_budget.add_flat_usage(cost, "production");
+    if let Err(e) = worker_manager.save_production_queue(&town_hall.worker_production_queue) {
+        eprintln!("Failed to save production queue: {e}");
     }
 }
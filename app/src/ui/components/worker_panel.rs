@@ -0,0 +1,483 @@
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use crate::game::resources::{CliManagerResource, TokenBudget, WorkerManager};
+use crate::game::systems::estimation::MissionEstimator;
+use crate::game::systems::selection::SelectedWorker;
+use crate::game::systems::zac_chat::ZacChat;
+use crate::game::systems::MissionManager;
+use crate::game::systems::zac_journal::{JournalEntryType, JournalManager};
+use crate::game::worker::{specialty, Worker, WorkerState, WorkerVisual};
+
+/// UI panel showing the selected worker and its profile
+#[derive(Component)]
+pub struct WorkerPanelUI;
+
+/// Marker component for the worker panel text
+#[derive(Component)]
+pub struct WorkerPanelText;
+
+/// Profile edit made by a worker panel button
+#[derive(Component, Clone, Copy)]
+pub enum WorkerPanelButton {
+    Model,
+    Persona,
+    Tools,
+    TokensDown,
+    TokensUp,
+    Personality,
+    Prompt,
+}
+
+impl WorkerPanelButton {
+    fn label(&self) -> &str {
+        match self {
+            WorkerPanelButton::Model => "Model",
+            WorkerPanelButton::Persona => "Persona",
+            WorkerPanelButton::Tools => "Tools",
+            WorkerPanelButton::TokensDown => "Tokens -",
+            WorkerPanelButton::TokensUp => "Tokens +",
+            WorkerPanelButton::Personality => "Personality",
+            WorkerPanelButton::Prompt => "Prompt",
+        }
+    }
+}
+
+/// Rename, recolor or remove the selected worker
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerLifecycleButton {
+    Rename,
+    Recolor,
+    Dismiss,
+    Retire,
+}
+
+impl WorkerLifecycleButton {
+    fn label(&self) -> &str {
+        match self {
+            WorkerLifecycleButton::Rename => "Rename",
+            WorkerLifecycleButton::Recolor => "Recolor",
+            WorkerLifecycleButton::Dismiss => "Dismiss",
+            WorkerLifecycleButton::Retire => "Retire",
+        }
+    }
+}
+
+/// Worker text typed in the panel
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum WorkerTextField {
+    #[default]
+    Name,
+    /// Free-text system prompt, replacing the persona
+    SystemPrompt,
+}
+
+/// Resource holding the name or system prompt being typed for a worker
+#[derive(Resource, Default)]
+pub struct WorkerTextEdit {
+    /// Worker being edited, taking keyboard input
+    pub editing: Option<Entity>,
+    pub field: WorkerTextField,
+    pub input: String,
+}
+
+const BUTTON_COLOR: Color = Color::srgb(0.25, 0.3, 0.45);
+const BUTTON_HOVER_COLOR: Color = Color::srgb(0.35, 0.4, 0.6);
+
+/// System to spawn the (initially hidden) worker panel
+pub fn spawn_worker_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            WorkerPanelUI,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(20.0),
+                    top: Val::Px(20.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(10.0)),
+                    row_gap: Val::Px(8.0),
+                    display: Display::None,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.9)),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                WorkerPanelText,
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 16.0,
+                        color: Color::srgb(0.8, 0.8, 0.8),
+                        ..default()
+                    },
+                ),
+            ));
+
+            let buttons = [
+                WorkerPanelButton::Model,
+                WorkerPanelButton::Persona,
+                WorkerPanelButton::Tools,
+                WorkerPanelButton::TokensDown,
+                WorkerPanelButton::TokensUp,
+                WorkerPanelButton::Personality,
+                WorkerPanelButton::Prompt,
+            ];
+
+            for button in buttons {
+                spawn_panel_button(parent, button, button.label());
+            }
+
+            let lifecycle_buttons = [
+                WorkerLifecycleButton::Rename,
+                WorkerLifecycleButton::Recolor,
+                WorkerLifecycleButton::Dismiss,
+                WorkerLifecycleButton::Retire,
+            ];
+
+            for button in lifecycle_buttons {
+                spawn_panel_button(parent, button, button.label());
+            }
+        });
+}
+
+fn spawn_panel_button(parent: &mut ChildBuilder, marker: impl Component, label: &str) {
+    parent
+        .spawn((
+            marker,
+            ButtonBundle {
+                style: Style {
+                    padding: UiRect::all(Val::Px(6.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(BUTTON_COLOR),
+                ..default()
+            },
+        ))
+        .with_children(|b| {
+            b.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font_size: 14.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+        });
+}
+
+/// System to show the selected worker in the panel, hiding it without a selection
+#[allow(clippy::too_many_arguments)]
+pub fn update_worker_panel(
+    selected: Res<SelectedWorker>,
+    text_edit: Res<WorkerTextEdit>,
+    worker_query: Query<&Worker>,
+    mission_manager: Res<MissionManager>,
+    estimator: Res<MissionEstimator>,
+    // The current mission's line, looked up again only when the mission changes
+    mut mission_line: Local<Option<(String, String)>>,
+    mut panel_query: Query<&mut Style, With<WorkerPanelUI>>,
+    mut text_query: Query<&mut Text, With<WorkerPanelText>>,
+) {
+    let worker = selected.0.and_then(|entity| worker_query.get(entity).ok());
+
+    for mut style in panel_query.iter_mut() {
+        let display = if worker.is_some() { Display::Flex } else { Display::None };
+        if style.display != display {
+            style.display = display;
+        }
+    }
+
+    let Some(worker) = worker else { return };
+
+    let profile = &worker.profile;
+    let mut content = format!(
+        "{} ({})\nTasks: {}  Tokens: {}  Cost: ${:.2}\nSpecialties: {}\n\nModel: {}\nPersona: {}\nTools: {}\nLimit: {}\nPersonality: {}",
+        worker.name,
+        worker.state.as_str(),
+        worker.total_tasks_completed,
+        worker.total_tokens_used,
+        worker.total_cost_usd,
+        specialty::summary(&worker.specialty_scores),
+        profile.model_name(),
+        profile.persona_name(),
+        profile.tool_policy.as_str(),
+        profile.token_limit_label(),
+        profile.personality.as_str(),
+    );
+
+    if let Some(mission_id) = &worker.current_task_id {
+        if mission_line.as_ref().map(|(id, _)| id) != Some(mission_id) {
+            let line = match mission_manager.load_mission(mission_id) {
+                Ok(Some(mission)) => format!("{}
+Estimate: {}", mission.title, estimator.estimate(&mission)),
+                _ => "(unknown mission)".to_string(),
+            };
+            *mission_line = Some((mission_id.clone(), line));
+        }
+        if let Some((_, line)) = mission_line.as_ref() {
+            content.push_str(&format!("
+
+Mission: {line}"));
+        }
+    }
+    if text_edit.editing.is_some() {
+        match text_edit.field {
+            WorkerTextField::Name => content.push_str(&format!("\n\nNew name: {}_\n(Enter to save, Esc to cancel)", text_edit.input)),
+            WorkerTextField::SystemPrompt => content.push_str(&format!(
+                "\n\nSystem prompt: {}_\n(Enter to save, empty for none, Esc to cancel)", text_edit.input)),
+        }
+    }
+
+    for mut text in text_query.iter_mut() {
+        if text.sections[0].value != content {
+            text.sections[0].value = content.clone();
+        }
+    }
+}
+
+/// System to apply worker panel button presses to the selected worker's profile;
+/// Prompt starts typing a custom system prompt instead
+pub fn handle_worker_panel_buttons(
+    mut interaction_query: Query<(&Interaction, &WorkerPanelButton, &mut BackgroundColor), Changed<Interaction>>,
+    selected: Res<SelectedWorker>,
+    mut text_edit: ResMut<WorkerTextEdit>,
+    mut worker_query: Query<&mut Worker>,
+    worker_manager: Res<WorkerManager>,
+) {
+    for (interaction, button, mut color) in interaction_query.iter_mut() {
+        *color = BackgroundColor(match interaction {
+            Interaction::None => BUTTON_COLOR,
+            _ => BUTTON_HOVER_COLOR,
+        });
+
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let Some(entity) = selected.0 else { continue };
+        let Ok(mut worker) = worker_query.get_mut(entity) else { continue };
+
+        let profile = &mut worker.profile;
+        match button {
+            WorkerPanelButton::Model => profile.next_model(),
+            WorkerPanelButton::Persona => profile.next_persona(),
+            WorkerPanelButton::Tools => profile.tool_policy = profile.tool_policy.next(),
+            WorkerPanelButton::TokensDown => profile.step_token_limit(false),
+            WorkerPanelButton::TokensUp => profile.step_token_limit(true),
+            WorkerPanelButton::Personality => profile.personality = profile.personality.next(),
+            WorkerPanelButton::Prompt => {
+                text_edit.editing = Some(entity);
+                text_edit.field = WorkerTextField::SystemPrompt;
+                text_edit.input = profile.system_prompt.clone();
+                continue;
+            }
+        }
+
+        match worker_manager.save_profile(&worker.id, &worker.profile) {
+            Ok(()) => println!("👷 Worker '{}' profile: {} / {} / {} tools / {}",
+                               worker.name, worker.profile.model_name(), worker.profile.persona_name(),
+                               worker.profile.tool_policy.as_str(), worker.profile.token_limit_label()),
+            Err(e) => eprintln!("❌ Failed to save worker profile: {e}"),
+        }
+    }
+}
+
+/// System to type a worker's new name or system prompt, keeping keys from game shortcuts.
+/// Enter saves it (names must be unique, and a daily cap set by name follows the rename), Esc cancels.
+#[allow(clippy::too_many_arguments)]
+pub fn worker_text_keyboard_input(
+    mut key_events: EventReader<KeyboardInput>,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut text_edit: ResMut<WorkerTextEdit>,
+    chat: Res<ZacChat>,
+    selected: Res<SelectedWorker>,
+    mut worker_query: Query<&mut Worker>,
+    worker_manager: Res<WorkerManager>,
+    mut token_budget: ResMut<TokenBudget>,
+) {
+    // An open chat takes the keys first
+    let Some(entity) = text_edit.editing.filter(|_| !chat.open) else {
+        key_events.clear();
+        return;
+    };
+
+    // Selecting another worker abandons the edit
+    if selected.0 != Some(entity) {
+        text_edit.editing = None;
+        return;
+    }
+
+    for event in key_events.read().filter(|event| event.state == ButtonState::Pressed) {
+        match &event.logical_key {
+            Key::Escape => text_edit.editing = None,
+            Key::Enter => {
+                let text = text_edit.input.trim().to_string();
+                let Ok(mut worker) = worker_query.get_mut(entity) else {
+                    text_edit.editing = None;
+                    continue;
+                };
+
+                if text_edit.field == WorkerTextField::SystemPrompt {
+                    worker.profile.system_prompt = text;
+                    match worker_manager.save_profile(&worker.id, &worker.profile) {
+                        Ok(()) => {
+                            println!("👷 Worker '{}' persona: {}", worker.name, worker.profile.persona_name());
+                            text_edit.editing = None;
+                        }
+                        Err(e) => eprintln!("❌ Failed to save worker profile: {e}"),
+                    }
+                    continue;
+                }
+
+                let name = text;
+                if name.is_empty() {
+                    println!("⚠️ A worker needs a name");
+                    continue;
+                }
+
+                match worker_manager.rename_worker(&worker.id, &name) {
+                    Ok(()) => {
+                        println!("👷 Worker '{}' is now called '{name}'", worker.name);
+                        if let Some(cap) = token_budget.workers.rename(&worker.name, &name) {
+                            println!("   Its daily cap of {cap} tokens carries over - key it by '{name}' or its id '{}' in [tokens.worker_caps] to keep it after a restart",
+                                     worker.id);
+                        }
+                        worker.name = name;
+                        text_edit.editing = None;
+                    }
+                    Err(e) => eprintln!("❌ Failed to rename worker: {e}"),
+                }
+            }
+            Key::Backspace => {
+                text_edit.input.pop();
+            }
+            Key::Space => text_edit.input.push(' '),
+            Key::Character(text) => {
+                text_edit.input.extend(text.chars().filter(|c| !c.is_control()));
+            }
+            _ => {}
+        }
+    }
+
+    // Keys typed into the text don't reach the rest of the game
+    keyboard.reset_all();
+}
+
+/// System for the lifecycle buttons: rename and recolor the selected worker,
+/// or dismiss/retire it. A working worker needs a second press, which cancels its mission;
+/// a reflecting worker can't be removed until it is done.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_worker_lifecycle_buttons(
+    mut commands: Commands,
+    mut interaction_query: Query<(&Interaction, &WorkerLifecycleButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut selected: ResMut<SelectedWorker>,
+    mut text_edit: ResMut<WorkerTextEdit>,
+    mut worker_query: Query<(&mut Worker, &Children)>,
+    visual_query: Query<&Handle<StandardMaterial>, With<WorkerVisual>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    worker_manager: Res<WorkerManager>,
+    mission_manager: Res<MissionManager>,
+    cli_manager: Res<CliManagerResource>,
+    journal: Res<JournalManager>,
+    mut pending: Local<Option<(Entity, WorkerLifecycleButton)>>,
+) {
+    for (interaction, button, mut color) in interaction_query.iter_mut() {
+        *color = BackgroundColor(match interaction {
+            Interaction::None => BUTTON_COLOR,
+            _ => BUTTON_HOVER_COLOR,
+        });
+
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let Some(entity) = selected.0 else { continue };
+        let Ok((mut worker, children)) = worker_query.get_mut(entity) else { continue };
+
+        match button {
+            WorkerLifecycleButton::Rename => {
+                *pending = None;
+                text_edit.editing = Some(entity);
+                text_edit.field = WorkerTextField::Name;
+                text_edit.input = worker.name.clone();
+            }
+            WorkerLifecycleButton::Recolor => {
+                *pending = None;
+                let color = Worker::random_color();
+
+                match worker_manager.recolor_worker(&worker.id, color) {
+                    Ok(()) => {
+                        worker.color = color;
+                        for handle in children.iter().filter_map(|child| visual_query.get(*child).ok()) {
+                            if let Some(material) = materials.get_mut(handle) {
+                                material.base_color = color;
+                            }
+                        }
+                        println!("🎨 Worker '{}' recolored", worker.name);
+                    }
+                    Err(e) => eprintln!("❌ Failed to recolor worker: {e}"),
+                }
+            }
+            WorkerLifecycleButton::Dismiss | WorkerLifecycleButton::Retire => {
+                if worker.state == WorkerState::Reflecting {
+                    *pending = None;
+                    println!("⚠️ '{}' is reflecting on its last mission - {} it once it is idle",
+                             worker.name, button.label().to_lowercase());
+                    continue;
+                }
+
+                let working = matches!(worker.state, WorkerState::Working { .. });
+
+                if working && *pending != Some((entity, *button)) {
+                    *pending = Some((entity, *button));
+                    println!("⚠️ '{}' is working on a mission - press {} again to cancel it",
+                             worker.name, button.label());
+                    continue;
+                }
+                *pending = None;
+
+                if working {
+                    let cancelled = cli_manager.manager.lock().ok()
+                        .and_then(|mut cli| cli.cancel_worker(&worker.id));
+                    if let Some(mission_id) = cancelled {
+                        let _ = mission_manager.reset_mission(&mission_id);
+                        println!("🛑 Cancelled the mission '{}' was working on", worker.name);
+
+                        let mission = mission_manager.load_mission(&mission_id).ok().flatten();
+                        journal.record(JournalEntryType::UserOverride,
+                                       &format!("Cancelled mission '{}' by removing worker '{}' - it goes back in the queue",
+                                                mission.as_ref().map_or(mission_id.as_str(), |m| m.title.as_str()), worker.name),
+                                       mission.as_ref().map(|m| m.project_id.as_str()), Some(&mission_id));
+                    }
+                }
+
+                let result = if *button == WorkerLifecycleButton::Retire {
+                    worker_manager.retire_worker(&worker.id)
+                } else {
+                    worker_manager.dismiss_worker(&worker.id)
+                };
+
+                match result {
+                    Ok(()) => {
+                        commands.entity(entity).despawn_recursive();
+                        selected.0 = None;
+
+                        if *button == WorkerLifecycleButton::Retire {
+                            println!("🏆 '{}' retired to the hall of fame ({} tasks, {} tokens)",
+                                     worker.name, worker.total_tasks_completed, worker.total_tokens_used);
+                        } else {
+                            println!("👋 '{}' was dismissed", worker.name);
+                        }
+                    }
+                    Err(e) => eprintln!("❌ Failed to remove worker: {e}"),
+                }
+            }
+        }
+    }
+}
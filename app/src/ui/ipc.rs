@@ -1,5 +1,12 @@
+use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+/// Commands from the UI to the game world
+#[derive(Event, Debug, Clone)]
+pub enum UiCommand {
+    ChatWithZac { message: String },
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct GameStats {
     pub workers: u32,
@@ -14,13 +21,6 @@ pub fn select_entity(_entity_id: String) -> Result<(), String> {
     Ok(())
 }
 
-#[allow(dead_code)]
-#[tauri::command]
-pub fn send_chat_message(_message: String) -> Result<String, String> {
-    // Placeholder - will be implemented in M2
-    Ok("Received".to_string())
-}
-
 #[allow(dead_code)]
 #[tauri::command]
 pub fn get_game_stats() -> Result<GameStats, String> {
@@ -8,4 +8,23 @@ pub use components::{
     update_stage_display,
     button_hover_system,
     spawn_worker_on_keypress,
+    spawn_production_queue_panel,
+    rebuild_production_queue_list,
+    update_production_progress,
+    handle_production_order_buttons,
+    spawn_worker_panel,
+    update_worker_panel,
+    handle_worker_panel_buttons,
+    handle_worker_lifecycle_buttons,
+    worker_text_keyboard_input,
+    WorkerTextEdit,
+    spawn_chat_panel,
+    chat_keyboard_input,
+    update_chat_panel,
+    spawn_journal_panel,
+    toggle_journal_keypress,
+    handle_journal_buttons,
+    update_journal_panel,
+    JournalView,
 };
+pub use ipc::UiCommand;